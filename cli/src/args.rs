@@ -0,0 +1,20 @@
+/// Find the value of the option with the given name in the passed arguments.
+/// Supports both the '--name value' and '--name=value' notation.
+pub(crate) fn find_option_value(args: &[String], name: &str) -> Option<String> {
+    let flag = format!("--{}", name);
+    let prefix = format!("{}=", flag);
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if *arg == flag {
+            return iter.next().filter(|v| !v.is_empty()).map(|v| v.to_owned());
+        } else if arg.starts_with(&prefix) {
+            let value = &arg[prefix.len()..];
+            if !value.is_empty() {
+                return Some(value.to_owned());
+            }
+        }
+    }
+
+    None
+}
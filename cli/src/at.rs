@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use cmd_args::{option, Group};
+
+use crate::error;
+
+/// Name of the option specifying when something happened.
+const AT_OPTION: &str = "at";
+
+/// Add the option specifying when something happened to the given command group.
+pub(crate) fn add_option(group: Group) -> Group {
+    group.add_option(option::Descriptor::new(
+        AT_OPTION,
+        option::Type::Str {
+            default: String::from(""),
+        },
+        "When it happened instead of now (e. g. '09:15', 'yesterday 17:00', '-20m' or '2021-01-31T17:00')",
+    ))
+}
+
+/// Get the timestamp passed using the option added with `add_option`.
+/// Returns None if the option is not given, which means now.
+/// Exits when the value cannot be interpreted or lies in the future.
+pub(crate) fn timestamp_from_options(options: &HashMap<&str, option::Value>) -> Option<i64> {
+    let value = options
+        .get(AT_OPTION)
+        .and_then(|v| v.str())
+        .filter(|v| !v.trim().is_empty())?;
+
+    let timestamp =
+        shared::time::parse_time_expression(value).unwrap_or_else(|e| error::exit_with_message(&e));
+
+    if timestamp > chrono::Utc::now().timestamp_millis() {
+        error::exit_with_message(&format!("The time '{}' lies in the future", value))
+    }
+
+    Some(timestamp)
+}
@@ -3,15 +3,16 @@ use std::collections::HashMap;
 use cmd_args::{arg, option, Group};
 
 use crate::command::command::Command;
+use crate::error;
 
-/// Command used to clear the database (remove all work items).
+/// Command used to clear the database (move all work items to the trash).
 pub struct ClearCommand {}
 
 impl Command for ClearCommand {
     fn build(&self) -> Group {
         Group::new(
             Box::new(|args, options| execute(args, options)),
-            "Clear the database (Remove all work items)",
+            "Clear the database (Move all work items to the trash)",
         )
         .add_option(option::Descriptor::new(
             "ack",
@@ -34,12 +35,11 @@ fn execute(_args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     let acknowlegement = options.get("ack").unwrap().bool().unwrap();
 
     if acknowlegement {
-        match persistence::clear() {
-            Ok(_) => println!("Cleared the database (Removed all work items)."),
-            Err(e) => println!("Could not clear the database. Error: '{}'.", e),
-        }
+        persistence::clear().unwrap_or_else(error::exit_with);
+
+        println!("Cleared the database (Moved all work items to the trash).");
     } else {
-        println!("Do you really want to clear the database (Remove all work items)?");
+        println!("Do you really want to clear the database (Move all work items to the trash)?");
         println!("Please acknowledge the operation by re-entering the clear command followed by the --ack flag");
     }
 }
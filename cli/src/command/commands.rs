@@ -3,16 +3,26 @@ use crate::command::command::Command;
 use crate::command::continue_cmd::ContinueCommand;
 use crate::command::delete::DeleteCommand;
 use crate::command::edit::EditCommand;
+use crate::command::edit_events::EditEventsCommand;
 use crate::command::export::ExportCommand;
 use crate::command::finish::FinishCommand;
+use crate::command::history::HistoryCommand;
+use crate::command::import::ImportCommand;
 use crate::command::list::ListCommand;
 use crate::command::log::LogCommand;
+use crate::command::note::NoteCommand;
 use crate::command::pause::PauseCommand;
+use crate::command::project::ProjectCommand;
+use crate::command::redo::RedoCommand;
+use crate::command::search::SearchCommand;
 use crate::command::show::ShowCommand;
 use crate::command::start::StartCommand;
+use crate::command::stats::StatsCommand;
+use crate::command::trash::TrashCommand;
+use crate::command::undo::UndoCommand;
 
 /// All available commands.
-pub(crate) const COMMANDS: [&dyn Command; 11] = [
+pub(crate) const COMMANDS: [&dyn Command; 21] = [
     &ListCommand {},
     &LogCommand {},
     &StartCommand {},
@@ -20,8 +30,18 @@ pub(crate) const COMMANDS: [&dyn Command; 11] = [
     &PauseCommand {},
     &ContinueCommand {},
     &ExportCommand {},
+    &ImportCommand {},
     &DeleteCommand {},
     &EditCommand {},
+    &EditEventsCommand {},
     &ClearCommand {},
     &ShowCommand {},
+    &SearchCommand {},
+    &StatsCommand {},
+    &ProjectCommand {},
+    &NoteCommand {},
+    &TrashCommand {},
+    &UndoCommand {},
+    &RedoCommand {},
+    &HistoryCommand {},
 ];
@@ -2,23 +2,25 @@ use std::collections::HashMap;
 
 use cmd_args::{arg, option, Group};
 
-use persistence::calc::Status;
-
+use crate::at;
 use crate::command::command::Command;
+use crate::error;
 
 /// Command used to continue working on an in progress work item.
 pub struct ContinueCommand {}
 
 impl Command for ContinueCommand {
     fn build(&self) -> Group {
-        Group::new(
-            Box::new(|args, options| execute(args, options)),
-            "Continue working on an in progress work item",
+        at::add_option(
+            Group::new(
+                Box::new(|args, options| execute(args, options)),
+                "Continue working on an in progress work item",
+            )
+            .add_argument(arg::Descriptor::new(
+                arg::Type::Int,
+                "ID of the work item to continue working on",
+            )),
         )
-        .add_argument(arg::Descriptor::new(
-            arg::Type::Int,
-            "ID of the work item to continue working on",
-        ))
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -31,33 +33,16 @@ impl Command for ContinueCommand {
 }
 
 /// Execute the continue command.
-fn execute(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     let id = args[0]
         .int()
         .expect("Expected an ID of a work item as first argument");
 
-    match persistence::find_item_by_id(id) {
-        Ok(option) => match option {
-            Some(mut item) => {
-                if let Status::Paused = item.status() {
-                    item.continue_working().unwrap();
-
-                    match persistence::update_items(vec![&item]) {
-                        Ok(_) => println!("Continued work item with ID {}.", id),
-                        Err(e) => println!(
-                            "Failed to continue work item with ID {}. Error: '{}'",
-                            id, e
-                        ),
-                    };
-                } else {
-                    println!("Work item with ID {} is currently not paused and thus cannot be continued working on.", id);
-                }
-            }
-            None => println!("Could not find work item with ID {}.", id),
-        },
-        Err(e) => println!(
-            "An error occurred while trying to lookup the work item with ID {}. Error: '{}'",
-            id, e
-        ),
-    };
+    let mut item = persistence::get_item_by_id(id).unwrap_or_else(error::exit_with);
+
+    item.continue_working(at::timestamp_from_options(options))
+        .unwrap_or_else(error::exit_with);
+    persistence::update_items(vec![&item]).unwrap_or_else(error::exit_with);
+
+    println!("Continued work item with ID {}.", id);
 }
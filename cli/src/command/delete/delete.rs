@@ -2,16 +2,19 @@ use std::collections::HashMap;
 
 use cmd_args::{arg, option, Group};
 
+use persistence::Error;
+
 use crate::command::command::Command;
+use crate::error;
 
-/// Command used to delete a work item.
+/// Command used to delete a work item (by moving it to the trash).
 pub struct DeleteCommand {}
 
 impl Command for DeleteCommand {
     fn build(&self) -> Group {
         Group::new(
             Box::new(|args, options| execute(args, options)),
-            "Delete a work item",
+            "Delete a work item (moves it to the trash)",
         )
         .add_argument(arg::Descriptor::new(
             arg::Type::Int,
@@ -32,14 +35,11 @@ impl Command for DeleteCommand {
 fn execute(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
     let id = args[0].int().expect("Expected to have an ID supplied");
 
-    match persistence::delete_item(id) {
-        Ok(item) => match item {
-            Some(_) => println!("Work item with ID {} has been deleted.", id),
-            None => println!("There is no work item with ID {}.", id),
-        },
-        Err(e) => println!(
-            "An error occurred trying to delete work item with ID {}. Error: '{}'.",
-            id, e
+    match persistence::delete_item(id).unwrap_or_else(error::exit_with) {
+        Some(_) => println!(
+            "Work item with ID {} has been moved to the trash (restore it using 'worklog trash restore {}').",
+            id, id
         ),
+        None => error::exit_with(Error::NotFound(id)),
     };
 }
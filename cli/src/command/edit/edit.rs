@@ -1,8 +1,10 @@
 use crate::command::command::Command;
+use crate::error;
+use crate::project;
 use cmd_args::{arg, option, Group};
 use persistence::calc::WorkItem;
+use persistence::Error;
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 use std::iter::FromIterator;
 
 /// Command used to export work items.
@@ -10,7 +12,7 @@ pub struct EditCommand {}
 
 impl Command for EditCommand {
     fn build(&self) -> Group {
-        Group::new(
+        let group = Group::new(
             Box::new(|args, options| execute(args, options)),
             "Edit a work item",
         )
@@ -31,7 +33,9 @@ impl Command for EditCommand {
                 default: String::from(""),
             },
             "New tags for the work item",
-        ))
+        ));
+
+        project::add_option(group)
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -75,19 +79,13 @@ fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
         })
         .unwrap();
 
-    match persistence::find_item_by_id(id) {
-        Ok(item) => match item {
-            Some(mut item) => match update_work_item(&mut item, description, tags) {
-                Ok(_) => println!("Updated work item with ID {}.", id),
-                Err(e) => println!("Could not edit work item with ID {}. Error: '{}'.", id, e),
-            },
-            None => println!("Could not find work item with ID {}.", id),
-        },
-        Err(e) => println!(
-            "An error occurred while trying to access the work item with ID {}. Error: '{}'",
-            id, e
-        ),
-    };
+    let project = project::assignment_from_options(options);
+
+    let mut item = persistence::get_item_by_id(id).unwrap_or_else(error::exit_with);
+
+    update_work_item(&mut item, description, tags, project).unwrap_or_else(error::exit_with);
+
+    println!("Updated work item with ID {}.", id);
 }
 
 /// Update the passed work item with the given optional changes.
@@ -95,7 +93,8 @@ fn update_work_item(
     item: &mut WorkItem,
     description: Option<String>,
     tags: Option<Vec<String>>,
-) -> Result<(), Box<dyn Error>> {
+    project: Option<Option<String>>,
+) -> Result<(), Error> {
     if description.is_some() {
         item.set_description(description.unwrap());
     }
@@ -104,6 +103,10 @@ fn update_work_item(
         item.set_tags(HashSet::from_iter(tags.unwrap().into_iter()));
     }
 
+    if let Some(project) = project {
+        item.set_project(project);
+    }
+
     // Persist changes
     persistence::update_items(vec![item])
 }
@@ -0,0 +1,237 @@
+use crate::command::command::Command;
+use crate::error;
+use cmd_args::{arg, option, Group};
+use colorful::Colorful;
+use persistence::calc::event::{Event, EventType};
+use persistence::calc::WorkItem;
+use std::collections::HashMap;
+
+/// Separator between the start and end of a pause range whose times contain a date or duration.
+const RANGE_SEPARATOR: &str = "..";
+
+/// Command used to edit the event timeline of a work item.
+pub struct EditEventsCommand {}
+
+impl Command for EditEventsCommand {
+    fn build(&self) -> Group {
+        Group::new(
+            Box::new(|args, options| execute(args, options)),
+            "Edit the events (start, pauses, finish) of a work item",
+        )
+        .add_argument(arg::Descriptor::new(
+            arg::Type::Int,
+            "ID of the work item to edit the events of",
+        ))
+        .add_option(option::Descriptor::new(
+            "set-start",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Move the start of the work item to the given time (e. g. '09:15', 'yesterday 08:30' or '2021-01-31T09:15')",
+        ))
+        .add_option(option::Descriptor::new(
+            "set-finish",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Move the finish of the work item to the given time or finish it at that time (e. g. '17:30', '-20m' or '2021-01-31T17:30')",
+        ))
+        .add_option(option::Descriptor::new(
+            "add-pause",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Add a pause in the given time range (e. g. '12:00-12:45' or 'yesterday 12:00..yesterday 12:45')",
+        ))
+        .add_option(option::Descriptor::new(
+            "remove-pause",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Remove the pause with the given number (e. g. '2' for the second pause)",
+        ))
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        Some(vec!["events"])
+    }
+
+    fn name(&self) -> &str {
+        "edit-events"
+    }
+}
+
+/// Execute the edit-events command.
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let id = args[0]
+        .int()
+        .expect("Expected to have an ID supplied as first argument");
+
+    let mut item = persistence::get_item_by_id(id).unwrap_or_else(error::exit_with);
+    let mut changed = false;
+
+    if let Some(number_str) = option_value(options, "remove-pause") {
+        let number = number_str
+            .parse::<usize>()
+            .unwrap_or_else(|_| error::exit_with_message("The pause number must be a number"));
+
+        remove_pause(&mut item, number);
+        changed = true;
+    }
+
+    if let Some(range_str) = option_value(options, "add-pause") {
+        add_pause(&mut item, range_str);
+        changed = true;
+    }
+
+    if let Some(time_str) = option_value(options, "set-start") {
+        let timestamp = parse_time(time_str, item.created_timestamp());
+
+        item.move_event(0, timestamp)
+            .unwrap_or_else(error::exit_with);
+        changed = true;
+    }
+
+    if let Some(time_str) = option_value(options, "set-finish") {
+        set_finish(&mut item, time_str);
+        changed = true;
+    }
+
+    if changed {
+        persistence::update_items(vec![&item]).unwrap_or_else(error::exit_with);
+        println!("Updated the events of work item with ID {}.", id);
+    }
+
+    print_events(&item);
+}
+
+/// Get the value of the option with the given name (if not empty).
+fn option_value<'a>(options: &'a HashMap<&str, option::Value>, name: &str) -> Option<&'a str> {
+    options
+        .get(name)
+        .and_then(|v| v.str())
+        .filter(|v| !v.trim().is_empty())
+}
+
+/// Remove the pause with the given number (starting with 1) together with its continue event.
+fn remove_pause(item: &mut WorkItem, number: usize) {
+    let index = item
+        .events()
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.event_type() == EventType::Paused)
+        .map(|(index, _)| index)
+        .nth(number.wrapping_sub(1))
+        .unwrap_or_else(|| {
+            error::exit_with_message(&format!("The work item has no pause number {}", number))
+        });
+
+    item.edit_events(|events| {
+        let continued = events
+            .get(index + 1)
+            .is_some_and(|e| e.event_type() == EventType::Continued);
+
+        events.drain(index..if continued { index + 2 } else { index + 1 });
+    })
+    .unwrap_or_else(error::exit_with);
+}
+
+/// Add a pause in the given time range (e. g. '12:00-12:45' or 'yesterday 12:00..yesterday 12:45').
+/// Times containing a date or a duration must be separated by '..'.
+fn add_pause(item: &mut WorkItem, range_str: &str) {
+    let mut parts = match range_str.contains(RANGE_SEPARATOR) {
+        true => range_str.splitn(2, RANGE_SEPARATOR),
+        false => range_str.splitn(2, "-"),
+    };
+    let (from_str, to_str) = match (parts.next(), parts.next()) {
+        (Some(from_str), Some(to_str)) => (from_str, to_str),
+        _ => error::exit_with_message(&format!(
+            "Could not interpret '{}' as a time range. Use a format like '12:00-12:45' or 'yesterday 12:00..yesterday 12:45'",
+            range_str
+        )),
+    };
+
+    let from_timestamp = parse_time(from_str, item.created_timestamp());
+    let to_timestamp = parse_time(to_str, item.created_timestamp());
+
+    item.edit_events(|events| {
+        insert_sorted(events, Event::new(EventType::Paused, from_timestamp));
+        insert_sorted(events, Event::new(EventType::Continued, to_timestamp));
+    })
+    .unwrap_or_else(error::exit_with);
+}
+
+/// Move the finish event to the given time or finish the work item at that time.
+fn set_finish(item: &mut WorkItem, time_str: &str) {
+    let last_index = item.events().len() - 1;
+    let last_event = item.events()[last_index];
+
+    match last_event.event_type() {
+        EventType::Finished => {
+            // Interpret the time on the day of the event before the finish
+            let reference = item.events()[last_index.max(1) - 1].timestamp();
+            let timestamp = parse_time(time_str, reference);
+
+            item.move_event(last_index, timestamp)
+        }
+        _ => {
+            let timestamp = parse_time(time_str, last_event.timestamp());
+
+            item.insert_event(Event::new(EventType::Finished, timestamp))
+        }
+    }
+    .unwrap_or_else(error::exit_with);
+}
+
+/// Insert the given event after all events with a lower or equal timestamp.
+fn insert_sorted(events: &mut Vec<Event>, event: Event) {
+    let index = events
+        .iter()
+        .position(|e| e.timestamp() > event.timestamp())
+        .unwrap_or(events.len());
+
+    events.insert(index, event);
+}
+
+/// Parse the given time on the day of the passed reference timestamp if it is a bare time (HH:MM).
+/// Any other time expression (with a date, a relative day or a duration) is resolved like `--at`.
+fn parse_time(time_str: &str, reference_timestamp: i64) -> i64 {
+    match chrono::NaiveTime::parse_from_str(time_str.trim(), "%H:%M") {
+        Ok(time) => {
+            let date = shared::time::get_local_date_time(reference_timestamp).date_naive();
+
+            shared::time::timestamp_at(date, time)
+        }
+        Err(_) => shared::time::parse_time_expression(time_str)
+            .unwrap_or_else(|e| error::exit_with_message(&e)),
+    }
+}
+
+/// Print the numbered events of the work item.
+fn print_events(item: &WorkItem) {
+    println!();
+    println!(
+        "{}",
+        format!("# Events of work item with ID {}", item.id().unwrap()).underlined()
+    );
+    println!();
+
+    let mut pause_number = 0;
+    for event in item.events() {
+        let label = match event.event_type() {
+            EventType::Paused => {
+                pause_number += 1;
+                format!("{} #{}", event.event_type(), pause_number)
+            }
+            _ => format!("{}", event.event_type()),
+        };
+
+        println!(
+            "  • [{}] at {}",
+            label.color(colorful::Color::DarkSlateGray1),
+            shared::time::get_local_date_time(event.timestamp()).format("%H:%M:%S - %A, %Y-%m-%d")
+        );
+    }
+
+    println!();
+}
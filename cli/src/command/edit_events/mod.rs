@@ -0,0 +1,3 @@
+mod edit_events;
+
+pub use edit_events::EditEventsCommand;
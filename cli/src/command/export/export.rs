@@ -1,38 +1,80 @@
 use crate::command::command::Command;
 use crate::command::list;
+use crate::error;
 use cmd_args::{arg, option, Group};
-use persistence::calc::event::EventType;
-use persistence::calc::WorkItem;
+use export::{Exporter, ReportContext};
+use persistence::query::ItemQuery;
 use std::collections::HashMap;
 use std::fs;
+use std::io::{self, Write};
+
+/// Path denoting the standard output instead of a file.
+const STDOUT_PATH: &str = "-";
+
+/// Export format of backups, which contain all work items unless filtered explicitly.
+const BACKUP_FORMAT: &str = "json";
+
+/// Filter used by the other export formats when none is given.
+const DEFAULT_FILTER: &str = "today";
 
 /// Command used to export work items.
 pub struct ExportCommand {}
 
 impl Command for ExportCommand {
     fn build(&self) -> Group {
-        Group::new(
+        let group = Group::new(
             Box::new(|args, options| execute(args, options)),
             "Export work log entries",
         )
         .add_argument(arg::Descriptor::new(
             arg::Type::Str,
-            "Export type (e. g. 'markdown')",
+            "Export format ('markdown', 'csv', 'json', 'ics', 'html' or 'template')",
         ))
         .add_option(option::Descriptor::new(
             "path",
             option::Type::Str {
-                default: String::from("log_export.md"),
+                default: String::from(""),
             },
-            "Path of the file to export to",
+            "Path of the file to export to (defaults to 'log_export.<extension>', '-' for the standard output)",
+        ))
+        .add_option(option::Descriptor::new(
+            "all",
+            option::Type::Bool { default: false },
+            "Export all work items regardless of the filter (the default for the 'json' backup)",
         ))
         .add_option(option::Descriptor::new(
             "filter",
             option::Type::Str {
-                default: String::from("today"),
+                default: String::from(""),
+            },
+            "Filter by a date ('today' (default except for the 'json' backup), 'yesterday', '2020-02-20' (yyyy-MM-dd)), a week ('this-week', 'last-week', '2021-W05'), a month ('this-month', 'last-month', '2021-01') or a range ('2021-01-01..2021-01-31')",
+        ))
+        .add_option(option::Descriptor::new(
+            "delimiter",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Delimiter of the CSV export (defaults to ',', 'tab' for a tab character)",
+        ))
+        .add_option(option::Descriptor::new(
+            "per-item",
+            option::Type::Bool { default: false },
+            "Write one row per work item instead of one per active interval (CSV export)",
+        ))
+        .add_option(option::Descriptor::new(
+            "template",
+            option::Type::Str {
+                default: String::from(""),
             },
-            "Filter by a date ('today' (default), 'yesterday', '2020-02-20' (yyyy-MM-dd))",
+            "Path of the Tera template to render (defaults to the markdown layout, 'template' export)",
         ))
+        .add_option(option::Descriptor::new(
+            "notes",
+            option::Type::Bool { default: false },
+            "Include the notes of the work items ('markdown' and 'template' export)",
+        ));
+
+        list::add_query_options(group)
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -46,122 +88,153 @@ impl Command for ExportCommand {
 
 /// Execute the export command.
 fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
-    let export_type = args[0].str().unwrap();
-
-    if export_type.trim().to_lowercase() == "markdown" {
-        export_to_markdown(
-            options.get("path").unwrap().str().unwrap(),
-            options
-                .get("filter")
-                .unwrap()
-                .str()
-                .map_or(String::from("today"), |v| v.to_owned()),
-        );
-    } else {
-        panic!("Export type '{}' currently not supported", export_type);
-    }
-}
+    let format = args[0].str().unwrap();
 
-/// Export to a markdown file with the given file path.
-fn export_to_markdown(file_path: &str, filter: String) {
-    let (from_timestamp, to_timestamp) = list::filter_keyword_to_time_range(&filter[..]);
-    let items = persistence::find_items_by_timerange(from_timestamp, to_timestamp).unwrap();
+    let exporter = export::find_exporter(format).unwrap_or_else(|| {
+        let formats: Vec<&str> = export::EXPORTERS.iter().map(|e| e.name()).collect();
 
-    let first_item = items.first().unwrap();
-    let date_time = shared::time::get_local_date_time(first_item.created_timestamp());
-
-    let mut data = String::new();
-
-    data.push_str(&format!(
-        "# Report for {} the {}\n\n",
-        date_time.format("%A").to_string(),
-        date_time.format("%Y-%m-%d").to_string()
-    ));
+        error::exit_with_message(&format!(
+            "Export format '{}' is not supported. Use one of '{}'.",
+            format,
+            formats.join("', '")
+        ))
+    });
+
+    let path = options
+        .get("path")
+        .and_then(|v| v.str())
+        .filter(|v| !v.is_empty())
+        .map_or_else(
+            || {
+                let extension = options
+                    .get("template")
+                    .and_then(|v| v.str())
+                    .and_then(export::template::file_extension_of)
+                    .unwrap_or_else(|| exporter.file_extension());
+
+                format!("log_export.{}", extension)
+            },
+            |v| v.to_owned(),
+        );
+    let all = options.get("all").and_then(|v| v.bool()).unwrap_or(false);
+    let filter = options
+        .get("filter")
+        .and_then(|v| v.str())
+        .filter(|v| !v.is_empty());
+    let filter = match (all, filter) {
+        (true, _) => None,
+        (false, Some(filter)) => Some(filter),
+        (false, None) if exporter.name() == BACKUP_FORMAT => None,
+        (false, None) => Some(DEFAULT_FILTER),
+    };
 
-    data.push_str("## Statistics\n\n");
+    let context_options = build_context_options(options);
+    let include_notes = options.get("notes").and_then(|v| v.bool()).unwrap_or(false);
+
+    export_items(
+        exporter,
+        &path,
+        filter,
+        list::build_query(options),
+        &context_options,
+        include_notes,
+    );
+}
 
-    let total_work_time = {
-        let item_refs: Vec<&WorkItem> = items.iter().collect();
-        let total_work_time_ms = list::calculate_total_work_time(&item_refs);
+/// Collect the format specific options of the report context from the passed command options.
+fn build_context_options(options: &HashMap<&str, option::Value>) -> Vec<(&'static str, String)> {
+    let mut context_options = Vec::new();
 
-        shared::time::format_duration((total_work_time_ms / 1000) as u32)
-    };
-    let start_time =
-        shared::time::get_local_date_time(find_earliest_work_item(&items).created_timestamp())
-            .format("%H:%M")
-            .to_string();
-    let end_time =
-        shared::time::get_local_date_time(find_latest_work_item(&items).created_timestamp())
-            .format("%H:%M")
-            .to_string();
-
-    data.push_str(&format!(
-        "\
-| Total time worked | Started working | Finished working |
-| ----------------- | --------------- | ---------------- |
-| {} | {} | {} |\n\n",
-        total_work_time, start_time, end_time
-    ));
-
-    data.push_str("## Work items\n\n");
-
-    for item in &items {
-        data.push_str(&format!(
-            "- {}. Took `{}` ({}). Tags: *{}*.\n",
-            item.description(),
-            shared::time::format_duration((item.time_taken() / 1000) as u32),
-            format_event_timeline(item),
-            item.tags().join(", ")
-        ));
+    if let Some(delimiter) = options
+        .get("delimiter")
+        .and_then(|v| v.str())
+        .filter(|v| !v.is_empty())
+    {
+        context_options.push((export::csv::DELIMITER_OPTION, delimiter.to_owned()));
     }
 
-    fs::write(file_path, data).expect("Unable to write export file");
-}
+    if options
+        .get("per-item")
+        .and_then(|v| v.bool())
+        .unwrap_or(false)
+    {
+        context_options.push((export::csv::MODE_OPTION, String::from("items")));
+    }
 
-fn format_event_timeline(item: &WorkItem) -> String {
-    let mut result = Vec::new();
-
-    let mut start_time = None;
-    for event in item.events() {
-        match event.event_type() {
-            EventType::Started | EventType::Continued => {
-                start_time = Some(shared::time::get_local_date_time(event.timestamp()))
-            }
-            EventType::Finished | EventType::Paused => result.push(format!(
-                "{} - {}",
-                start_time.unwrap().format("%H:%M"),
-                shared::time::get_local_date_time(event.timestamp()).format("%H:%M")
-            )),
-        };
+    if let Some(template) = options
+        .get("template")
+        .and_then(|v| v.str())
+        .filter(|v| !v.is_empty())
+    {
+        context_options.push((export::template::TEMPLATE_OPTION, template.to_owned()));
     }
 
-    result.join(", ")
+    context_options
 }
 
-/// Find the latest work item among the list of passed work item references.
-fn find_latest_work_item(items: &[WorkItem]) -> &WorkItem {
-    let mut lastest_ts: i64 = items.first().unwrap().events().last().unwrap().timestamp();
-    let mut latest_item = items.first().unwrap();
-    for item in &items[1..] {
-        if item.events().last().unwrap().timestamp() > lastest_ts {
-            lastest_ts = item.events().last().unwrap().timestamp();
-            latest_item = item;
+/// Export the work items matching the given filter (or all when there is none)
+/// and query using the passed exporter.
+fn export_items(
+    exporter: &dyn Exporter,
+    path: &str,
+    filter: Option<&str>,
+    query: ItemQuery,
+    context_options: &[(&str, String)],
+    include_notes: bool,
+) {
+    let (items, from_timestamp, to_timestamp) = match filter {
+        Some(filter) => {
+            let (from_timestamp, to_timestamp) = shared::time::resolve_date_range(filter)
+                .unwrap_or_else(|e| error::exit_with_message(&e));
+            let items = persistence::query_items(&query.time_range(from_timestamp, to_timestamp))
+                .unwrap_or_else(error::exit_with);
+
+            (items, from_timestamp, to_timestamp)
         }
+        None => {
+            let items = persistence::query_items(&query).unwrap_or_else(error::exit_with);
+            let from_timestamp = items
+                .iter()
+                .map(|item| item.created_timestamp())
+                .min()
+                .unwrap_or(0);
+
+            (items, from_timestamp, i64::MAX)
+        }
+    };
+    if items.is_empty() {
+        println!("There are no work items to export.");
+        return;
     }
 
-    latest_item
-}
+    let context = context_options.iter().fold(
+        ReportContext::new(from_timestamp, to_timestamp)
+            .with_projects(persistence::list_projects().unwrap_or_else(error::exit_with))
+            .with_notes(include_notes),
+        |c, (name, value)| c.with_option(name, value),
+    );
 
-/// Find the earliest work item among the list of passed work item references.
-fn find_earliest_work_item(items: &[WorkItem]) -> &WorkItem {
-    let mut earliest_ts: i64 = items.first().unwrap().created_timestamp();
-    let mut earliest_item = items.first().unwrap();
-    for item in &items[1..] {
-        if item.created_timestamp() < earliest_ts {
-            earliest_ts = item.created_timestamp();
-            earliest_item = item;
-        }
-    }
+    let result = if path == STDOUT_PATH {
+        let stdout = io::stdout();
+        let mut writer = stdout.lock();
 
-    earliest_item
+        exporter
+            .export(&items, &context, &mut writer)
+            .and_then(|_| writer.flush())
+    } else {
+        fs::File::create(path).and_then(|file| {
+            let mut writer = io::BufWriter::new(file);
+
+            exporter
+                .export(&items, &context, &mut writer)
+                .and_then(|_| writer.flush())
+        })
+    };
+
+    result.unwrap_or_else(|e| {
+        error::exit_with_message(&format!(
+            "Unable to write export file '{}'. Error: '{}'.",
+            path, e
+        ))
+    });
 }
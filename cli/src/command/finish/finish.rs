@@ -4,21 +4,25 @@ use cmd_args::{arg, option, Group};
 
 use persistence::calc::Status;
 
+use crate::at;
 use crate::command::command::Command;
+use crate::error;
 
 /// Command used to finish an in progress work item.
 pub struct FinishCommand {}
 
 impl Command for FinishCommand {
     fn build(&self) -> Group {
-        Group::new(
-            Box::new(|args, options| execute(args, options)),
-            "Finish working on a work item in progress",
+        at::add_option(
+            Group::new(
+                Box::new(|args, options| execute(args, options)),
+                "Finish working on a work item in progress",
+            )
+            .add_argument(arg::Descriptor::new(
+                arg::Type::Int,
+                "ID of the work item to finish working on",
+            )),
         )
-        .add_argument(arg::Descriptor::new(
-            arg::Type::Int,
-            "ID of the work item to finish working on",
-        ))
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -31,43 +35,32 @@ impl Command for FinishCommand {
 }
 
 /// Execute the finish command.
-fn execute(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     let id = args[0]
         .int()
         .expect("Expected first argument to be a work item ID");
 
-    match persistence::find_item_by_id(id).unwrap() {
-        Some(mut item) => {
-            if let Status::Done = item.status() {
-                println!("Work item with ID {} is already finished.", id);
-            } else {
-                item.finish_working(None).unwrap();
+    let mut item = persistence::get_item_by_id(id).unwrap_or_else(error::exit_with);
 
-                match persistence::update_items(vec![&item]) {
-                    Ok(_) => println!("Finished work item with ID {}.", id),
-                    Err(e) => println!(
-                        "Failed to update work item with ID {}. Error: '{}'",
-                        item.id().unwrap(),
-                        e
-                    ),
-                };
-            }
-        }
-        None => {
-            println!("Could not find work item with ID {}.", id);
-        }
-    }
+    item.finish_working(at::timestamp_from_options(options))
+        .unwrap_or_else(error::exit_with);
+    persistence::update_items(vec![&item]).unwrap_or_else(error::exit_with);
+
+    println!("Finished work item with ID {}.", id);
 }
 
-pub(crate) fn finish_all_paused_work_items() {
-    let mut result = persistence::find_items_by_status(Status::Paused).unwrap();
+/// Finish all paused work items at the given timestamp (or now).
+pub(crate) fn finish_all_paused_work_items(timestamp: Option<i64>) {
+    let mut result =
+        persistence::find_items_by_status(Status::Paused).unwrap_or_else(error::exit_with);
 
     let mut to_update = Vec::new();
     for item in result.iter_mut() {
-        item.finish_working(None).unwrap();
+        item.finish_working(timestamp)
+            .unwrap_or_else(error::exit_with);
 
         to_update.push(&*item);
     }
 
-    persistence::update_items(to_update).unwrap();
+    persistence::update_items(to_update).unwrap_or_else(error::exit_with);
 }
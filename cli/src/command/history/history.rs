@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use cmd_args::{arg, option, Group};
+use colorful::Colorful;
+
+use persistence::journal::{ChangeKind, JournalChange, JournalEntry};
+
+use crate::command::command::Command;
+use crate::error;
+
+/// Maximum number of work item IDs to list per kind of change.
+const MAX_LISTED_IDS: usize = 10;
+
+/// Command used to show the history of changes that may be undone and redone.
+pub struct HistoryCommand {}
+
+impl Command for HistoryCommand {
+    fn build(&self) -> Group {
+        Group::new(
+            Box::new(|args, options| execute(args, options)),
+            "Show the latest changes to work items (to undo or redo them)",
+        )
+        .add_option(option::Descriptor::new(
+            "limit",
+            option::Type::Str {
+                default: String::from("20"),
+            },
+            "Maximum number of changes to show",
+        ))
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        Some(vec!["journal"])
+    }
+
+    fn name(&self) -> &str {
+        "history"
+    }
+}
+
+/// Execute the history command.
+fn execute(_args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let limit_str = options.get("limit").map_or("20", |v| v.str().unwrap());
+    let limit = limit_str.parse::<usize>().unwrap_or_else(|_| {
+        error::exit_with_message(&format!("The limit '{}' is not a valid number", limit_str))
+    });
+
+    let entries = persistence::list_journal(limit).unwrap_or_else(error::exit_with);
+    if entries.is_empty() {
+        println!("There are no changes yet.");
+        return;
+    }
+
+    for entry in &entries {
+        let id_str = format!("#{}", entry.id()).color(colorful::Color::DodgerBlue3);
+
+        let date_str = shared::time::get_local_date_time(entry.timestamp())
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+            .color(colorful::Color::DeepPink1a);
+
+        let undone_str = match entry.is_undone() {
+            true => format!(" {}", "(undone)".color(colorful::Color::DarkSlateGray1)),
+            false => String::new(),
+        };
+
+        println!(
+            "  • {} [{}] {}{}",
+            id_str,
+            date_str,
+            describe_entry(entry),
+            undone_str
+        );
+    }
+
+    println!();
+    println!("Use 'worklog undo' and 'worklog redo' to undo and redo the changes in order.");
+}
+
+/// Describe the changes recorded in the given journal entry.
+pub(crate) fn describe_entry(entry: &JournalEntry) -> String {
+    let mut parts = Vec::new();
+    for (kind, verb) in &[
+        (ChangeKind::Logged, "Logged"),
+        (ChangeKind::Updated, "Updated"),
+        (ChangeKind::Deleted, "Deleted"),
+        (ChangeKind::Restored, "Restored"),
+    ] {
+        let changes: Vec<&JournalChange> = entry
+            .changes()
+            .iter()
+            .filter(|c| c.kind() == *kind)
+            .collect();
+
+        match changes.as_slice() {
+            [] => {}
+            [change] => parts.push(format!(
+                "{} work item {} '{}'",
+                verb,
+                change.item_id(),
+                change
+                    .after()
+                    .or_else(|| change.before())
+                    .map_or("", |item| item.description())
+            )),
+            _ => {
+                let mut ids: Vec<String> = changes
+                    .iter()
+                    .take(MAX_LISTED_IDS)
+                    .map(|c| c.item_id().to_string())
+                    .collect();
+                if changes.len() > MAX_LISTED_IDS {
+                    ids.push(format!("... {} more", changes.len() - MAX_LISTED_IDS));
+                }
+
+                parts.push(format!(
+                    "{} {} work items ({})",
+                    verb,
+                    changes.len(),
+                    ids.join(", ")
+                ));
+            }
+        }
+    }
+
+    parts.join("; ")
+}
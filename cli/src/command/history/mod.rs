@@ -0,0 +1,4 @@
+mod history;
+
+pub(crate) use history::describe_entry;
+pub use history::HistoryCommand;
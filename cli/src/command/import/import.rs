@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+use cmd_args::{arg, option, Group};
+use import::Importer;
+use persistence::calc::{Project, WorkItem};
+use persistence::query::ItemQuery;
+use persistence::revision::Origin;
+use persistence::Repository;
+
+use crate::command::command::Command;
+use crate::error;
+
+/// Path denoting the standard input instead of a file.
+const STDIN_PATH: &str = "-";
+
+/// Command used to import work items.
+pub struct ImportCommand {}
+
+/// Work item to import.
+struct ImportedItem {
+    /// The work item read.
+    item: WorkItem,
+    /// Whether the work item already exists (or appeared before in the imported file).
+    duplicate: bool,
+}
+
+impl Command for ImportCommand {
+    fn build(&self) -> Group {
+        Group::new(
+            Box::new(|args, options| execute(args, options)),
+            "Import work log entries",
+        )
+        .add_argument(arg::Descriptor::new(
+            arg::Type::Str,
+            "Import format ('json', 'timewarrior', 'watson' or 'toggl')",
+        ))
+        .add_option(option::Descriptor::new(
+            "path",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Path of the file to import from (defaults to 'log_export.json' for 'json', '-' for the standard input)",
+        ))
+        .add_option(option::Descriptor::new(
+            "keep-ids",
+            option::Type::Bool { default: false },
+            "Keep the IDs of the imported work items instead of assigning new ones",
+        ))
+        .add_option(option::Descriptor::new(
+            "dry-run",
+            option::Type::Bool { default: false },
+            "Only report the work items that would be imported without changing the database",
+        ))
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        "import"
+    }
+}
+
+/// Execute the import command.
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let format = args[0].str().unwrap();
+
+    let importer = import::find_importer(format).unwrap_or_else(|| {
+        let formats: Vec<&str> = import::IMPORTERS.iter().map(|i| i.name()).collect();
+
+        error::exit_with_message(&format!(
+            "Import format '{}' is not supported. Use one of '{}'.",
+            format,
+            formats.join("', '")
+        ))
+    });
+
+    let path = options
+        .get("path")
+        .and_then(|v| v.str())
+        .filter(|v| !v.is_empty())
+        .or_else(|| importer.default_path())
+        .unwrap_or_else(|| {
+            error::exit_with_message(&format!(
+                "Please specify the file to import using --path (e. g. 'worklog import {} --path export.{}').",
+                importer.name(),
+                importer.name()
+            ))
+        })
+        .to_owned();
+    let keep_ids = options
+        .get("keep-ids")
+        .and_then(|v| v.bool())
+        .unwrap_or(false);
+    let dry_run = options
+        .get("dry-run")
+        .and_then(|v| v.bool())
+        .unwrap_or(false);
+
+    let items = read_items(importer, &path);
+
+    let mut repository = Repository::open().unwrap_or_else(error::exit_with);
+
+    if dry_run {
+        let items = detect_duplicates(&repository, items).unwrap_or_else(error::exit_with);
+        for imported in &items {
+            println!("  • {}", format_imported_item(imported));
+        }
+
+        let duplicates = items.iter().filter(|i| i.duplicate).count();
+        println!(
+            "Would import {} work items (skipping {} duplicates).",
+            items.len() - duplicates,
+            duplicates
+        );
+    } else {
+        persistence::select_origin(Origin::Import);
+
+        let (imported, duplicates) =
+            import_items(&mut repository, items, keep_ids).unwrap_or_else(error::exit_with);
+
+        println!(
+            "Imported {} work items (skipped {} duplicates).",
+            imported, duplicates
+        );
+    }
+}
+
+/// Read the work items from the file at the given path using the passed importer.
+fn read_items(importer: &dyn Importer, path: &str) -> Vec<WorkItem> {
+    let result = if path == STDIN_PATH {
+        let stdin = io::stdin();
+        let mut reader = stdin.lock();
+
+        importer.import(&mut reader)
+    } else {
+        fs::File::open(path).and_then(|file| {
+            let mut reader = io::BufReader::new(file);
+
+            importer.import(&mut reader)
+        })
+    };
+
+    result.unwrap_or_else(|e| {
+        error::exit_with_message(&format!(
+            "Unable to read import file '{}'. Error: '{}'.",
+            path, e
+        ))
+    })
+}
+
+/// Store the given work items except for duplicates in a single transaction.
+/// Either keeps their IDs or lets the database assign new ones.
+/// Returns the number of imported work items and skipped duplicates.
+fn import_items(
+    repository: &mut Repository,
+    items: Vec<WorkItem>,
+    keep_ids: bool,
+) -> Result<(usize, usize), persistence::Error> {
+    repository.transaction(|repository| {
+        let mut imported = 0;
+        let mut duplicates = 0;
+
+        for ImportedItem { item, duplicate } in detect_duplicates(repository, items)? {
+            if duplicate {
+                duplicates += 1;
+                continue;
+            }
+
+            ensure_project_exists(repository, item.project())?;
+
+            if keep_ids {
+                repository.restore_item(item)?;
+            } else {
+                repository.log_item(item)?;
+            }
+            imported += 1;
+        }
+
+        Ok((imported, duplicates))
+    })
+}
+
+/// Create the project with the given name (if any) unless it already exists,
+/// so that work items of a backup can be assigned to it.
+fn ensure_project_exists(
+    repository: &mut Repository,
+    name: Option<&str>,
+) -> Result<(), persistence::Error> {
+    if let Some(name) = name {
+        if repository.find_project_by_name(name)?.is_none() {
+            repository.create_project(Project::new(name.to_owned(), None, None))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Mark the work items already existing in the database or appearing
+/// multiple times in the given list as duplicates.
+fn detect_duplicates(
+    repository: &Repository,
+    items: Vec<WorkItem>,
+) -> Result<Vec<ImportedItem>, persistence::Error> {
+    let intervals: Vec<(i64, i64)> = items.iter().flat_map(|i| i.intervals()).collect();
+    let existing = match (
+        intervals.iter().map(|(start, _)| *start).min(),
+        intervals.iter().map(|(_, end)| *end).max(),
+    ) {
+        (Some(from_timestamp), Some(to_timestamp)) => repository
+            .query_items(&ItemQuery::new().time_range(from_timestamp, to_timestamp + 1))?,
+        _ => Vec::new(),
+    };
+
+    let mut imported: Vec<ImportedItem> = Vec::with_capacity(items.len());
+    for item in items {
+        let duplicate = existing.iter().any(|e| import::is_duplicate(&item, e))
+            || imported
+                .iter()
+                .any(|i| import::is_duplicate(&item, &i.item));
+
+        imported.push(ImportedItem { item, duplicate });
+    }
+
+    Ok(imported)
+}
+
+/// Format the given imported work item for the dry run report.
+fn format_imported_item(imported: &ImportedItem) -> String {
+    let item = &imported.item;
+    let start = shared::time::get_local_date_time(item.created_timestamp());
+
+    format!(
+        "{} {} | {} | {} | {}",
+        if imported.duplicate {
+            "[DUPLICATE]"
+        } else {
+            "[NEW]      "
+        },
+        start.format("%Y-%m-%d %H:%M"),
+        shared::time::format_duration((item.time_taken() / 1000) as u32),
+        item.description(),
+        item.tags().join(", ")
+    )
+}
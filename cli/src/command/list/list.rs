@@ -3,17 +3,25 @@ use std::collections::HashMap;
 use cmd_args::{arg, option, Group};
 use colorful::Colorful;
 
-use persistence::calc::{Status, WorkItem};
+use persistence::calc::{
+    break_down_by_week, calculate_project_statistics, calculate_rounded_total_time,
+    calculate_total_work_time, round_item_time, DayBreakdown, Status, WorkItem,
+};
+use persistence::query::{ItemQuery, SortOrder};
+use shared::calc::RoundingPolicy;
 
 use crate::command::command::Command;
-use std::ops::Sub;
+use crate::error;
+
+/// Label of the work items not assigned to any project.
+const NO_PROJECT_LABEL: &str = "(no project)";
 
 /// Command used to list work items.
 pub struct ListCommand {}
 
 impl Command for ListCommand {
     fn build(&self) -> Group {
-        Group::new(
+        let group = Group::new(
             Box::new(|args, options| execute(args, options)),
             "List work items",
         )
@@ -27,8 +35,15 @@ impl Command for ListCommand {
             option::Type::Str {
                 default: String::from("today"),
             },
-            "Filter by a date ('today' (default), 'yesterday', '2020-02-20' (yyyy-MM-dd)) or work item ID",
+            "Filter by a date ('today' (default), 'yesterday', '2020-02-20' (yyyy-MM-dd)), a week ('this-week', 'last-week', '2021-W05'), a month ('this-month', 'last-month', '2021-01'), a range ('2021-01-01..2021-01-31') or work item ID",
         ))
+        .add_option(option::Descriptor::new(
+            "by-project",
+            option::Type::Bool { default: false },
+            "Group the work items by project instead of by day",
+        ));
+
+        add_query_options(group)
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -43,22 +58,33 @@ impl Command for ListCommand {
 /// Execute the list command.
 fn execute(_args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     let all: bool = options.get("all").map_or(false, |v| v.bool().unwrap());
+    let by_project: bool = options
+        .get("by-project")
+        .map_or(false, |v| v.bool().unwrap());
+
+    let query = build_query(options).sort(SortOrder::NewestFirst);
 
-    let mut entries = match all {
-        true => persistence::list_items().unwrap(),
+    // Time range the listed work items have been filtered by (if any)
+    let mut time_range: Option<(i64, i64)> = None;
+
+    let entries = match all {
+        true => persistence::query_items(&query).unwrap_or_else(error::exit_with),
         false => {
             let filter: &str = options.get("filter").map_or("today", |v| v.str().unwrap());
 
             // Check if filter string is a work item ID
             match filter.parse::<i32>() {
                 Ok(id) => persistence::find_item_by_id(id)
-                    .unwrap()
+                    .unwrap_or_else(error::exit_with)
                     .map_or(Vec::new(), |v| vec![v]),
                 Err(_) => {
                     // Filter string is not an work item ID but a date!
-                    let (from_timestamp, to_timestamp) = filter_keyword_to_time_range(filter);
+                    let (from_timestamp, to_timestamp) = shared::time::resolve_date_range(filter)
+                        .unwrap_or_else(|e| error::exit_with_message(&e));
+                    time_range = Some((from_timestamp, to_timestamp));
 
-                    persistence::find_items_by_timerange(from_timestamp, to_timestamp).unwrap()
+                    persistence::query_items(&query.time_range(from_timestamp, to_timestamp))
+                        .unwrap_or_else(error::exit_with)
                 }
             }
         }
@@ -70,104 +96,274 @@ fn execute(_args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     println!("{}", found_str);
     println!(" {} ", "-".repeat(found_str.len() - 2));
 
-    // Sort entries by their timestamp (newest come first).
-    entries.sort_by_key(|v| i64::max_value() - v.created_timestamp());
+    // Break the work down by week and day (only counting the work done in the time range)
+    let (from_timestamp, to_timestamp) = time_range.unwrap_or((i64::MIN, i64::MAX));
+    let item_refs: Vec<&WorkItem> = entries.iter().collect();
+    let weeks = break_down_by_week(&item_refs, from_timestamp, to_timestamp);
+    let days_count: usize = weeks.iter().map(|w| w.days().len()).sum();
+    let rounding_policy = shared::calc::active_rounding_policy();
+
+    if by_project {
+        print_by_project(
+            &item_refs,
+            from_timestamp,
+            to_timestamp,
+            rounding_policy.as_ref(),
+        );
+    } else {
+        // Print the newest work first
+        for week in weeks.iter().rev() {
+            if weeks.len() > 1 {
+                println!();
+                println!(
+                    "{}",
+                    format!(
+                        "## Week {}-W{:02} ({})",
+                        week.year(),
+                        week.week(),
+                        format_rounded_duration(
+                            week.total_time(),
+                            rounding_policy
+                                .as_ref()
+                                .map(|policy| week.rounded_total_time(policy))
+                        )
+                    )
+                    .bold()
+                );
+            }
 
-    // First collect all items per day
-    let mut items_per_day = Vec::new();
-    items_per_day.push(Vec::new());
-    let mut last_date_option: Option<chrono::Date<_>> = None;
-    for item in &entries {
-        let date_time = shared::time::get_local_date_time(item.created_timestamp());
+            for day in week.days().iter().rev() {
+                print_date_header(day, rounding_policy.as_ref());
+
+                for item in day.items().iter().rev() {
+                    println!(
+                        "  • {}",
+                        format_item(
+                            item,
+                            day.from_timestamp(),
+                            day.to_timestamp(),
+                            rounding_policy.as_ref()
+                        )
+                    );
+                }
+            }
+        }
+    }
 
-        let is_another_day = match last_date_option {
-            Some(last_date) => date_time.date().sub(last_date).num_days().abs() >= 1,
-            None => false,
-        };
+    if days_count > 1 || by_project {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "Total: {}",
+                format_rounded_duration(
+                    calculate_total_work_time(&item_refs, from_timestamp, to_timestamp),
+                    rounding_policy.as_ref().map(|policy| {
+                        calculate_rounded_total_time(
+                            &item_refs,
+                            from_timestamp,
+                            to_timestamp,
+                            policy,
+                        )
+                    })
+                )
+            )
+            .bold()
+        );
+    }
 
-        if is_another_day {
-            items_per_day.push(Vec::new());
-        }
+    println!();
+}
 
-        let current_day_items = items_per_day.last_mut().unwrap();
-        current_day_items.push(item);
+/// Add the options used to narrow down the work items to the given command group.
+pub(crate) fn add_query_options(group: Group) -> Group {
+    group
+        .add_option(option::Descriptor::new(
+            "tags",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Only include work items having all of the given tags (e. g. 'tag1, tag2')",
+        ))
+        .add_option(option::Descriptor::new(
+            "exclude-tags",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Exclude work items having any of the given tags (e. g. 'tag1, tag2')",
+        ))
+        .add_option(option::Descriptor::new(
+            "status",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Only include work items with one of the given statuses (e. g. 'done, in_progress, paused')",
+        ))
+        .add_option(option::Descriptor::new(
+            "search",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Only include work items whose description contains the given text",
+        ))
+        .add_option(option::Descriptor::new(
+            "project",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Only include work items assigned to the given project",
+        ))
+}
+
+/// Build the item query from the options added using `add_query_options`.
+pub(crate) fn build_query(options: &HashMap<&str, option::Value>) -> ItemQuery {
+    let mut query = ItemQuery::new();
 
-        last_date_option = Some(date_time.date());
+    for tag in split_option_list(options, "tags") {
+        query = query.with_tag(&tag);
     }
 
-    // Print work items for each day
-    for items in items_per_day {
-        if !items.is_empty() {
-            print_date_header(&items);
+    for tag in split_option_list(options, "exclude-tags") {
+        query = query.without_tag(&tag);
+    }
 
-            for item in items {
-                println!("  • {}", format_item(item));
-            }
+    for status_str in split_option_list(options, "status") {
+        let status = status_str
+            .to_uppercase()
+            .replace('-', "_")
+            .parse::<Status>()
+            .unwrap_or_else(|_| {
+                error::exit_with_message(&format!(
+                    "Unknown status '{}'. Use 'done', 'in_progress' or 'paused'.",
+                    status_str
+                ))
+            });
+
+        query = query.with_status(status);
+    }
+
+    if let Some(search) = options.get("search").and_then(|v| v.str()) {
+        if !search.is_empty() {
+            query = query.description_contains(search);
         }
     }
 
-    println!();
+    if let Some(project) = options.get("project").and_then(|v| v.str()) {
+        if !project.is_empty() {
+            query = query.in_project(project);
+        }
+    }
+
+    query
 }
 
-/// Print the header for a new date.
-fn print_date_header(items: &[&WorkItem]) {
-    let first = *items.first().unwrap();
-    let date_time = shared::time::get_local_date_time(first.created_timestamp());
+/// Split the comma separated list of the option with the given name.
+fn split_option_list(options: &HashMap<&str, option::Value>, name: &str) -> Vec<String> {
+    options
+        .get(name)
+        .and_then(|v| v.str())
+        .map_or(Vec::new(), |v| {
+            v.split(",")
+                .map(|s| s.trim().to_owned())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+}
+
+/// Print the given work items grouped by their project (most time first).
+fn print_by_project(
+    items: &[&WorkItem],
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: Option<&RoundingPolicy>,
+) {
+    let projects = persistence::list_projects().unwrap_or_else(error::exit_with);
+
+    for statistics in calculate_project_statistics(
+        items,
+        &projects,
+        from_timestamp,
+        to_timestamp,
+        rounding_policy,
+    ) {
+        let client_str = statistics
+            .client()
+            .map_or(String::new(), |client| format!(" for {}", client));
+        let amount_str = statistics
+            .amount()
+            .map_or(String::new(), |amount| format!(" - {:.2}", amount));
+
+        println!();
+        println!(
+            "{}",
+            format!(
+                "# {}{} ({}){}",
+                statistics.project().unwrap_or(NO_PROJECT_LABEL),
+                client_str,
+                format_rounded_duration(statistics.time(), statistics.rounded_time()),
+                amount_str
+            )
+            .underlined()
+        );
+        println!();
+
+        for item in items.iter().filter(|i| i.project() == statistics.project()) {
+            println!(
+                "  • {}",
+                format_item(item, from_timestamp, to_timestamp, rounding_policy)
+            );
+        }
+    }
+}
 
+/// Print the header for a new date.
+fn print_date_header(day: &DayBreakdown, rounding_policy: Option<&RoundingPolicy>) {
     println!();
     println!(
         "{}",
         format!(
             "# {} ({})",
-            date_time.format("%A - %d. %B %Y"),
-            shared::time::format_duration((calculate_total_work_time(items) / 1000) as u32)
+            day.date().format("%A - %d. %B %Y"),
+            format_rounded_duration(
+                day.total_time(),
+                rounding_policy.map(|policy| day.rounded_total_time(policy))
+            )
         )
         .underlined()
     );
     println!();
 }
 
-/// Calculate the total work time of the passed items.
-pub(crate) fn calculate_total_work_time(items: &[&WorkItem]) -> i64 {
-    let mut time_events: Vec<shared::calc::TimeEvent> = items
-        .iter()
-        .flat_map(|i| {
-            i.events()
-                .iter()
-                .map(|e| shared::calc::TimeEvent::new(e.is_start(), e.timestamp()))
-        })
-        .collect();
-
-    for item in items {
-        if let Status::InProgress = item.status() {
-            // Add dummy end time event to calculate the correct time
-            time_events.push(shared::calc::TimeEvent::new(
-                false,
-                chrono::Utc::now().timestamp_millis(),
-            ));
-        }
-    }
-
-    shared::calc::calculate_unique_total_time(&mut time_events)
-}
-
-/// Format a work item.
-fn format_item(item: &WorkItem) -> String {
+/// Format a work item showing the work done in the given time range.
+fn format_item(
+    item: &WorkItem,
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: Option<&RoundingPolicy>,
+) -> String {
     let id_str = format!(
         "#{}",
         item.id().expect("Work item must have an ID at this point!")
     )
     .color(colorful::Color::DodgerBlue3);
 
-    let time_str = shared::time::get_local_date_time(item.created_timestamp())
+    let start_timestamp = item
+        .intervals_in_range(from_timestamp, to_timestamp)
+        .first()
+        .map_or(item.created_timestamp(), |(start, _)| *start);
+    let time_str = shared::time::get_local_date_time(start_timestamp)
         .format("%H:%M")
         .to_string()
         .color(colorful::Color::DeepPink1a);
 
     let description = item.description();
 
-    let duration_str = shared::time::format_duration((item.time_taken() / 1000) as u32)
-        .color(colorful::Color::Orange1);
+    let duration_str = format_rounded_duration(
+        item.time_taken_in_range(from_timestamp, to_timestamp),
+        rounding_policy
+            .and_then(|policy| round_item_time(item, from_timestamp, to_timestamp, policy)),
+    )
+    .color(colorful::Color::Orange1);
     let status_str = match item.status() {
         Status::Done => duration_str,
         Status::InProgress => {
@@ -181,37 +377,29 @@ fn format_item(item: &WorkItem) -> String {
         .join(", ")
         .color(colorful::Color::DarkSlateGray1);
 
+    let project_str = item.project().map_or(String::new(), |project| {
+        format!(" @{}", project.color(colorful::Color::Gold1))
+    });
+
     format!(
-        "{} [{}] {} - {} ({})",
-        id_str, time_str, description, status_str, tags_str
+        "{} [{}] {}{} - {} ({})",
+        id_str, time_str, description, project_str, status_str, tags_str
     )
 }
 
-/// Convert the passed filter keyword ("today", "yesterday", "2020-02-02")
-/// to a time range of timestamps.
-pub(crate) fn filter_keyword_to_time_range(keyword: &str) -> (i64, i64) {
-    match keyword {
-        "today" => {
-            let today = chrono::Utc::today();
-            let from_timestamp = today.and_hms(0, 0, 0).timestamp_millis();
-            let to_timestamp = today.succ().and_hms(0, 0, 0).timestamp_millis();
-
-            (from_timestamp, to_timestamp)
-        }
-        "yesterday" => {
-            let yesterday = chrono::Utc::today().pred();
-            let from_timestamp = yesterday.and_hms(0, 0, 0).timestamp_millis();
-            let to_timestamp = yesterday.succ().and_hms(0, 0, 0).timestamp_millis();
-
-            (from_timestamp, to_timestamp)
-        }
-        str => {
-            let date = chrono::NaiveDate::parse_from_str(str, "%Y-%m-%d")
-                .expect("Expected date format to be in form 'YYYY-MM-dd'");
-            let from_timestamp = date.and_hms(0, 0, 0).timestamp_millis();
-            let to_timestamp = date.succ().and_hms(0, 0, 0).timestamp_millis();
-
-            (from_timestamp, to_timestamp)
-        }
+/// Format the given duration (in milliseconds) followed by the rounded duration (if any).
+fn format_rounded_duration(duration: i64, rounded: Option<i64>) -> String {
+    match rounded {
+        Some(rounded) => format!(
+            "{}, rounded {}",
+            format_duration(duration),
+            format_duration(rounded)
+        ),
+        None => format_duration(duration),
     }
 }
+
+/// Format the given duration (in milliseconds).
+fn format_duration(duration: i64) -> String {
+    shared::time::format_duration((duration / 1000) as u32)
+}
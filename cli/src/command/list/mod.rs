@@ -1,5 +1,4 @@
 mod list;
 
-pub(crate) use list::calculate_total_work_time;
-pub(crate) use list::filter_keyword_to_time_range;
 pub use list::ListCommand;
+pub(crate) use list::{add_query_options, build_query};
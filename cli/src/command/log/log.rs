@@ -1,4 +1,6 @@
 use crate::command::command::Command;
+use crate::error;
+use crate::project;
 use cmd_args::{arg, option, Group};
 use colorful::Colorful;
 use persistence::calc::event::{Event, EventType};
@@ -11,7 +13,7 @@ pub struct LogCommand {}
 
 impl Command for LogCommand {
     fn build(&self) -> Group {
-        Group::new(
+        let group = Group::new(
             Box::new(|args, options| execute(args, options)),
             "Log an already done work item",
         )
@@ -23,7 +25,9 @@ impl Command for LogCommand {
         .add_argument(arg::Descriptor::new(
             arg::Type::Str,
             "Time spent on the task (Format like '2h 3m 12s', '45m' or '1h 15m')",
-        ))
+        ));
+
+        project::add_option(group)
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -36,16 +40,21 @@ impl Command for LogCommand {
 }
 
 /// Execute the log command.
-fn execute(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     let description = args[0].str().unwrap();
     let tags_str = args[1].str().unwrap();
     let time_taken_str = args[2].str().unwrap();
 
     let tags: Vec<String> = tags_str.split(",").map(|s| s.trim().to_owned()).collect();
-    let time_taken_ms = shared::time::parse_duration(time_taken_str).unwrap() as i64 * 1000;
+    let time_taken_ms = shared::time::parse_duration(time_taken_str)
+        .unwrap_or_else(|e| error::exit_with_message(&e.to_string()))
+        as i64
+        * 1000;
+
+    let project = project::assignment_from_options(options).flatten();
 
     let current_timestamp_ms = chrono::Utc::now().timestamp_millis();
-    let item = persistence::calc::WorkItem::new_internal(
+    let mut item = persistence::calc::WorkItem::new_internal(
         -1,
         description.to_owned(),
         Status::Done,
@@ -55,8 +64,9 @@ fn execute(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
             Event::new(EventType::Finished, current_timestamp_ms),
         ],
     );
+    item.set_project(project);
 
-    let new_id = persistence::log_item(item).unwrap();
+    let new_id = persistence::log_item(item).unwrap_or_else(error::exit_with);
 
     println!(
         "Create work item with ID {}.",
@@ -2,13 +2,23 @@ mod clear;
 mod continue_cmd;
 mod delete;
 mod edit;
+mod edit_events;
 mod export;
 mod finish;
+mod history;
+mod import;
 mod list;
 mod log;
+mod note;
 mod pause;
+mod project;
+mod redo;
+mod search;
 mod show;
 mod start;
+mod stats;
+mod trash;
+mod undo;
 
 mod command;
 mod commands;
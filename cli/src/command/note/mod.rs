@@ -0,0 +1,3 @@
+mod note;
+
+pub use note::NoteCommand;
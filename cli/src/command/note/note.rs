@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use cmd_args::{arg, option, Group};
+
+use crate::at;
+use crate::command::command::Command;
+use crate::error;
+
+/// Command used to add a note to a work item.
+pub struct NoteCommand {}
+
+impl Command for NoteCommand {
+    fn build(&self) -> Group {
+        at::add_option(
+            Group::new(
+                Box::new(|args, options| execute(args, options)),
+                "Add a timestamped note to a work item",
+            )
+            .add_argument(arg::Descriptor::new(
+                arg::Type::Int,
+                "ID of the work item to add the note to",
+            ))
+            .add_argument(arg::Descriptor::new(arg::Type::Str, "Text of the note")),
+        )
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        Some(vec!["comment"])
+    }
+
+    fn name(&self) -> &str {
+        "note"
+    }
+}
+
+/// Execute the note command.
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let id = args[0]
+        .int()
+        .expect("Expected first argument to be a work item ID");
+    let text = args[1].str().unwrap().trim();
+    if text.is_empty() {
+        error::exit_with_message("The note must not be empty.")
+    }
+
+    let mut item = persistence::get_item_by_id(id).unwrap_or_else(error::exit_with);
+
+    item.add_note(text.to_owned(), at::timestamp_from_options(options));
+    persistence::update_items(vec![&item]).unwrap_or_else(error::exit_with);
+
+    println!("Added note to work item with ID {}.", id);
+}
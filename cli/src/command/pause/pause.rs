@@ -4,21 +4,25 @@ use cmd_args::{arg, option, Group};
 
 use persistence::calc::Status;
 
+use crate::at;
 use crate::command::command::Command;
+use crate::error;
 
 /// Command used to pause working on an in progress work item.
 pub struct PauseCommand {}
 
 impl Command for PauseCommand {
     fn build(&self) -> Group {
-        Group::new(
-            Box::new(|args, options| execute(args, options)),
-            "Pause working on an in progress work item",
+        at::add_option(
+            Group::new(
+                Box::new(|args, options| execute(args, options)),
+                "Pause working on an in progress work item",
+            )
+            .add_argument(arg::Descriptor::new(
+                arg::Type::Str,
+                "ID of the work item to pause working on or 'all' to pause all work items",
+            )),
         )
-        .add_argument(arg::Descriptor::new(
-            arg::Type::Str,
-            "ID of the work item to pause working on or 'all' to pause all work items",
-        ))
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -31,56 +35,52 @@ impl Command for PauseCommand {
 }
 
 /// Execute the pause command.
-fn execute(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     let value = args[0].str().expect("Expected to have one argument");
+    let timestamp = at::timestamp_from_options(options);
 
     match value.parse::<i32>() {
         Ok(id) => {
-            pause_work_item_by_id(id);
+            pause_work_item_by_id(id, timestamp);
         }
         Err(_) => {
             // Check if value is "all" to pause all work items in progress
             if value == "all" {
-                pause_all_work_items_in_progress();
+                pause_all_work_items_in_progress(timestamp);
 
                 println!("Paused all work items in progress.");
+            } else {
+                error::exit_with_message(&format!(
+                    "Expected a work item ID or 'all' but got '{}'.",
+                    value
+                ))
             }
         }
     }
 }
 
-fn pause_work_item_by_id(id: i32) {
-    match persistence::find_item_by_id(id).unwrap() {
-        Some(mut item) => {
-            if let Status::InProgress = item.status() {
-                item.pause_working().unwrap();
+fn pause_work_item_by_id(id: i32, timestamp: Option<i64>) {
+    let mut item = persistence::get_item_by_id(id).unwrap_or_else(error::exit_with);
 
-                match persistence::update_items(vec![&item]) {
-                    Ok(_) => println!("Paused work item with ID {}.", id),
-                    Err(e) => println!("Failed to update work item with ID {}. Error: '{}'", id, e),
-                };
-            } else {
-                println!(
-                    "Work item with ID {} is currently not in progress and thus cannot be paused.",
-                    id
-                );
-            }
-        }
-        None => {
-            println!("Could not find work item with ID {}.", id);
-        }
-    }
+    item.pause_working(timestamp)
+        .unwrap_or_else(error::exit_with);
+    persistence::update_items(vec![&item]).unwrap_or_else(error::exit_with);
+
+    println!("Paused work item with ID {}.", id);
 }
 
-pub(crate) fn pause_all_work_items_in_progress() {
-    let mut result = persistence::find_items_by_status(Status::InProgress).unwrap();
+/// Pause all work items in progress at the given timestamp (or now).
+pub(crate) fn pause_all_work_items_in_progress(timestamp: Option<i64>) {
+    let mut result =
+        persistence::find_items_by_status(Status::InProgress).unwrap_or_else(error::exit_with);
 
     let mut to_update = Vec::new();
     for item in result.iter_mut() {
-        item.pause_working().unwrap();
+        item.pause_working(timestamp)
+            .unwrap_or_else(error::exit_with);
 
         to_update.push(&*item);
     }
 
-    persistence::update_items(to_update).unwrap();
+    persistence::update_items(to_update).unwrap_or_else(error::exit_with);
 }
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use cmd_args::{arg, option, Group};
+use colorful::Colorful;
+
+use persistence::calc::{Project, WorkItem};
+
+use crate::command::command::Command;
+use crate::error;
+use crate::project::{self, NO_PROJECT};
+
+/// Command used to manage projects and assign work items to them.
+pub struct ProjectCommand {}
+
+impl Command for ProjectCommand {
+    fn build(&self) -> Group {
+        Group::new(
+            Box::new(|args, options| execute(args, options)),
+            "Manage projects and assign work items to them",
+        )
+        .add_argument(arg::Descriptor::new(
+            arg::Type::Str,
+            "Action ('list', 'create', 'archive', 'unarchive' or 'assign')",
+        ))
+        .add_option(option::Descriptor::new(
+            "name",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Name of the project",
+        ))
+        .add_option(option::Descriptor::new(
+            "client",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Client the project is done for ('create')",
+        ))
+        .add_option(option::Descriptor::new(
+            "rate",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Rate billed per hour worked on the project, e. g. '95' or '82.50' ('create')",
+        ))
+        .add_option(option::Descriptor::new(
+            "items",
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "IDs of the work items to assign to the project, e. g. '3, 4' ('assign', use the name 'none' to remove them from their project)",
+        ))
+        .add_option(option::Descriptor::new(
+            "all",
+            option::Type::Bool { default: false },
+            "List archived projects as well ('list')",
+        ))
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        Some(vec!["projects"])
+    }
+
+    fn name(&self) -> &str {
+        "project"
+    }
+}
+
+/// Execute the project command.
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let action = args[0].str().unwrap();
+
+    match action {
+        "list" | "ls" => list_projects(options.get("all").and_then(|v| v.bool()).unwrap_or(false)),
+        "create" => create_project(
+            required_name(options),
+            optional_str(options, "client"),
+            optional_str(options, "rate").map(parse_rate),
+        ),
+        "archive" => set_archived(required_name(options), true),
+        "unarchive" => set_archived(required_name(options), false),
+        "assign" => assign_items(required_name(options), &parse_ids(options)),
+        _ => error::exit_with_message(&format!(
+            "Unknown action '{}'. Use 'list', 'create', 'archive', 'unarchive' or 'assign'.",
+            action
+        )),
+    }
+}
+
+/// Print the projects (including the archived ones if requested).
+fn list_projects(all: bool) {
+    let projects: Vec<Project> = persistence::list_projects()
+        .unwrap_or_else(error::exit_with)
+        .into_iter()
+        .filter(|p| all || !p.is_archived())
+        .collect();
+
+    if projects.is_empty() {
+        println!("There are no projects. Create one using 'worklog project create --name NAME'.");
+        return;
+    }
+
+    for project in &projects {
+        let mut details = Vec::new();
+        if let Some(client) = project.client() {
+            details.push(format!("client: {}", client));
+        }
+        if let Some(rate) = project.hourly_rate() {
+            details.push(format!("{:.2} per hour", rate));
+        }
+        if project.is_archived() {
+            details.push(String::from("archived"));
+        }
+
+        let details_str = match details.is_empty() {
+            true => String::new(),
+            false => format!(" ({})", details.join(", ")),
+        };
+
+        println!(
+            "  • {}{}",
+            project.name().color(colorful::Color::DodgerBlue3),
+            details_str
+        );
+    }
+}
+
+/// Create a new project.
+fn create_project(name: &str, client: Option<&str>, hourly_rate: Option<f64>) {
+    if name == NO_PROJECT {
+        error::exit_with_message(&format!(
+            "The name '{}' is reserved for work items without project.",
+            NO_PROJECT
+        ))
+    }
+
+    persistence::create_project(Project::new(
+        name.to_owned(),
+        client.map(|c| c.to_owned()),
+        hourly_rate,
+    ))
+    .unwrap_or_else(error::exit_with);
+
+    println!("Created project '{}'.", name);
+}
+
+/// Archive the project with the given name (or restore it from the archive).
+fn set_archived(name: &str, archived: bool) {
+    let mut project = persistence::get_project_by_name(name).unwrap_or_else(error::exit_with);
+
+    project.set_archived(archived);
+    persistence::update_project(&project).unwrap_or_else(error::exit_with);
+
+    match archived {
+        true => println!("Archived project '{}'.", name),
+        false => println!("Restored project '{}' from the archive.", name),
+    }
+}
+
+/// Assign the work items with the given IDs to the project with the passed name
+/// ('none' to remove them from their project).
+fn assign_items(name: &str, ids: &[i32]) {
+    let project = project::assignable_project(name);
+
+    let mut items: Vec<WorkItem> = ids
+        .iter()
+        .map(|id| persistence::get_item_by_id(*id).unwrap_or_else(error::exit_with))
+        .collect();
+    for item in &mut items {
+        item.set_project(project.clone());
+    }
+
+    persistence::update_items(items.iter().collect()).unwrap_or_else(error::exit_with);
+
+    match project {
+        Some(project) => println!(
+            "Assigned {} work items to project '{}'.",
+            items.len(),
+            project
+        ),
+        None => println!("Removed {} work items from their project.", items.len()),
+    }
+}
+
+/// Get the project name passed using the name option.
+/// Exits when it is missing.
+fn required_name<'a>(options: &'a HashMap<&str, option::Value>) -> &'a str {
+    optional_str(options, "name").unwrap_or_else(|| {
+        error::exit_with_message("Please specify the project using --name (e. g. --name website).")
+    })
+}
+
+/// Get the non-empty value of the string option with the given name (if any).
+fn optional_str<'a>(options: &'a HashMap<&str, option::Value>, name: &str) -> Option<&'a str> {
+    options
+        .get(name)
+        .and_then(|v| v.str())
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+}
+
+/// Parse the given hourly rate.
+/// Exits when it is not a non-negative number.
+fn parse_rate(value: &str) -> f64 {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|rate| rate.is_finite() && *rate >= 0.0)
+        .unwrap_or_else(|| {
+            error::exit_with_message(&format!(
+                "The hourly rate must be a non-negative number (e. g. '95' or '82.50') but got '{}'.",
+                value
+            ))
+        })
+}
+
+/// Parse the comma separated work item IDs passed using the items option.
+/// Exits when there are none or one is not a number.
+fn parse_ids(options: &HashMap<&str, option::Value>) -> Vec<i32> {
+    optional_str(options, "items")
+        .unwrap_or_else(|| {
+            error::exit_with_message(
+                "Please specify the work items to assign using --items (e. g. --items \"3, 4\").",
+            )
+        })
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<i32>().unwrap_or_else(|_| {
+                error::exit_with_message(&format!("'{}' is not a work item ID.", s))
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,3 @@
+mod redo;
+
+pub use redo::RedoCommand;
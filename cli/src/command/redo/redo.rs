@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use cmd_args::{arg, option, Group};
+
+use crate::command::command::Command;
+use crate::command::history::describe_entry;
+use crate::error;
+
+/// Command used to redo the latest undone change to work items.
+pub struct RedoCommand {}
+
+impl Command for RedoCommand {
+    fn build(&self) -> Group {
+        Group::new(
+            Box::new(|args, options| execute(args, options)),
+            "Redo the change to work items undone last (see 'worklog history')",
+        )
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        "redo"
+    }
+}
+
+/// Execute the redo command.
+fn execute(_args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+    match persistence::redo().unwrap_or_else(error::exit_with) {
+        Some(entry) => println!(
+            "Redid change #{} from {}: {}.",
+            entry.id(),
+            shared::time::get_local_date_time(entry.timestamp()).format("%Y-%m-%d %H:%M:%S"),
+            describe_entry(&entry)
+        ),
+        None => println!("There is nothing to redo."),
+    }
+}
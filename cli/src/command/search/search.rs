@@ -0,0 +1,99 @@
+use crate::command::command::Command;
+use crate::error;
+use cmd_args::{arg, option, Group};
+use colorful::Colorful;
+use persistence::query::SearchResult;
+use std::collections::HashMap;
+
+/// Command used to search work items by text.
+pub struct SearchCommand {}
+
+impl Command for SearchCommand {
+    fn build(&self) -> Group {
+        Group::new(
+            Box::new(|args, options| execute(args, options)),
+            "Search work items by their description and notes",
+        )
+        .add_argument(arg::Descriptor::new(
+            arg::Type::Str,
+            "Text to search for (e. g. 'migration bug')",
+        ))
+        .add_option(option::Descriptor::new(
+            "limit",
+            option::Type::Str {
+                default: String::from("20"),
+            },
+            "Maximum number of work items to show",
+        ))
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        Some(vec!["find"])
+    }
+
+    fn name(&self) -> &str {
+        "search"
+    }
+}
+
+/// Execute the search command.
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let text = args[0].str().unwrap();
+    let limit_str = options.get("limit").map_or("20", |v| v.str().unwrap());
+    let limit = limit_str.parse::<usize>().unwrap_or_else(|_| {
+        error::exit_with_message(&format!("The limit '{}' is not a valid number", limit_str))
+    });
+
+    let results = persistence::search(text).unwrap_or_else(error::exit_with);
+
+    let found_str: String = format!("| Found {} matching work items |", results.len());
+
+    println!(" {} ", "-".repeat(found_str.len() - 2));
+    println!("{}", found_str);
+    println!(" {} ", "-".repeat(found_str.len() - 2));
+    println!();
+
+    for result in results.iter().take(limit) {
+        println!("  • {}", format_result(result));
+    }
+
+    if results.len() > limit {
+        println!();
+        println!(
+            "  ... and {} more (use --limit to show more)",
+            results.len() - limit
+        );
+    }
+
+    println!();
+}
+
+/// Format a search result.
+fn format_result(result: &SearchResult) -> String {
+    let item = result.item();
+
+    let id_str = format!(
+        "#{}",
+        item.id().expect("Work item must have an ID at this point!")
+    )
+    .color(colorful::Color::DodgerBlue3);
+
+    let date_str = shared::time::get_local_date_time(item.created_timestamp())
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+        .color(colorful::Color::DeepPink1a);
+
+    let mut snippet = result
+        .highlighted_snippet(|term| format!("{}", term.color(colorful::Color::Yellow).bold()));
+    if result.is_note_match() {
+        // Show which work item the matching note belongs to
+        snippet = format!("{} (Note: {})", item.description(), snippet);
+    }
+
+    let tags_formatted: Vec<_> = item.tags().iter().map(|s| format!("#{}", s)).collect();
+    let tags_str = tags_formatted
+        .join(", ")
+        .color(colorful::Color::DarkSlateGray1);
+
+    format!("{} [{}] {} ({})", id_str, date_str, snippet, tags_str)
+}
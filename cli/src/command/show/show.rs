@@ -1,7 +1,10 @@
 use crate::command::command::Command;
+use crate::error;
 use cmd_args::{arg, option, Group};
 use colorful::Colorful;
-use persistence::calc::WorkItem;
+use persistence::calc::event::Event;
+use persistence::calc::{calculate_rounded_total_time, WorkItem};
+use persistence::revision::{Revision, RevisionField};
 use std::collections::HashMap;
 
 /// Command used to show details about a work item.
@@ -17,6 +20,11 @@ impl Command for ShowCommand {
             arg::Type::Int,
             "ID of the work item to show details for",
         ))
+        .add_option(option::Descriptor::new(
+            "history",
+            option::Type::Bool { default: false },
+            "Show the history of all changes to the description, tags, project, status and events of the work item and when it has been deleted",
+        ))
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -29,19 +37,30 @@ impl Command for ShowCommand {
 }
 
 /// Execute the show command.
-fn execute(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
     let id = args[0].int().unwrap();
+    let show_history = options
+        .get("history")
+        .and_then(|v| v.bool())
+        .unwrap_or(false);
+
+    if !show_history {
+        print_item(persistence::get_item_by_id(id).unwrap_or_else(error::exit_with));
+        return;
+    }
 
-    match persistence::find_item_by_id(id) {
-        Ok(optional_item) => match optional_item {
-            Some(item) => print_item(item),
-            None => println!("Could not find work item with ID {}.", id),
-        },
-        Err(e) => println!(
-            "Failed to show details for work item with ID {}. Error: '{}'.",
-            id, e
-        ),
+    let history = persistence::item_history(id).unwrap_or_else(error::exit_with);
+    match persistence::find_item_by_id(id).unwrap_or_else(error::exit_with) {
+        Some(item) => print_item(item),
+        None if !history.is_empty() => {
+            // The history of deleted work items is still available
+            print_header(&format!("History of deleted work item with ID {}", id));
+            println!();
+        }
+        None => error::exit_with(persistence::Error::NotFound(id)),
     }
+
+    print_history(&history);
 }
 
 /// Print the work item.
@@ -75,6 +94,14 @@ fn print_item(item: WorkItem) {
 
     println!();
 
+    if let Some(project) = item.project() {
+        println!("{}", "# Project".underlined());
+
+        println!("{}", project.color(colorful::Color::Gold1));
+
+        println!();
+    }
+
     println!("{}", "# Events".underlined());
 
     for event in item.events() {
@@ -87,6 +114,23 @@ fn print_item(item: WorkItem) {
 
     println!();
 
+    if !item.notes().is_empty() {
+        println!("{}", "# Notes".underlined());
+
+        for note in item.notes() {
+            println!(
+                "  • [{}] {}",
+                shared::time::get_local_date_time(note.timestamp())
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+                    .color(colorful::Color::DarkSlateGray1),
+                note.text()
+            );
+        }
+
+        println!();
+    }
+
     println!("{}", "# Statistics".underlined());
 
     println!(
@@ -94,9 +138,67 @@ fn print_item(item: WorkItem) {
         shared::time::format_duration((item.time_taken() / 1000) as u32)
     );
 
+    if let Some(policy) = shared::calc::active_rounding_policy() {
+        let rounded = calculate_rounded_total_time(&[&item], i64::MIN, i64::MAX, &policy);
+
+        println!(
+            "  • Rounded time: {} ({})",
+            shared::time::format_duration((rounded / 1000) as u32),
+            policy
+        );
+    }
+
     println!();
 }
 
+/// Print the revision history of a work item.
+fn print_history(revisions: &[Revision]) {
+    println!("{}", "# History".underlined());
+
+    for revision in revisions {
+        println!(
+            "  • [{}] {} {}: {} -> {}",
+            shared::time::get_local_date_time(revision.timestamp())
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .color(colorful::Color::DarkSlateGray1),
+            format!("({})", revision.origin()).color(colorful::Color::Gold1),
+            revision.field(),
+            format_revision_value(revision.field(), revision.old_value()),
+            format_revision_value(revision.field(), revision.new_value())
+        );
+    }
+
+    println!();
+}
+
+/// Format a value of the given field recorded in the revision history.
+fn format_revision_value(field: RevisionField, value: Option<&str>) -> String {
+    let value = match value {
+        Some(value) => value,
+        None => return String::from("-"),
+    };
+
+    match field {
+        RevisionField::Events => match serde_json::from_str::<Vec<Event>>(value) {
+            Ok(events) => events
+                .iter()
+                .map(|event| {
+                    format!(
+                        "{} {}",
+                        event.event_type(),
+                        shared::time::get_local_date_time(event.timestamp())
+                            .format("%Y-%m-%d %H:%M")
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+            Err(_) => value.to_owned(),
+        },
+        _ => format!("'{}'", value),
+    }
+}
+
 /// Print a header string to the console.
 fn print_header(str: &str) {
     println!(" {} ", "-".repeat(str.len() + 2));
@@ -6,15 +6,18 @@ use colorful::Colorful;
 
 use persistence::calc::Status;
 
+use crate::at;
 use crate::command::command::Command;
 use crate::command::{finish, pause};
+use crate::error;
+use crate::project;
 
 /// Command used to start a work item.
 pub struct StartCommand {}
 
 impl Command for StartCommand {
     fn build(&self) -> Group {
-        Group::new(
+        let group = Group::new(
             Box::new(|args, options| execute(args, options)),
             "Start working on a work item",
         )
@@ -32,7 +35,9 @@ impl Command for StartCommand {
             "finish",
             option::Type::Bool { default: false },
             "Finish all work items currently in progress",
-        ))
+        ));
+
+        project::add_option(at::add_option(group))
     }
 
     fn aliases(&self) -> Option<Vec<&str>> {
@@ -53,26 +58,30 @@ fn execute(args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
 
     let pause_work_items_in_progress = options.get("pause").unwrap().bool().unwrap();
     let finish_work_items_in_progress = options.get("finish").unwrap().bool().unwrap();
+    let timestamp = at::timestamp_from_options(options);
+    let project = project::assignment_from_options(options).flatten();
 
     // If both --pause and --finish are specified we are finishing all items!
 
     // Stopping in progress work items first
     if finish_work_items_in_progress || pause_work_items_in_progress {
-        pause::pause_all_work_items_in_progress();
+        pause::pause_all_work_items_in_progress(timestamp);
     }
 
     // When --finish specified -> Finish all paused work items
     if finish_work_items_in_progress {
-        finish::finish_all_paused_work_items();
+        finish::finish_all_paused_work_items(timestamp);
     }
 
-    let item = persistence::calc::WorkItem::new(
+    let mut item = persistence::calc::WorkItem::new_at(
         description.to_owned(),
         Status::InProgress,
         HashSet::from_iter(tags.into_iter()),
+        timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
     );
+    item.set_project(project);
 
-    let new_id = persistence::log_item(item).unwrap();
+    let new_id = persistence::log_item(item).unwrap_or_else(error::exit_with);
 
     println!(
         "Started working on work item with ID {}.",
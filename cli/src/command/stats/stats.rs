@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use cmd_args::{arg, option, Group};
+use colorful::Colorful;
+use persistence::calc::{
+    calculate_project_statistics, calculate_rounded_total_time, calculate_tag_statistics,
+    calculate_total_work_time, ProjectStatistics, TagStatistics, WorkItem,
+};
+use serde::Serialize;
+use shared::calc::RoundingPolicy;
+
+use crate::command::command::Command;
+use crate::command::list;
+use crate::error;
+
+/// Label of the work items without tags.
+const UNTAGGED_LABEL: &str = "(untagged)";
+
+/// Label of the work items not assigned to any project.
+const NO_PROJECT_LABEL: &str = "(no project)";
+
+/// Command used to show statistics about the time worked per tag or project.
+pub struct StatsCommand {}
+
+/// Statistics written when passing the --json option.
+#[derive(Serialize)]
+struct StatisticsReport<'a> {
+    /// Inclusive lower bound of the time range (None when showing all work items).
+    from_timestamp: Option<i64>,
+    /// Exclusive upper bound of the time range (None when showing all work items).
+    to_timestamp: Option<i64>,
+    /// Wall-clock time worked on all work items (in milliseconds).
+    total_time: i64,
+    /// Description of how times are rounded (None when not rounding).
+    rounding: Option<String>,
+    /// Time worked on all work items rounded using the rounding policy (in milliseconds).
+    rounded_total_time: Option<i64>,
+    /// Statistics per tag (None when grouping by project).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<&'a [TagStatistics]>,
+    /// Statistics per project (None when grouping by tag).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    projects: Option<&'a [ProjectStatistics]>,
+}
+
+/// Statistics grouped by tag or project.
+enum Statistics {
+    /// Statistics per tag.
+    Tags(Vec<TagStatistics>),
+    /// Statistics per project.
+    Projects(Vec<ProjectStatistics>),
+}
+
+impl Command for StatsCommand {
+    fn build(&self) -> Group {
+        let group = Group::new(
+            Box::new(|args, options| execute(args, options)),
+            "Show the time worked per tag (or project)",
+        )
+        .add_option(option::Descriptor::new(
+            "all",
+            option::Type::Bool { default: false },
+            "Consider all work items regardless of the filter",
+        ))
+        .add_option(option::Descriptor::new(
+            "filter",
+            option::Type::Str {
+                default: String::from("this-week"),
+            },
+            "Filter by a date ('today', 'yesterday', '2020-02-20' (yyyy-MM-dd)), a week ('this-week' (default), 'last-week', '2021-W05'), a month ('this-month', 'last-month', '2021-01') or a range ('2021-01-01..2021-01-31')",
+        ))
+        .add_option(option::Descriptor::new(
+            "json",
+            option::Type::Bool { default: false },
+            "Print the statistics as JSON (times in milliseconds)",
+        ))
+        .add_option(option::Descriptor::new(
+            "by-project",
+            option::Type::Bool { default: false },
+            "Show the time worked and amount to bill per project instead of per tag",
+        ));
+
+        list::add_query_options(group)
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        Some(vec!["statistics"])
+    }
+
+    fn name(&self) -> &str {
+        "stats"
+    }
+}
+
+/// Execute the stats command.
+fn execute(_args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let all = options.get("all").and_then(|v| v.bool()).unwrap_or(false);
+    let json = options.get("json").and_then(|v| v.bool()).unwrap_or(false);
+    let by_project = options
+        .get("by-project")
+        .and_then(|v| v.bool())
+        .unwrap_or(false);
+
+    let query = list::build_query(options);
+    let (time_range, items) = if all {
+        let items = persistence::query_items(&query).unwrap_or_else(error::exit_with);
+
+        (None, items)
+    } else {
+        let filter = options
+            .get("filter")
+            .and_then(|v| v.str())
+            .unwrap_or("this-week");
+        let (from_timestamp, to_timestamp) = shared::time::resolve_date_range(filter)
+            .unwrap_or_else(|e| error::exit_with_message(&e));
+        let items = persistence::query_items(&query.time_range(from_timestamp, to_timestamp))
+            .unwrap_or_else(error::exit_with);
+
+        (Some((from_timestamp, to_timestamp)), items)
+    };
+
+    let (from_timestamp, to_timestamp) = time_range.unwrap_or((i64::MIN, i64::MAX));
+    let item_refs: Vec<&WorkItem> = items.iter().collect();
+    let total_time = calculate_total_work_time(&item_refs, from_timestamp, to_timestamp);
+    let rounding_policy = shared::calc::active_rounding_policy();
+    let rounded_total_time = rounding_policy.as_ref().map(|policy| {
+        calculate_rounded_total_time(&item_refs, from_timestamp, to_timestamp, policy)
+    });
+    let statistics = match by_project {
+        true => Statistics::Projects(calculate_project_statistics(
+            &item_refs,
+            &persistence::list_projects().unwrap_or_else(error::exit_with),
+            from_timestamp,
+            to_timestamp,
+            rounding_policy.as_ref(),
+        )),
+        false => Statistics::Tags(calculate_tag_statistics(
+            &item_refs,
+            from_timestamp,
+            to_timestamp,
+            rounding_policy.as_ref(),
+        )),
+    };
+
+    if json {
+        let report = StatisticsReport {
+            from_timestamp: time_range.map(|(from_timestamp, _)| from_timestamp),
+            to_timestamp: time_range.map(|(_, to_timestamp)| to_timestamp),
+            total_time,
+            rounding: rounding_policy.map(|policy| policy.to_string()),
+            rounded_total_time,
+            tags: match &statistics {
+                Statistics::Tags(tags) => Some(tags),
+                Statistics::Projects(_) => None,
+            },
+            projects: match &statistics {
+                Statistics::Tags(_) => None,
+                Statistics::Projects(projects) => Some(projects),
+            },
+        };
+
+        let json = serde_json::to_string_pretty(&report).unwrap_or_else(|e| {
+            error::exit_with_message(&format!("Unable to write the statistics. Error: '{}'.", e))
+        });
+        println!("{}", json);
+    } else {
+        print_statistics(
+            &statistics,
+            total_time,
+            rounding_policy.zip(rounded_total_time),
+        );
+    }
+}
+
+/// Print the given statistics as table.
+/// Adds the rounded time when passing the rounding policy and rounded total time.
+fn print_statistics(
+    statistics: &Statistics,
+    total_time: i64,
+    rounding: Option<(RoundingPolicy, i64)>,
+) {
+    println!();
+    println!(
+        "{}",
+        format!("Total time worked: {}", format_duration(total_time)).bold()
+    );
+    if let Some((policy, rounded_total_time)) = &rounding {
+        println!(
+            "{}",
+            format!(
+                "Rounded time: {} ({})",
+                format_duration(*rounded_total_time),
+                policy
+            )
+            .bold()
+        );
+    }
+    println!();
+
+    let (header, rows) = match statistics {
+        Statistics::Tags(statistics) => tag_table(statistics, rounding.is_some()),
+        Statistics::Projects(statistics) => project_table(statistics, rounding.is_some()),
+    };
+    if rows.is_empty() {
+        println!("There are no work items to show statistics for.");
+        return;
+    }
+
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| -> String {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+
+    println!("  {}", format_row(header).underlined());
+    for row in &rows {
+        println!("  {}", format_row(row.iter().map(|c| c.as_str()).collect()));
+    }
+    println!();
+}
+
+/// Build the header and rows of the table showing the given statistics per tag.
+fn tag_table(statistics: &[TagStatistics], rounded: bool) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let mut header = vec![
+        "Tag",
+        "Time",
+        "Share",
+        "Items",
+        "Average per item",
+        "Sum of item times",
+    ];
+    if rounded {
+        header.push("Rounded time");
+    }
+    let rows = statistics
+        .iter()
+        .map(|s| {
+            let mut row = vec![
+                s.tag().unwrap_or(UNTAGGED_LABEL).to_owned(),
+                format_duration(s.unique_time()),
+                format!("{:.1} %", s.share() * 100.0),
+                s.item_count().to_string(),
+                format_duration(s.average_item_time()),
+                format_duration(s.total_time()),
+            ];
+            if let Some(rounded_time) = s.rounded_time() {
+                row.push(format_duration(rounded_time));
+            }
+
+            row
+        })
+        .collect();
+
+    (header, rows)
+}
+
+/// Build the header and rows of the table showing the given statistics per project.
+fn project_table(
+    statistics: &[ProjectStatistics],
+    rounded: bool,
+) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let mut header = vec!["Project", "Client", "Time", "Share", "Items"];
+    if rounded {
+        header.push("Rounded time");
+    }
+    header.extend_from_slice(&["Hourly rate", "Amount"]);
+
+    let rows = statistics
+        .iter()
+        .map(|s| {
+            let mut row = vec![
+                s.project().unwrap_or(NO_PROJECT_LABEL).to_owned(),
+                s.client().unwrap_or("-").to_owned(),
+                format_duration(s.time()),
+                format!("{:.1} %", s.share() * 100.0),
+                s.item_count().to_string(),
+            ];
+            if let Some(rounded_time) = s.rounded_time() {
+                row.push(format_duration(rounded_time));
+            }
+            row.push(format_amount(s.hourly_rate()));
+            row.push(format_amount(s.amount()));
+
+            row
+        })
+        .collect();
+
+    (header, rows)
+}
+
+/// Format the given amount of money (or '-' if there is none).
+fn format_amount(amount: Option<f64>) -> String {
+    amount.map_or(String::from("-"), |amount| format!("{:.2}", amount))
+}
+
+/// Format the given duration (in milliseconds).
+fn format_duration(duration: i64) -> String {
+    shared::time::format_duration((duration / 1000) as u32)
+}
@@ -0,0 +1,3 @@
+mod trash;
+
+pub use trash::TrashCommand;
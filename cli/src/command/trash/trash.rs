@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use cmd_args::{arg, option, Group};
+use colorful::Colorful;
+
+use persistence::calc::TrashedItem;
+use shared::time::DurationError;
+
+use crate::command::command::Command;
+use crate::error;
+
+/// Command used to list, restore and purge deleted work items.
+pub struct TrashCommand {}
+
+impl Command for TrashCommand {
+    fn build(&self) -> Group {
+        Group::new(
+            Box::new(|args, options| execute_list(args, options)),
+            "Manage the deleted work items in the trash (lists them when no sub-command is given)",
+        )
+        .add_child(
+            "list",
+            Some(vec!["ls"]),
+            crate::add_global_options(Group::new(
+                Box::new(|args, options| execute_list(args, options)),
+                "List the work items in the trash (most recently deleted first)",
+            )),
+        )
+        .add_child(
+            "restore",
+            None,
+            crate::add_global_options(
+                Group::new(
+                    Box::new(|args, options| execute_restore(args, options)),
+                    "Restore a work item from the trash",
+                )
+                .add_argument(arg::Descriptor::new(
+                    arg::Type::Int,
+                    "ID of the work item to restore",
+                )),
+            ),
+        )
+        .add_child(
+            "purge",
+            None,
+            crate::add_global_options(
+                Group::new(
+                    Box::new(|args, options| execute_purge(args, options)),
+                    "Permanently delete the work items that have been in the trash for a while",
+                )
+                .add_option(option::Descriptor::new(
+                    "older-than",
+                    option::Type::Str {
+                        default: String::from("30d"),
+                    },
+                    "Minimum time the work items have been in the trash, e. g. '30d' (default), '12h' or '0d' for all",
+                )),
+            ),
+        )
+    }
+
+    fn aliases(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        "trash"
+    }
+}
+
+/// Execute the trash list command.
+fn execute_list(_args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+    let trash = persistence::list_trash().unwrap_or_else(error::exit_with);
+
+    if trash.is_empty() {
+        println!("The trash is empty.");
+        return;
+    }
+
+    for trashed_item in &trash {
+        println!("  • {}", format_trashed_item(trashed_item));
+    }
+
+    println!();
+    println!("Restore a work item using 'worklog trash restore ID'.");
+}
+
+/// Execute the trash restore command.
+fn execute_restore(args: &Vec<arg::Value>, _options: &HashMap<&str, option::Value>) {
+    let id = args[0]
+        .int()
+        .expect("Expected first argument to be a work item ID");
+
+    persistence::restore_from_trash(id).unwrap_or_else(error::exit_with);
+
+    println!("Restored work item with ID {} from the trash.", id);
+}
+
+/// Execute the trash purge command.
+fn execute_purge(_args: &Vec<arg::Value>, options: &HashMap<&str, option::Value>) {
+    let older_than_str = options
+        .get("older-than")
+        .and_then(|v| v.str())
+        .unwrap_or("30d")
+        .trim();
+
+    let invalid_message = format!(
+        "The duration '{}' is invalid. Use for example '30d', '12h' or '1d 12h'.",
+        older_than_str
+    );
+    if !older_than_str.ends_with(['d', 'h', 'm', 's']) {
+        error::exit_with_message(&invalid_message)
+    }
+
+    let older_than = match shared::time::parse_duration(older_than_str) {
+        Ok(seconds) if seconds >= 0 => seconds as i64,
+        Err(e @ DurationError::Overflow(_)) => error::exit_with_message(&e.to_string()),
+        _ => error::exit_with_message(&invalid_message),
+    };
+
+    let deleted_before = chrono::Utc::now().timestamp_millis() - older_than * 1000;
+    let count = persistence::purge_trash(deleted_before).unwrap_or_else(error::exit_with);
+
+    println!(
+        "Permanently deleted {} work items that have been in the trash for at least {}.",
+        count, older_than_str
+    );
+}
+
+/// Format a work item in the trash.
+fn format_trashed_item(trashed_item: &TrashedItem) -> String {
+    let item = trashed_item.item();
+
+    let id_str = format!(
+        "#{}",
+        item.id().expect("Work item must have an ID at this point!")
+    )
+    .color(colorful::Color::DodgerBlue3);
+
+    let date_str = shared::time::get_local_date_time(item.created_timestamp())
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+        .color(colorful::Color::DeepPink1a);
+
+    let deleted_str = shared::time::get_local_date_time(trashed_item.deleted_timestamp())
+        .format("%Y-%m-%d %H:%M")
+        .to_string();
+
+    format!(
+        "{} [{}] {} (deleted {})",
+        id_str,
+        date_str,
+        item.description(),
+        deleted_str
+    )
+}
@@ -0,0 +1,3 @@
+mod undo;
+
+pub use undo::UndoCommand;
@@ -0,0 +1,42 @@
+use cmd_args::{option, Group};
+
+use persistence::DatabaseLocation;
+
+use crate::args::find_option_value;
+
+/// Name of the option selecting a database file.
+const DATABASE_OPTION: &str = "db";
+
+/// Name of the option selecting a named profile.
+const PROFILE_OPTION: &str = "profile";
+
+/// Add the options used to select the database to the given command group.
+pub(crate) fn add_options(group: Group) -> Group {
+    group
+        .add_option(option::Descriptor::new(
+            DATABASE_OPTION,
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Path of the logs database file to use (':memory:' for a volatile in-memory database)",
+        ))
+        .add_option(option::Descriptor::new(
+            PROFILE_OPTION,
+            option::Type::Str {
+                default: String::from(""),
+            },
+            "Name of the profile to use (e. g. 'acme')",
+        ))
+}
+
+/// Select the database to use based on the raw command line arguments.
+/// This happens before dispatching to a command so that the options are
+/// respected no matter whether they are placed before or after the command name.
+/// The '--db' option takes precedence over the '--profile' option.
+pub(crate) fn select_from_args(args: &[String]) {
+    if let Some(db_path) = find_option_value(args, DATABASE_OPTION) {
+        persistence::select_database(DatabaseLocation::from_path(&db_path));
+    } else if let Some(profile) = find_option_value(args, PROFILE_OPTION) {
+        persistence::select_database(DatabaseLocation::Profile(profile));
+    }
+}
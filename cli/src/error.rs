@@ -0,0 +1,44 @@
+use std::process;
+
+use persistence::Error;
+
+/// Exit code for errors without a more specific exit code.
+const EXIT_CODE_GENERAL: i32 = 1;
+
+/// Exit code used when a work item (or project) could not be found.
+const EXIT_CODE_NOT_FOUND: i32 = 2;
+
+/// Exit code used when a work item is not in the state required by the operation.
+const EXIT_CODE_INVALID_TRANSITION: i32 = 3;
+
+/// Exit code used when the database is locked by another process.
+const EXIT_CODE_LOCKED: i32 = 4;
+
+/// Exit code used when the database has been created by a newer version.
+const EXIT_CODE_SCHEMA_TOO_NEW: i32 = 5;
+
+/// Print the given error and exit with an exit code specific to the kind of error.
+/// Meant to be used like `persistence::list_items().unwrap_or_else(error::exit_with)`.
+pub(crate) fn exit_with<T>(error: Error) -> T {
+    eprintln!("{}.", error);
+
+    process::exit(exit_code(&error))
+}
+
+/// Print the given message and exit with the general error exit code.
+pub(crate) fn exit_with_message<T>(message: &str) -> T {
+    eprintln!("{}", message);
+
+    process::exit(EXIT_CODE_GENERAL)
+}
+
+/// Get the exit code for the given error.
+fn exit_code(error: &Error) -> i32 {
+    match error {
+        Error::NotFound(_) | Error::ProjectNotFound(_) => EXIT_CODE_NOT_FOUND,
+        Error::InvalidTransition(_) | Error::InvalidTimeline(_) => EXIT_CODE_INVALID_TRANSITION,
+        Error::Locked(_) => EXIT_CODE_LOCKED,
+        Error::SchemaTooNew { .. } => EXIT_CODE_SCHEMA_TOO_NEW,
+        _ => EXIT_CODE_GENERAL,
+    }
+}
@@ -1,23 +1,49 @@
 use cmd_args::{parser, Group};
-use std::process;
+use persistence::revision::Origin;
+use std::{env, process};
 
+mod args;
+mod at;
 mod command;
+mod database;
+mod error;
+mod project;
+mod rounding;
+mod time_zone;
 
 fn main() {
-    let mut group = Group::new(
+    // Select the database, time zone and rounding policy to work with before executing any command
+    let args: Vec<String> = env::args().collect();
+    database::select_from_args(&args);
+    time_zone::select_from_args(&args);
+    rounding::select_from_args(&args);
+
+    // Changes made by the CLI are recorded as such in the revision history (imports excepted)
+    persistence::select_origin(Origin::Cli);
+
+    let mut group = add_global_options(Group::new(
         Box::new(|_args, _options| {
             println!("### Incorrect usage ###");
             println!("Pass '--help' to see all available options.");
             process::exit(1);
         }),
         "Tool to log your work",
-    );
+    ));
 
     // Add all sub-commands
     for command in &command::COMMANDS {
-        group = group.add_child(command.name(), command.aliases(), command.build());
+        group = group.add_child(
+            command.name(),
+            command.aliases(),
+            add_global_options(command.build()),
+        );
     }
 
     // Start the command line parser
     parser::parse(group, None).unwrap();
 }
+
+/// Add the options available to every command to the given command group.
+fn add_global_options(group: Group) -> Group {
+    rounding::add_options(time_zone::add_options(database::add_options(group)))
+}
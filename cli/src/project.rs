@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use cmd_args::{option, Group};
+
+use crate::error;
+
+/// Name of the option assigning work items to a project.
+const PROJECT_OPTION: &str = "project";
+
+/// Project name removing work items from their project.
+pub(crate) const NO_PROJECT: &str = "none";
+
+/// Add the option assigning work items to a project to the given command group.
+pub(crate) fn add_option(group: Group) -> Group {
+    group.add_option(option::Descriptor::new(
+        PROJECT_OPTION,
+        option::Type::Str {
+            default: String::from(""),
+        },
+        "Name of the project to assign the work item to ('none' for no project)",
+    ))
+}
+
+/// Get the project assignment passed using the option added with `add_option`.
+/// Returns None if the option is not given, `Some(None)` to remove work items from
+/// their project and `Some(Some(name))` to assign them to the project with the given name.
+/// Exits when the project does not exist or is archived.
+pub(crate) fn assignment_from_options(
+    options: &HashMap<&str, option::Value>,
+) -> Option<Option<String>> {
+    let name = options
+        .get(PROJECT_OPTION)
+        .and_then(|v| v.str())
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())?;
+
+    Some(assignable_project(name))
+}
+
+/// Check that work items may be assigned to the project with the given name
+/// (`None` for 'none', meaning no project).
+/// Exits when the project does not exist or is archived.
+pub(crate) fn assignable_project(name: &str) -> Option<String> {
+    if name == NO_PROJECT {
+        return None;
+    }
+
+    let project = persistence::get_project_by_name(name).unwrap_or_else(error::exit_with);
+    if project.is_archived() {
+        error::exit_with_message(&format!(
+            "The project '{}' is archived. Unarchive it using 'worklog project unarchive --name {}' first.",
+            name, name
+        ))
+    }
+
+    Some(project.name().to_owned())
+}
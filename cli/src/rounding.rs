@@ -0,0 +1,40 @@
+use std::env;
+
+use cmd_args::{option, Group};
+
+use shared::calc::{RoundingPolicy, ROUNDING_ENV_VARIABLE};
+
+use crate::args::find_option_value;
+use crate::error;
+
+/// Name of the option selecting the rounding policy.
+const ROUNDING_OPTION: &str = "rounding";
+
+/// Add the option used to select the rounding policy to the given command group.
+pub(crate) fn add_options(group: Group) -> Group {
+    group.add_option(option::Descriptor::new(
+        ROUNDING_OPTION,
+        option::Type::Str {
+            default: String::from(""),
+        },
+        "Round the time worked for billing as 'MODE:UNIT[:SCOPE[:MINIMUM]]' with mode 'up', 'down' or 'nearest' and scope 'interval', 'item' (default) or 'day' (e. g. 'up:15m', 'nearest:6m:day:30m' or 'none')",
+    ))
+}
+
+/// Select the rounding policy to use based on the raw command line arguments.
+/// The '--rounding' option takes precedence over the WORKLOG_ROUNDING environment variable.
+/// Exits when the given rounding policy is invalid.
+pub(crate) fn select_from_args(args: &[String]) {
+    let spec = find_option_value(args, ROUNDING_OPTION).or_else(|| {
+        env::var(ROUNDING_ENV_VARIABLE)
+            .ok()
+            .filter(|v| !v.is_empty())
+    });
+
+    if let Some(spec) = spec {
+        let policy =
+            RoundingPolicy::from_spec(&spec).unwrap_or_else(|e| error::exit_with_message(&e));
+
+        shared::calc::select_rounding_policy(policy);
+    }
+}
@@ -0,0 +1,40 @@
+use std::env;
+
+use cmd_args::{option, Group};
+
+use shared::time::{TimeZoneSetting, TIME_ZONE_ENV_VARIABLE};
+
+use crate::args::find_option_value;
+use crate::error;
+
+/// Name of the option selecting the time zone.
+const TIME_ZONE_OPTION: &str = "timezone";
+
+/// Add the option used to select the time zone to the given command group.
+pub(crate) fn add_options(group: Group) -> Group {
+    group.add_option(option::Descriptor::new(
+        TIME_ZONE_OPTION,
+        option::Type::Str {
+            default: String::from(""),
+        },
+        "Time zone to determine dates and day boundaries in (e. g. 'Europe/Berlin', defaults to the system time zone)",
+    ))
+}
+
+/// Select the time zone to use based on the raw command line arguments.
+/// The '--timezone' option takes precedence over the WORKLOG_TZ environment variable.
+/// Exits when the given time zone is unknown.
+pub(crate) fn select_from_args(args: &[String]) {
+    let name = find_option_value(args, TIME_ZONE_OPTION).or_else(|| {
+        env::var(TIME_ZONE_ENV_VARIABLE)
+            .ok()
+            .filter(|v| !v.is_empty())
+    });
+
+    if let Some(name) = name {
+        let setting =
+            TimeZoneSetting::from_name(&name).unwrap_or_else(|e| error::exit_with_message(&e));
+
+        shared::time::select_time_zone(setting);
+    }
+}
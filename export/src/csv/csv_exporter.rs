@@ -0,0 +1,327 @@
+use std::io;
+
+use persistence::calc::{round_item_time, WorkItem};
+use shared::calc::RoundingScope;
+
+use crate::{Exporter, ReportContext};
+
+/// Option holding the delimiter separating the fields ('tab' for a tab character, defaults to ',').
+pub const DELIMITER_OPTION: &str = "delimiter";
+
+/// Option holding the mode of the export ('intervals' (default) or 'items').
+pub const MODE_OPTION: &str = "mode";
+
+/// Line break terminating a record as defined by RFC 4180.
+const LINE_BREAK: &str = "\r\n";
+
+/// Columns of the export.
+const HEADER: [&str; 9] = [
+    "id",
+    "description",
+    "tags",
+    "status",
+    "start",
+    "end",
+    "duration",
+    "rounded_duration",
+    "project",
+];
+
+/// Exporter writing a CSV timesheet quoted as defined by RFC 4180.
+/// Writes one row per active interval in the reported time range by default
+/// or one row per work item with its total time taken in the time range in the 'items' mode.
+/// The rounded duration is left empty without a rounding policy, for intervals unless
+/// every interval is rounded and for work items when the time worked is rounded per day.
+/// The last column holds the project of the work item (empty when it is not assigned to one).
+pub struct CsvExporter {}
+
+/// Mode of the CSV export.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Mode {
+    /// One row per active interval of a work item.
+    Intervals,
+    /// One row per work item.
+    Items,
+}
+
+impl Exporter for CsvExporter {
+    fn name(&self) -> &str {
+        "csv"
+    }
+
+    fn file_extension(&self) -> &str {
+        "csv"
+    }
+
+    fn export(
+        &self,
+        items: &[WorkItem],
+        context: &ReportContext,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let delimiter = parse_delimiter(context.option(DELIMITER_OPTION))?;
+        let mode = parse_mode(context.option(MODE_OPTION))?;
+
+        let from_timestamp = context.from_timestamp();
+        let to_timestamp = context.to_timestamp();
+
+        let rounding_policy = context.rounding_policy();
+
+        write_record(writer, delimiter, &HEADER)?;
+
+        for item in items {
+            let id = item.id().map_or(String::new(), |id| id.to_string());
+            let tags = item.tags().join(", ");
+            let status = format!("{}", item.status());
+            let intervals = item.intervals_in_range(from_timestamp, to_timestamp);
+
+            let rows: Vec<(i64, i64, i64, Option<i64>)> = match mode {
+                Mode::Intervals => intervals
+                    .iter()
+                    .map(|(start, end)| {
+                        let rounded = rounding_policy
+                            .filter(|policy| policy.scope() == RoundingScope::Interval)
+                            .map(|policy| policy.round(end - start));
+
+                        (*start, *end, end - start, rounded)
+                    })
+                    .collect(),
+                Mode::Items => match (intervals.first(), intervals.last()) {
+                    (Some((start, _)), Some((_, end))) => vec![(
+                        *start,
+                        *end,
+                        item.time_taken_in_range(from_timestamp, to_timestamp),
+                        rounding_policy.and_then(|policy| {
+                            round_item_time(item, from_timestamp, to_timestamp, policy)
+                        }),
+                    )],
+                    _ => Vec::new(),
+                },
+            };
+
+            for (start, end, duration, rounded) in rows {
+                let fields = [
+                    id.clone(),
+                    item.description().to_owned(),
+                    tags.clone(),
+                    status.clone(),
+                    format_timestamp(start),
+                    format_timestamp(end),
+                    format_duration(duration),
+                    rounded.map_or(String::new(), format_duration),
+                    item.project().unwrap_or("").to_owned(),
+                ];
+
+                let fields: Vec<&str> = fields.iter().map(|f| f.as_str()).collect();
+                write_record(writer, delimiter, &fields)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the delimiter from the passed option value.
+fn parse_delimiter(value: Option<&str>) -> io::Result<char> {
+    match value {
+        None | Some("") => Ok(','),
+        Some("tab") | Some("\\t") => Ok('\t'),
+        Some(value) => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '"' && c != '\r' && c != '\n' => Ok(c),
+                _ => Err(invalid_input(format!(
+                    "The CSV delimiter must be a single character (or 'tab') but got '{}'",
+                    value
+                ))),
+            }
+        }
+    }
+}
+
+/// Parse the mode from the passed option value.
+fn parse_mode(value: Option<&str>) -> io::Result<Mode> {
+    match value {
+        None | Some("") | Some("intervals") => Ok(Mode::Intervals),
+        Some("items") => Ok(Mode::Items),
+        Some(value) => Err(invalid_input(format!(
+            "Unknown CSV mode '{}'. Use 'intervals' or 'items'",
+            value
+        ))),
+    }
+}
+
+/// Create an error signalling an invalid option.
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// Write a single record with the given fields.
+fn write_record(writer: &mut dyn io::Write, delimiter: char, fields: &[&str]) -> io::Result<()> {
+    let quoted: Vec<String> = fields.iter().map(|f| quote(f, delimiter)).collect();
+
+    write!(
+        writer,
+        "{}{}",
+        quoted.join(&delimiter.to_string()),
+        LINE_BREAK
+    )
+}
+
+/// Quote the given field if necessary as defined by RFC 4180.
+fn quote(field: &str, delimiter: char) -> String {
+    let needs_quotes = field
+        .chars()
+        .any(|c| c == delimiter || c == '"' || c == '\r' || c == '\n');
+
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Format the given timestamp as ISO 8601 date time in the active time zone.
+fn format_timestamp(timestamp: i64) -> String {
+    shared::time::get_local_date_time(timestamp)
+        .format("%Y-%m-%dT%H:%M:%S%:z")
+        .to_string()
+}
+
+/// Format the given duration (in milliseconds) as 'H:MM:SS'.
+fn format_duration(duration: i64) -> String {
+    let seconds = duration / 1000;
+
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use persistence::calc::event::{Event, EventType};
+    use persistence::calc::Status;
+    use shared::calc::RoundingPolicy;
+    use std::collections::HashSet;
+
+    fn export(context: &ReportContext) -> io::Result<String> {
+        let items = vec![WorkItem::new_internal(
+            7,
+            String::from("Fix \"quoted\", and separated"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 0),
+                Event::new(EventType::Paused, 60 * 60 * 1000),
+                Event::new(EventType::Continued, 90 * 60 * 1000),
+                Event::new(EventType::Finished, 100 * 60 * 1000),
+            ],
+        )];
+
+        let mut output = Vec::new();
+        CsvExporter {}.export(&items, context, &mut output)?;
+
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_export_intervals() {
+        let output = export(&ReportContext::new(0, 24 * 60 * 60 * 1000)).unwrap();
+        let lines: Vec<&str> = output.split(LINE_BREAK).collect();
+
+        assert_eq!(
+            lines[0],
+            "id,description,tags,status,start,end,duration,rounded_duration,project"
+        );
+        assert!(lines[1].starts_with("7,\"Fix \"\"quoted\"\", and separated\",,DONE,"));
+        assert!(lines[1].ends_with(",1:00:00,,"));
+        assert!(lines[2].ends_with(",0:10:00,,"));
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_export_items_with_delimiter() {
+        let output = export(
+            &ReportContext::new(0, 24 * 60 * 60 * 1000)
+                .with_option(MODE_OPTION, "items")
+                .with_option(DELIMITER_OPTION, ";"),
+        )
+        .unwrap();
+        let lines: Vec<&str> = output.split(LINE_BREAK).collect();
+
+        assert_eq!(
+            lines[0],
+            "id;description;tags;status;start;end;duration;rounded_duration;project"
+        );
+        assert!(lines[1].starts_with("7;\"Fix \"\"quoted\"\", and separated\";;DONE;"));
+        assert!(lines[1].ends_with(";1:10:00;;"));
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_export_rounded() {
+        let context = ReportContext::new(0, 24 * 60 * 60 * 1000);
+
+        let per_interval = RoundingPolicy::from_spec("up:15m:interval").unwrap();
+        let output = export(&context.clone().with_rounding_policy(per_interval)).unwrap();
+        let lines: Vec<&str> = output.split(LINE_BREAK).collect();
+        assert!(lines[0].ends_with(",duration,rounded_duration,project"));
+        assert!(lines[1].ends_with(",1:00:00,1:00:00,"));
+        assert!(lines[2].ends_with(",0:10:00,0:15:00,"));
+
+        let per_item = RoundingPolicy::from_spec("up:15m").unwrap();
+        let output = export(&context.with_rounding_policy(per_item)).unwrap();
+        let lines: Vec<&str> = output.split(LINE_BREAK).collect();
+        assert!(lines[1].ends_with(",1:00:00,,"));
+
+        let output = export(
+            &ReportContext::new(0, 24 * 60 * 60 * 1000)
+                .with_option(MODE_OPTION, "items")
+                .with_rounding_policy(per_item),
+        )
+        .unwrap();
+        let lines: Vec<&str> = output.split(LINE_BREAK).collect();
+        assert!(lines[1].ends_with(",1:10:00,1:15:00,"));
+    }
+
+    #[test]
+    fn test_export_project() {
+        let mut item = WorkItem::new_internal(
+            8,
+            String::from("Landing page"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 0),
+                Event::new(EventType::Finished, 60 * 60 * 1000),
+            ],
+        );
+        item.set_project(Some(String::from("website")));
+
+        let mut output = Vec::new();
+        CsvExporter {}
+            .export(
+                &[item],
+                &ReportContext::new(0, 24 * 60 * 60 * 1000),
+                &mut output,
+            )
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.split(LINE_BREAK).collect();
+
+        assert!(lines[0].ends_with(",duration,rounded_duration,project"));
+        assert!(lines[1].ends_with(",1:00:00,,website"));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let context = ReportContext::new(0, 1);
+
+        assert!(export(&context.clone().with_option(DELIMITER_OPTION, ";;")).is_err());
+        assert!(export(&context.with_option(MODE_OPTION, "days")).is_err());
+    }
+}
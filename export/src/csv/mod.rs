@@ -0,0 +1,3 @@
+mod csv_exporter;
+
+pub use csv_exporter::{CsvExporter, DELIMITER_OPTION, MODE_OPTION};
@@ -0,0 +1,22 @@
+use std::io;
+
+use persistence::calc::WorkItem;
+
+use crate::ReportContext;
+
+/// Exporter writing work items in a specific format.
+pub trait Exporter {
+    /// Name of the format used to select the exporter (e. g. 'markdown').
+    fn name(&self) -> &str;
+
+    /// Extension of files in the exported format (without leading dot).
+    fn file_extension(&self) -> &str;
+
+    /// Export the given work items to the passed writer.
+    fn export(
+        &self,
+        items: &[WorkItem],
+        context: &ReportContext,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()>;
+}
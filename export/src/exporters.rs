@@ -0,0 +1,33 @@
+use crate::{
+    CsvExporter, Exporter, HtmlExporter, IcsExporter, JsonExporter, MarkdownExporter,
+    TemplateExporter,
+};
+
+/// All available exporters.
+pub const EXPORTERS: [&dyn Exporter; 6] = [
+    &MarkdownExporter {},
+    &CsvExporter {},
+    &JsonExporter {},
+    &IcsExporter {},
+    &HtmlExporter {},
+    &TemplateExporter {},
+];
+
+/// Find the exporter for the format with the given name (case insensitive).
+pub fn find_exporter(name: &str) -> Option<&'static dyn Exporter> {
+    EXPORTERS
+        .iter()
+        .find(|e| e.name().eq_ignore_ascii_case(name.trim()))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_exporter() {
+        assert_eq!(find_exporter(" Markdown").unwrap().name(), "markdown");
+        assert!(find_exporter("docx").is_none());
+    }
+}
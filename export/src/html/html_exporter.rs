@@ -0,0 +1,485 @@
+use std::io;
+
+use persistence::calc::{
+    break_down_by_day, calculate_rounded_total_time, calculate_tag_statistics,
+    calculate_total_work_time, round_item_time, DayBreakdown, TagStatistics, WorkItem,
+};
+use shared::calc::RoundingPolicy;
+
+use crate::{Exporter, ReportContext};
+
+/// Style sheet embedded into the report.
+const STYLE: &str = include_str!("report.css");
+
+/// Script making the item table sortable embedded into the report.
+const SCRIPT: &str = include_str!("report.js");
+
+/// Width of the charts (in SVG user units).
+const CHART_WIDTH: i64 = 960;
+
+/// Width of the labels left of the bars of the charts.
+const LABEL_WIDTH: i64 = 240;
+
+/// Height of a single bar row of the charts.
+const ROW_HEIGHT: i64 = 22;
+
+/// Height of the hour axis of the timeline charts.
+const AXIS_HEIGHT: i64 = 20;
+
+/// Hours between two lines of the hour grid of the timeline charts.
+const GRID_HOURS: i64 = 3;
+
+/// Maximum number of characters of the labels of the charts.
+const MAX_LABEL_LENGTH: usize = 32;
+
+/// Colors of the bars (one per tag, repeating).
+const COLORS: [&str; 8] = [
+    "#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#76b7b2", "#edc948", "#b07aa1", "#ff9da7",
+];
+
+/// Color of the bars of untagged work.
+const UNTAGGED_COLOR: &str = "#9da5ae";
+
+/// Label of untagged work.
+const UNTAGGED_LABEL: &str = "(untagged)";
+
+/// Exporter writing a self-contained HTML report that works offline.
+/// It contains a timeline of the intervals worked per day, a chart of the time per tag
+/// (both inline SVG) and a sortable table of the work items.
+/// With a rounding policy the rounded time is shown next to the time worked.
+pub struct HtmlExporter {}
+
+impl Exporter for HtmlExporter {
+    fn name(&self) -> &str {
+        "html"
+    }
+
+    fn file_extension(&self) -> &str {
+        "html"
+    }
+
+    fn export(
+        &self,
+        items: &[WorkItem],
+        context: &ReportContext,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let from_timestamp = context.from_timestamp();
+        let to_timestamp = context.to_timestamp();
+        let rounding_policy = context.rounding_policy();
+
+        let item_refs: Vec<&WorkItem> = items.iter().collect();
+
+        // Only consider the work done in the time range
+        let intervals: Vec<(i64, i64)> = items
+            .iter()
+            .flat_map(|i| i.intervals_in_range(from_timestamp, to_timestamp))
+            .collect();
+        let start_timestamp = intervals.iter().map(|(start, _)| *start).min();
+        let end_timestamp = intervals.iter().map(|(_, end)| *end).max();
+
+        let days = break_down_by_day(&item_refs, from_timestamp, to_timestamp);
+        let tags =
+            calculate_tag_statistics(&item_refs, from_timestamp, to_timestamp, rounding_policy);
+
+        let title = format!(
+            "Report from {} to {}",
+            format_time(start_timestamp.or(Some(from_timestamp)), "%Y-%m-%d"),
+            format_time(
+                end_timestamp
+                    .or(Some(to_timestamp))
+                    .map(|timestamp| timestamp - 1),
+                "%Y-%m-%d"
+            )
+        );
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<title>{}</title>", escape(&title))?;
+        writeln!(writer, "<style>\n{}</style>", STYLE)?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<h1>{}</h1>", escape(&title))?;
+
+        writeln!(writer, "<h2>Statistics</h2>")?;
+        let total_time = format_duration(calculate_total_work_time(
+            &item_refs,
+            from_timestamp,
+            to_timestamp,
+        ));
+        let (rounded_header, rounded_cell) = match rounding_policy {
+            Some(policy) => (
+                "<th>Rounded time</th>",
+                format!(
+                    "<td>{}</td>",
+                    format_duration(calculate_rounded_total_time(
+                        &item_refs,
+                        from_timestamp,
+                        to_timestamp,
+                        policy
+                    ))
+                ),
+            ),
+            None => ("", String::new()),
+        };
+        writeln!(
+            writer,
+            "<table>\n<tr><th>Total time worked</th>{}<th>Days worked</th><th>Started working</th><th>Finished working</th></tr>",
+            rounded_header
+        )?;
+        writeln!(
+            writer,
+            "<tr><td>{}</td>{}<td>{}</td><td>{}</td><td>{}</td></tr>\n</table>",
+            total_time,
+            rounded_cell,
+            days.len(),
+            format_time(start_timestamp, "%Y-%m-%d %H:%M"),
+            format_time(end_timestamp, "%Y-%m-%d %H:%M")
+        )?;
+        if let Some(policy) = rounding_policy {
+            writeln!(writer, "<p>Times are {}.</p>", policy)?;
+        }
+
+        writeln!(writer, "<h2>Timeline</h2>")?;
+        for day in &days {
+            writeln!(
+                writer,
+                "<h3>{} ({})</h3>",
+                day.date().format("%A, %Y-%m-%d"),
+                format_rounded_duration(
+                    day.total_time(),
+                    rounding_policy.map(|policy| day.rounded_total_time(policy))
+                )
+            )?;
+            write_timeline(writer, day, &tags)?;
+        }
+
+        writeln!(writer, "<h2>Tags</h2>")?;
+        write_tag_chart(writer, &tags)?;
+
+        writeln!(writer, "<h2>Work items</h2>")?;
+        write_item_table(writer, items, from_timestamp, to_timestamp, rounding_policy)?;
+
+        writeln!(writer, "<script>\n{}</script>", SCRIPT)?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")
+    }
+}
+
+/// Write a Gantt-style chart of the intervals worked on the given day (one row per work item).
+fn write_timeline(
+    writer: &mut dyn io::Write,
+    day: &DayBreakdown,
+    tags: &[TagStatistics],
+) -> io::Result<()> {
+    let day_length = day.to_timestamp() - day.from_timestamp();
+    let bar_width = CHART_WIDTH - LABEL_WIDTH;
+    let x_of = |timestamp: i64| {
+        LABEL_WIDTH + (timestamp - day.from_timestamp()) * bar_width / day_length.max(1)
+    };
+    let height = AXIS_HEIGHT + day.items().len() as i64 * ROW_HEIGHT;
+
+    writeln!(
+        writer,
+        "<svg viewBox=\"0 0 {} {}\" role=\"img\">",
+        CHART_WIDTH, height
+    )?;
+
+    let mut timestamp = day.from_timestamp();
+    while timestamp <= day.to_timestamp() {
+        let x = x_of(timestamp);
+        writeln!(
+            writer,
+            "<line class=\"grid\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
+            x, AXIS_HEIGHT, x, height
+        )?;
+        writeln!(
+            writer,
+            "<text class=\"label\" x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+            x,
+            AXIS_HEIGHT - 6,
+            format_time(Some(timestamp), "%H:%M")
+        )?;
+
+        timestamp += GRID_HOURS * 60 * 60 * 1000;
+    }
+
+    for (row, item) in day.items().iter().enumerate() {
+        let y = AXIS_HEIGHT + row as i64 * ROW_HEIGHT;
+        let color = color_of(item.tags().first().map(|t| t.as_str()), tags);
+
+        writeln!(
+            writer,
+            "<text x=\"0\" y=\"{}\">{}</text>",
+            y + ROW_HEIGHT - 7,
+            escape(&truncate(item.description()))
+        )?;
+
+        for (start, end) in item.intervals_in_range(day.from_timestamp(), day.to_timestamp()) {
+            writeln!(
+                writer,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{}: {} - {} ({})</title></rect>",
+                x_of(start),
+                y + 3,
+                (x_of(end) - x_of(start)).max(1),
+                ROW_HEIGHT - 6,
+                color,
+                escape(item.description()),
+                format_time(Some(start), "%H:%M"),
+                format_time(Some(end), "%H:%M"),
+                format_duration(end - start)
+            )?;
+        }
+    }
+
+    writeln!(writer, "</svg>")
+}
+
+/// Write a bar chart of the share of the time worked per tag.
+fn write_tag_chart(writer: &mut dyn io::Write, tags: &[TagStatistics]) -> io::Result<()> {
+    let bar_width = CHART_WIDTH - LABEL_WIDTH - 120;
+
+    writeln!(
+        writer,
+        "<svg viewBox=\"0 0 {} {}\" role=\"img\">",
+        CHART_WIDTH,
+        tags.len() as i64 * ROW_HEIGHT
+    )?;
+
+    for (row, statistics) in tags.iter().enumerate() {
+        let y = row as i64 * ROW_HEIGHT;
+        let width = (statistics.share() * bar_width as f64).round() as i64;
+
+        writeln!(
+            writer,
+            "<text x=\"0\" y=\"{}\">{}</text>",
+            y + ROW_HEIGHT - 7,
+            escape(&truncate(statistics.tag().unwrap_or(UNTAGGED_LABEL)))
+        )?;
+        writeln!(
+            writer,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{} work items</title></rect>",
+            LABEL_WIDTH,
+            y + 3,
+            width.max(1),
+            ROW_HEIGHT - 6,
+            color_of(statistics.tag(), tags),
+            statistics.item_count()
+        )?;
+        writeln!(
+            writer,
+            "<text class=\"label\" x=\"{}\" y=\"{}\">{} ({:.1} %)</text>",
+            LABEL_WIDTH + width.max(1) + 6,
+            y + ROW_HEIGHT - 7,
+            format_rounded_duration(statistics.unique_time(), statistics.rounded_time()),
+            statistics.share() * 100.0
+        )?;
+    }
+
+    writeln!(writer, "</svg>")
+}
+
+/// Write a table of the work done on the given items that can be sorted by clicking the headers.
+fn write_item_table(
+    writer: &mut dyn io::Write,
+    items: &[WorkItem],
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: Option<&RoundingPolicy>,
+) -> io::Result<()> {
+    writeln!(writer, "<table class=\"sortable\">")?;
+    writeln!(
+        writer,
+        "<thead><tr><th>Description</th><th>Tags</th><th>Status</th><th>Started</th><th>Finished</th><th>Time</th>{}</tr></thead>",
+        if rounding_policy.is_some() {
+            "<th>Rounded time</th>"
+        } else {
+            ""
+        }
+    )?;
+    writeln!(writer, "<tbody>")?;
+
+    for item in items {
+        let intervals = item.intervals_in_range(from_timestamp, to_timestamp);
+        let start = intervals.iter().map(|(start, _)| *start).min();
+        let end = intervals.iter().map(|(_, end)| *end).max();
+        let time_taken = item.time_taken_in_range(from_timestamp, to_timestamp);
+        let description = escape(item.description());
+        let tags = escape(&item.tags().join(", "));
+        let rounded_cell = match rounding_policy {
+            Some(policy) => {
+                let rounded = round_item_time(item, from_timestamp, to_timestamp, policy);
+
+                format!(
+                    "<td class=\"number\" data-value=\"{}\">{}</td>",
+                    rounded.unwrap_or(time_taken),
+                    rounded.map_or(String::from("-"), format_duration)
+                )
+            }
+            None => String::new(),
+        };
+
+        writeln!(
+            writer,
+            "<tr><td data-value=\"{}\">{}</td><td data-value=\"{}\">{}</td><td data-value=\"{}\">{}</td><td data-value=\"{}\">{}</td><td data-value=\"{}\">{}</td><td class=\"number\" data-value=\"{}\">{}</td>{}</tr>",
+            description,
+            description,
+            tags,
+            tags,
+            item.status(),
+            item.status(),
+            start.unwrap_or(0),
+            format_time(start, "%Y-%m-%d %H:%M"),
+            end.unwrap_or(0),
+            format_time(end, "%Y-%m-%d %H:%M"),
+            time_taken,
+            format_duration(time_taken),
+            rounded_cell
+        )?;
+    }
+
+    writeln!(writer, "</tbody>\n</table>")
+}
+
+/// Format the given duration (in milliseconds) followed by the rounded duration (if any).
+fn format_rounded_duration(duration: i64, rounded: Option<i64>) -> String {
+    match rounded {
+        Some(rounded) => format!(
+            "{}, rounded {}",
+            format_duration(duration),
+            format_duration(rounded)
+        ),
+        None => format_duration(duration),
+    }
+}
+
+/// Get the color of the bars of the given tag (or untagged work if there is none).
+fn color_of(tag: Option<&str>, tags: &[TagStatistics]) -> &'static str {
+    match tag {
+        Some(tag) => tags
+            .iter()
+            .filter(|s| s.tag().is_some())
+            .position(|s| s.tag() == Some(tag))
+            .map_or(UNTAGGED_COLOR, |index| COLORS[index % COLORS.len()]),
+        None => UNTAGGED_COLOR,
+    }
+}
+
+/// Shorten the given label of a chart to at most `MAX_LABEL_LENGTH` characters.
+fn truncate(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_LENGTH {
+        return label.to_owned();
+    }
+
+    let mut truncated: String = label.chars().take(MAX_LABEL_LENGTH - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Escape the given text for use in HTML content and attribute values.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Format the given duration (in milliseconds).
+fn format_duration(duration: i64) -> String {
+    shared::time::format_duration((duration / 1000) as u32)
+}
+
+/// Format the given timestamp using the passed format (or '-' if there is none).
+fn format_time(timestamp: Option<i64>, format: &str) -> String {
+    timestamp.map_or(String::from("-"), |ts| {
+        shared::time::get_local_date_time(ts)
+            .format(format)
+            .to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use persistence::calc::event::{Event, EventType};
+    use persistence::calc::Status;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_export() {
+        shared::time::select_time_zone(
+            shared::time::TimeZoneSetting::from_name("Europe/Berlin").unwrap(),
+        );
+
+        let mut tags = HashSet::new();
+        tags.insert(String::from("dev"));
+
+        // Sunday 2021-01-31 09:00 to 10:00 and 11:00 to 11:30
+        let items = vec![WorkItem::new_internal(
+            1,
+            String::from("Fix <script> & \"quotes\""),
+            Status::Done,
+            tags,
+            vec![
+                Event::new(EventType::Started, 1612080000000),
+                Event::new(EventType::Paused, 1612083600000),
+                Event::new(EventType::Continued, 1612087200000),
+                Event::new(EventType::Finished, 1612089000000),
+            ],
+        )];
+
+        let mut output = Vec::new();
+        HtmlExporter {}
+            .export(
+                &items,
+                &ReportContext::new(1612047600000, 1612047600000 + 7 * 24 * 60 * 60 * 1000),
+                &mut output,
+            )
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<h1>Report from 2021-01-31 to 2021-01-31</h1>"));
+        assert!(output.contains("<h3>Sunday, 2021-01-31 (1h 30m)</h3>"));
+        assert!(output.contains("Fix &lt;script&gt; &amp; &quot;quotes&quot;"));
+        assert!(!output.contains("<script> &"));
+
+        // One bar per interval: 09:00 is at 3/8 of the bar area of the day
+        assert_eq!(output.matches("<rect x=\"510\"").count(), 1);
+        assert!(output.contains("09:00 - 10:00 (1h)</title>"));
+        assert!(output.contains("11:00 - 11:30 (30m)</title>"));
+
+        // Tag chart and sortable table
+        assert!(output.contains("1h 30m (100.0 %)"));
+        assert!(output.contains("<td class=\"number\" data-value=\"5400000\">1h 30m</td>"));
+
+        // No external assets
+        assert!(!output.contains("src="));
+        assert!(!output.contains("href="));
+        assert!(!output.contains("Rounded time"));
+
+        let mut output = Vec::new();
+        HtmlExporter {}
+            .export(
+                &items,
+                &ReportContext::new(1612047600000, 1612047600000 + 7 * 24 * 60 * 60 * 1000)
+                    .with_rounding_policy(RoundingPolicy::from_spec("up:1h").unwrap()),
+                &mut output,
+            )
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("<td>1h 30m</td><td>2h</td>"));
+        assert!(output.contains("<h3>Sunday, 2021-01-31 (1h 30m, rounded 2h)</h3>"));
+        assert!(output.contains("<td class=\"number\" data-value=\"7200000\">2h</td></tr>"));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short"), "short");
+        assert_eq!(truncate(&"x".repeat(40)).chars().count(), MAX_LABEL_LENGTH);
+    }
+}
@@ -0,0 +1,3 @@
+mod html_exporter;
+
+pub use html_exporter::HtmlExporter;
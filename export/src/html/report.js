@@ -0,0 +1,25 @@
+document.querySelectorAll("table.sortable th").forEach(function (header) {
+  header.addEventListener("click", function () {
+    var table = header.closest("table");
+    var body = table.tBodies[0];
+    var column = header.cellIndex;
+    var ascending = header.getAttribute("aria-sort") !== "ascending";
+
+    table.querySelectorAll("th").forEach(function (h) {
+      h.removeAttribute("aria-sort");
+    });
+    header.setAttribute("aria-sort", ascending ? "ascending" : "descending");
+
+    var rows = Array.prototype.slice.call(body.rows);
+    rows.sort(function (a, b) {
+      var x = a.cells[column].getAttribute("data-value");
+      var y = b.cells[column].getAttribute("data-value");
+      var result = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+
+      return ascending ? result : -result;
+    });
+    rows.forEach(function (row) {
+      body.appendChild(row);
+    });
+  });
+});
@@ -0,0 +1,182 @@
+use std::io;
+
+use chrono::{TimeZone, Utc};
+use persistence::calc::WorkItem;
+
+use crate::{Exporter, ReportContext};
+
+/// Line break terminating a content line as defined by RFC 5545.
+const LINE_BREAK: &str = "\r\n";
+
+/// Maximum length of a content line (in octets, excluding the line break).
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Identifier of the product that created the calendar.
+const PRODUCT_ID: &str = "-//worklog//worklog export//EN";
+
+/// Domain appended to the unique identifiers of the events.
+const UID_DOMAIN: &str = "worklog";
+
+/// Exporter writing an iCalendar file with one event per active interval of the work items.
+/// Intervals overlapping the reported time range are exported unclipped.
+/// Each event has a stable unique identifier derived from the work item ID and the
+/// index of the interval so that re-importing the file updates the events instead of duplicating them.
+pub struct IcsExporter {}
+
+impl Exporter for IcsExporter {
+    fn name(&self) -> &str {
+        "ics"
+    }
+
+    fn file_extension(&self) -> &str {
+        "ics"
+    }
+
+    fn export(
+        &self,
+        items: &[WorkItem],
+        context: &ReportContext,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let time_stamp = format_timestamp(Utc::now().timestamp_millis())?;
+
+        write_line(writer, "BEGIN:VCALENDAR")?;
+        write_line(writer, "VERSION:2.0")?;
+        write_line(writer, &format!("PRODID:{}", PRODUCT_ID))?;
+        write_line(writer, "CALSCALE:GREGORIAN")?;
+
+        for item in items {
+            let id = item.id().map_or(String::from("new"), |id| id.to_string());
+            let tags: Vec<String> = item.tags().iter().map(|t| escape_text(t)).collect();
+
+            for (index, (start, end)) in item.intervals().into_iter().enumerate() {
+                if end <= context.from_timestamp() || start >= context.to_timestamp() {
+                    continue;
+                }
+
+                write_line(writer, "BEGIN:VEVENT")?;
+                write_line(writer, &format!("UID:{}-{}@{}", id, index, UID_DOMAIN))?;
+                write_line(writer, &format!("DTSTAMP:{}", time_stamp))?;
+                write_line(writer, &format!("DTSTART:{}", format_timestamp(start)?))?;
+                write_line(writer, &format!("DTEND:{}", format_timestamp(end)?))?;
+                write_line(
+                    writer,
+                    &format!("SUMMARY:{}", escape_text(item.description())),
+                )?;
+                if !tags.is_empty() {
+                    write_line(writer, &format!("CATEGORIES:{}", tags.join(",")))?;
+                }
+                write_line(writer, "END:VEVENT")?;
+            }
+        }
+
+        write_line(writer, "END:VCALENDAR")
+    }
+}
+
+/// Write the given content line folded as defined by RFC 5545.
+/// Lines longer than 75 octets are continued on the next line starting with a space.
+fn write_line(writer: &mut dyn io::Write, line: &str) -> io::Result<()> {
+    let mut length = 0;
+    for c in line.chars() {
+        // Never split a multi-byte character
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            write!(writer, "{} ", LINE_BREAK)?;
+            length = 1;
+        }
+
+        write!(writer, "{}", c)?;
+        length += c.len_utf8();
+    }
+
+    write!(writer, "{}", LINE_BREAK)
+}
+
+/// Escape the given text value as defined by RFC 5545.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Format the given timestamp (in milliseconds) as UTC date time.
+/// Fails for timestamps out of the representable range.
+fn format_timestamp(timestamp: i64) -> io::Result<String> {
+    Utc.timestamp_millis_opt(timestamp)
+        .single()
+        .map(|date_time| date_time.format("%Y%m%dT%H%M%SZ").to_string())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The timestamp {} is out of range", timestamp),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use persistence::calc::event::{Event, EventType};
+    use persistence::calc::Status;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_export_intervals() {
+        let mut tags = HashSet::new();
+        tags.insert(String::from("dev"));
+        tags.insert(String::from("a,b"));
+
+        let items = vec![WorkItem::new_internal(
+            5,
+            String::from("Fix; the bug"),
+            Status::Done,
+            tags,
+            vec![
+                Event::new(EventType::Started, 0),
+                Event::new(EventType::Paused, 60 * 60 * 1000),
+                Event::new(EventType::Continued, 2 * 60 * 60 * 1000),
+                Event::new(EventType::Finished, 3 * 60 * 60 * 1000),
+            ],
+        )];
+
+        let mut output = Vec::new();
+        IcsExporter {}
+            .export(
+                &items,
+                &ReportContext::new(90 * 60 * 1000, 24 * 60 * 60 * 1000),
+                &mut output,
+            )
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        // Only the second interval overlaps the time range but keeps its index
+        assert!(!output.contains("UID:5-0@worklog"));
+        assert!(output.contains("UID:5-1@worklog\r\n"));
+        assert!(output.contains("DTSTART:19700101T020000Z\r\n"));
+        assert!(output.contains("DTEND:19700101T030000Z\r\n"));
+        assert!(output.contains("SUMMARY:Fix\\; the bug\r\n"));
+        assert!(output.contains("CATEGORIES:a\\,b,dev\r\n"));
+        assert!(output.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_fold_long_lines() {
+        let mut output = Vec::new();
+        write_line(&mut output, &format!("SUMMARY:{}", "ä".repeat(40))).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        let lines: Vec<&str> = output.trim_end().split(LINE_BREAK).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE_LENGTH));
+        assert!(lines[1].starts_with(' '));
+    }
+
+    #[test]
+    fn test_timestamp_out_of_range() {
+        let result = format_timestamp(i64::MAX);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,3 @@
+mod ics_exporter;
+
+pub use ics_exporter::IcsExporter;
@@ -0,0 +1,148 @@
+use std::io;
+
+use persistence::calc::WorkItem;
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON backup schema.
+/// Increase it whenever the schema changes in an incompatible way.
+///
+/// Version 1 looks as follows (timestamps are in milliseconds since the epoch):
+/// ```json
+/// {
+///   "version": 1,
+///   "items": [
+///     {
+///       "id": 1,
+///       "description": "Fix the migration bug",
+///       "tags": ["bug", "database"],
+///       "status": "DONE",
+///       "project": "website",
+///       "events": [
+///         { "type": "STARTED", "timestamp": 1612170000000 },
+///         { "type": "PAUSED", "timestamp": 1612173600000 },
+///         { "type": "CONTINUED", "timestamp": 1612177200000 },
+///         { "type": "FINISHED", "timestamp": 1612180800000 }
+///       ],
+///       "notes": [
+///         { "timestamp": 1612175000000, "text": "Caused by the missing index" }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+/// The `project` is only present for work items assigned to a project
+/// and the `notes` only for work items with notes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Backup of work items to write.
+#[derive(Serialize)]
+struct BackupRef<'a> {
+    /// Version of the schema.
+    version: u32,
+    /// Backed up work items.
+    items: &'a [WorkItem],
+}
+
+/// Backup of work items read.
+#[derive(Deserialize)]
+struct Backup {
+    /// Version of the schema.
+    version: u32,
+    /// Backed up work items.
+    items: Vec<WorkItem>,
+}
+
+/// Write the given work items as JSON backup in the latest schema version.
+pub fn write_backup(items: &[WorkItem], writer: &mut dyn io::Write) -> io::Result<()> {
+    let backup = BackupRef {
+        version: SCHEMA_VERSION,
+        items,
+    };
+
+    serde_json::to_writer_pretty(&mut *writer, &backup)?;
+    writeln!(writer)
+}
+
+/// Read the work items of a JSON backup.
+/// Fails when the schema version is not supported or a work item is invalid.
+pub fn read_backup(reader: &mut dyn io::Read) -> io::Result<Vec<WorkItem>> {
+    let backup: Backup = serde_json::from_reader(reader)?;
+
+    if backup.version > SCHEMA_VERSION {
+        return Err(invalid_data(format!(
+            "The backup has schema version {} but only versions up to {} are supported",
+            backup.version, SCHEMA_VERSION
+        )));
+    }
+
+    for (index, item) in backup.items.iter().enumerate() {
+        item.validate().map_err(|e| {
+            invalid_data(format!(
+                "Work item {} ('{}') is invalid: {}",
+                item.id()
+                    .map_or((index + 1).to_string(), |id| id.to_string()),
+                item.description(),
+                e
+            ))
+        })?;
+    }
+
+    Ok(backup.items)
+}
+
+/// Create an error signalling invalid backup data.
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use persistence::calc::event::{Event, EventType};
+    use persistence::calc::Status;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_round_trip() {
+        let mut tags = HashSet::new();
+        tags.insert(String::from("b"));
+        tags.insert(String::from("a"));
+
+        let items = vec![WorkItem::new_internal(
+            3,
+            String::from("Backup"),
+            Status::Paused,
+            tags,
+            vec![
+                Event::new(EventType::Started, 10),
+                Event::new(EventType::Paused, 20),
+            ],
+        )];
+
+        let mut output = Vec::new();
+        write_backup(&items, &mut output).unwrap();
+
+        let json = String::from_utf8(output.clone()).unwrap();
+        assert!(json.contains("\"version\": 1"));
+        assert!(json.contains("\"status\": \"PAUSED\""));
+        assert!(json.contains("\"type\": \"STARTED\""));
+
+        let read = read_backup(&mut output.as_slice()).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].id(), Some(3));
+        assert_eq!(read[0].tags(), vec![String::from("a"), String::from("b")]);
+        assert_eq!(read[0].events(), items[0].events());
+    }
+
+    #[test]
+    fn test_read_invalid_backup() {
+        let newer = r#"{ "version": 2, "items": [] }"#;
+        assert!(read_backup(&mut newer.as_bytes()).is_err());
+
+        let wrong_status = r#"{ "version": 1, "items": [{
+            "id": 1, "description": "Wrong", "tags": [], "status": "DONE",
+            "events": [{ "type": "STARTED", "timestamp": 10 }]
+        }] }"#;
+        assert!(read_backup(&mut wrong_status.as_bytes()).is_err());
+    }
+}
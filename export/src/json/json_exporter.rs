@@ -0,0 +1,30 @@
+use std::io;
+
+use persistence::calc::WorkItem;
+
+use crate::json::write_backup;
+use crate::{Exporter, ReportContext};
+
+/// Exporter writing a full-fidelity JSON backup of the work items
+/// (see `SCHEMA_VERSION` for the schema).
+/// Unlike reports the events of the work items are not clipped to the reported time range.
+pub struct JsonExporter {}
+
+impl Exporter for JsonExporter {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn file_extension(&self) -> &str {
+        "json"
+    }
+
+    fn export(
+        &self,
+        items: &[WorkItem],
+        _context: &ReportContext,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        write_backup(items, writer)
+    }
+}
@@ -0,0 +1,5 @@
+mod backup;
+mod json_exporter;
+
+pub use backup::{read_backup, write_backup, SCHEMA_VERSION};
+pub use json_exporter::JsonExporter;
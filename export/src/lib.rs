@@ -1,7 +1,19 @@
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
-}
+pub mod csv;
+mod exporter;
+mod exporters;
+mod html;
+mod ics;
+pub mod json;
+mod markdown;
+mod report_context;
+pub mod template;
+
+pub use self::csv::CsvExporter;
+pub use exporter::Exporter;
+pub use exporters::{find_exporter, EXPORTERS};
+pub use html::HtmlExporter;
+pub use ics::IcsExporter;
+pub use json::JsonExporter;
+pub use markdown::MarkdownExporter;
+pub use report_context::ReportContext;
+pub use template::TemplateExporter;
@@ -0,0 +1,557 @@
+use std::io;
+
+use persistence::calc::{
+    break_down_by_week, calculate_project_statistics, calculate_rounded_total_time,
+    calculate_total_work_time, round_item_time, WorkItem,
+};
+use shared::calc::RoundingPolicy;
+
+use crate::{Exporter, ReportContext};
+
+/// Format of times in the report.
+const TIME_FORMAT: &str = "%H:%M";
+
+/// Format of dates in the report.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Format of date times in reports spanning multiple days.
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Exporter writing a markdown report.
+/// Reports spanning multiple days are broken down by week and day.
+/// With a rounding policy the rounded time is shown next to the time worked.
+/// When work items are assigned to projects, the time and amount per project is listed as well.
+/// Notes of the work items are listed below them when requested.
+pub struct MarkdownExporter {}
+
+impl Exporter for MarkdownExporter {
+    fn name(&self) -> &str {
+        "markdown"
+    }
+
+    fn file_extension(&self) -> &str {
+        "md"
+    }
+
+    fn export(
+        &self,
+        items: &[WorkItem],
+        context: &ReportContext,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let from_timestamp = context.from_timestamp();
+        let to_timestamp = context.to_timestamp();
+        let rounding_policy = context.rounding_policy();
+        let include_notes = context.include_notes();
+
+        let item_refs: Vec<&WorkItem> = items.iter().collect();
+
+        // Only consider the work done in the time range
+        let intervals: Vec<(i64, i64)> = items
+            .iter()
+            .flat_map(|i| i.intervals_in_range(from_timestamp, to_timestamp))
+            .collect();
+        let start_timestamp = intervals.iter().map(|(start, _)| *start).min();
+        let end_timestamp = intervals.iter().map(|(_, end)| *end).max();
+
+        let total_work_time = format_duration(calculate_total_work_time(
+            &item_refs,
+            from_timestamp,
+            to_timestamp,
+        ));
+        let rounded_total_time = rounding_policy.map(|policy| {
+            format_duration(calculate_rounded_total_time(
+                &item_refs,
+                from_timestamp,
+                to_timestamp,
+                policy,
+            ))
+        });
+
+        let date_time = shared::time::get_local_date_time(from_timestamp);
+        let (_, end_of_day) = shared::time::day_range(date_time.date_naive());
+        if to_timestamp <= end_of_day {
+            write!(
+                writer,
+                "# Report for {} the {}\n\n",
+                date_time.format("%A"),
+                date_time.format("%Y-%m-%d")
+            )?;
+
+            write!(writer, "## Statistics\n\n")?;
+            match &rounded_total_time {
+                Some(rounded_total_time) => write!(
+                    writer,
+                    "\
+| Total time worked | Rounded time | Started working | Finished working |
+| ----------------- | ------------ | --------------- | ---------------- |
+| {} | {} | {} | {} |\n\n",
+                    total_work_time,
+                    rounded_total_time,
+                    format_time(start_timestamp, TIME_FORMAT),
+                    format_time(end_timestamp, TIME_FORMAT)
+                )?,
+                None => write!(
+                    writer,
+                    "\
+| Total time worked | Started working | Finished working |
+| ----------------- | --------------- | ---------------- |
+| {} | {} | {} |\n\n",
+                    total_work_time,
+                    format_time(start_timestamp, TIME_FORMAT),
+                    format_time(end_timestamp, TIME_FORMAT)
+                )?,
+            }
+            write_rounding_policy(writer, rounding_policy)?;
+            write_projects(writer, &item_refs, context)?;
+
+            write!(writer, "## Work items\n\n")?;
+
+            for item in items {
+                write_item(
+                    writer,
+                    item,
+                    from_timestamp,
+                    to_timestamp,
+                    rounding_policy,
+                    include_notes,
+                )?;
+            }
+
+            return Ok(());
+        }
+
+        let weeks = break_down_by_week(&item_refs, from_timestamp, to_timestamp);
+        let days_worked: usize = weeks.iter().map(|w| w.days().len()).sum();
+
+        write!(
+            writer,
+            "# Report from {} to {}\n\n",
+            format_time(start_timestamp.or(Some(from_timestamp)), DATE_FORMAT),
+            format_time(
+                end_timestamp
+                    .or(Some(to_timestamp))
+                    .map(|timestamp| timestamp - 1),
+                DATE_FORMAT
+            )
+        )?;
+
+        write!(writer, "## Statistics\n\n")?;
+        match &rounded_total_time {
+            Some(rounded_total_time) => write!(
+                writer,
+                "\
+| Total time worked | Rounded time | Days worked | Started working | Finished working |
+| ----------------- | ------------ | ----------- | --------------- | ---------------- |
+| {} | {} | {} | {} | {} |\n\n",
+                total_work_time,
+                rounded_total_time,
+                days_worked,
+                format_time(start_timestamp, DATE_TIME_FORMAT),
+                format_time(end_timestamp, DATE_TIME_FORMAT)
+            )?,
+            None => write!(
+                writer,
+                "\
+| Total time worked | Days worked | Started working | Finished working |
+| ----------------- | ----------- | --------------- | ---------------- |
+| {} | {} | {} | {} |\n\n",
+                total_work_time,
+                days_worked,
+                format_time(start_timestamp, DATE_TIME_FORMAT),
+                format_time(end_timestamp, DATE_TIME_FORMAT)
+            )?,
+        }
+        write_rounding_policy(writer, rounding_policy)?;
+        write_projects(writer, &item_refs, context)?;
+
+        for week in &weeks {
+            write!(
+                writer,
+                "## Week {}-W{:02} ({})\n\n",
+                week.year(),
+                week.week(),
+                format_rounded_duration(
+                    week.total_time(),
+                    rounding_policy.map(|policy| week.rounded_total_time(policy))
+                )
+            )?;
+
+            for day in week.days() {
+                write!(
+                    writer,
+                    "### {} ({})\n\n",
+                    day.date().format("%A, %Y-%m-%d"),
+                    format_rounded_duration(
+                        day.total_time(),
+                        rounding_policy.map(|policy| day.rounded_total_time(policy))
+                    )
+                )?;
+
+                for item in day.items() {
+                    write_item(
+                        writer,
+                        item,
+                        day.from_timestamp(),
+                        day.to_timestamp(),
+                        rounding_policy,
+                        include_notes,
+                    )?;
+                }
+
+                writeln!(writer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the line describing the work done on the given item in the passed time range
+/// (followed by the notes written in the time range if requested).
+fn write_item(
+    writer: &mut dyn io::Write,
+    item: &WorkItem,
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: Option<&RoundingPolicy>,
+    include_notes: bool,
+) -> io::Result<()> {
+    let rounded = rounding_policy
+        .and_then(|policy| round_item_time(item, from_timestamp, to_timestamp, policy))
+        .map_or(String::new(), |rounded| {
+            format!(", rounded `{}`", format_duration(rounded))
+        });
+
+    let project = item
+        .project()
+        .map_or(String::new(), |project| format!(" Project: *{}*.", project));
+
+    writeln!(
+        writer,
+        "- {}. Took `{}`{} ({}). Tags: *{}*.{}",
+        item.description(),
+        format_duration(item.time_taken_in_range(from_timestamp, to_timestamp)),
+        rounded,
+        format_event_timeline(item, from_timestamp, to_timestamp),
+        item.tags().join(", "),
+        project
+    )?;
+
+    if include_notes {
+        for note in item
+            .notes()
+            .iter()
+            .filter(|n| n.timestamp() >= from_timestamp && n.timestamp() < to_timestamp)
+        {
+            writeln!(
+                writer,
+                "  - {}: {}",
+                format_time(Some(note.timestamp()), TIME_FORMAT),
+                note.text().replace('\n', " ")
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the table of the time worked and amount to bill per project
+/// (only when any of the given items is assigned to a project).
+fn write_projects(
+    writer: &mut dyn io::Write,
+    items: &[&WorkItem],
+    context: &ReportContext,
+) -> io::Result<()> {
+    if items.iter().all(|i| i.project().is_none()) {
+        return Ok(());
+    }
+
+    write!(
+        writer,
+        "\
+## Projects
+
+| Project | Client | Time | Items | Amount |
+| ------- | ------ | ---- | ----- | ------ |\n"
+    )?;
+
+    for statistics in calculate_project_statistics(
+        items,
+        context.projects(),
+        context.from_timestamp(),
+        context.to_timestamp(),
+        context.rounding_policy(),
+    ) {
+        writeln!(
+            writer,
+            "| {} | {} | {} | {} | {} |",
+            statistics.project().unwrap_or("(no project)"),
+            statistics.client().unwrap_or("-"),
+            format_rounded_duration(statistics.time(), statistics.rounded_time()),
+            statistics.item_count(),
+            statistics
+                .amount()
+                .map_or(String::from("-"), |amount| format!("{:.2}", amount))
+        )?;
+    }
+
+    writeln!(writer)
+}
+
+/// Write the sentence describing how times are rounded (if they are).
+fn write_rounding_policy(
+    writer: &mut dyn io::Write,
+    rounding_policy: Option<&RoundingPolicy>,
+) -> io::Result<()> {
+    match rounding_policy {
+        Some(policy) => write!(writer, "Times are {}.\n\n", policy),
+        None => Ok(()),
+    }
+}
+
+/// Format the given duration (in milliseconds) followed by the rounded duration (if any).
+fn format_rounded_duration(duration: i64, rounded: Option<i64>) -> String {
+    match rounded {
+        Some(rounded) => format!(
+            "{}, rounded {}",
+            format_duration(duration),
+            format_duration(rounded)
+        ),
+        None => format_duration(duration),
+    }
+}
+
+/// Format the given duration (in milliseconds).
+fn format_duration(duration: i64) -> String {
+    shared::time::format_duration((duration / 1000) as u32)
+}
+
+/// Format the given timestamp using the passed format (or '-' if there is none).
+fn format_time(timestamp: Option<i64>, format: &str) -> String {
+    timestamp.map_or(String::from("-"), |ts| {
+        shared::time::get_local_date_time(ts)
+            .format(format)
+            .to_string()
+    })
+}
+
+/// Format the intervals worked on the given item in the passed time range.
+fn format_event_timeline(item: &WorkItem, from_timestamp: i64, to_timestamp: i64) -> String {
+    item.intervals_in_range(from_timestamp, to_timestamp)
+        .iter()
+        .map(|(start, end)| {
+            format!(
+                "{} - {}",
+                shared::time::get_local_date_time(*start).format("%H:%M"),
+                shared::time::get_local_date_time(*end).format("%H:%M")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use persistence::calc::event::{Event, EventType};
+    use persistence::calc::{Project, Status};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_export() {
+        let mut tags = HashSet::new();
+        tags.insert(String::from("dev"));
+        let items = vec![WorkItem::new_internal(
+            1,
+            String::from("Write exporter"),
+            Status::Done,
+            tags,
+            vec![
+                Event::new(EventType::Started, 0),
+                Event::new(EventType::Finished, 90 * 60 * 1000),
+            ],
+        )];
+
+        let mut output = Vec::new();
+        MarkdownExporter {}
+            .export(
+                &items,
+                &ReportContext::new(0, 24 * 60 * 60 * 1000),
+                &mut output,
+            )
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("# Report for "));
+        assert!(output.contains("| 1h 30m |"));
+        assert!(output.contains("- Write exporter. Took `1h 30m` ("));
+        assert!(output.contains("Tags: *dev*."));
+    }
+
+    #[test]
+    fn test_export_multiple_days() {
+        shared::time::select_time_zone(
+            shared::time::TimeZoneSetting::from_name("Europe/Berlin").unwrap(),
+        );
+
+        // Sunday 2021-01-31 23:00 to Monday 2021-02-01 01:00
+        let items = vec![WorkItem::new_internal(
+            1,
+            String::from("Deploy"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 1612130400000),
+                Event::new(EventType::Finished, 1612137600000),
+            ],
+        )];
+
+        let mut output = Vec::new();
+        MarkdownExporter {}
+            .export(
+                &items,
+                &ReportContext::new(1612047600000, 1612047600000 + 7 * 24 * 60 * 60 * 1000),
+                &mut output,
+            )
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("# Report from 2021-01-31 to 2021-02-01"));
+        assert!(output.contains("| 2h | 2 | 2021-01-31 23:00 | 2021-02-01 01:00 |"));
+        assert!(output.contains("## Week 2021-W04 (1h)"));
+        assert!(
+            output.contains("### Monday, 2021-02-01 (1h)\n\n- Deploy. Took `1h` (00:00 - 01:00).")
+        );
+    }
+
+    #[test]
+    fn test_export_rounded() {
+        shared::time::select_time_zone(
+            shared::time::TimeZoneSetting::from_name("Europe/Berlin").unwrap(),
+        );
+
+        // Sunday 2021-01-31 09:00 to 09:50 and Monday 2021-02-01 09:00 to 09:05
+        let items = vec![
+            WorkItem::new_internal(
+                1,
+                String::from("Review"),
+                Status::Done,
+                HashSet::new(),
+                vec![
+                    Event::new(EventType::Started, 1612080000000),
+                    Event::new(EventType::Finished, 1612083000000),
+                ],
+            ),
+            WorkItem::new_internal(
+                2,
+                String::from("Deploy"),
+                Status::Done,
+                HashSet::new(),
+                vec![
+                    Event::new(EventType::Started, 1612166400000),
+                    Event::new(EventType::Finished, 1612166700000),
+                ],
+            ),
+        ];
+
+        let mut output = Vec::new();
+        MarkdownExporter {}
+            .export(
+                &items,
+                &ReportContext::new(1612047600000, 1612047600000 + 7 * 24 * 60 * 60 * 1000)
+                    .with_rounding_policy(RoundingPolicy::from_spec("up:15m").unwrap()),
+                &mut output,
+            )
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("| 55m | 1h 15m | 2 |"));
+        assert!(output.contains("Times are rounded up to multiples of 15m per work item."));
+        assert!(output.contains("## Week 2021-W04 (50m, rounded 1h)"));
+        assert!(output.contains("- Deploy. Took `5m`, rounded `15m` (09:00 - 09:05)."));
+    }
+
+    #[test]
+    fn test_export_projects() {
+        let mut items = vec![
+            WorkItem::new_internal(
+                1,
+                String::from("Landing page"),
+                Status::Done,
+                HashSet::new(),
+                vec![
+                    Event::new(EventType::Started, 0),
+                    Event::new(EventType::Finished, 90 * 60 * 1000),
+                ],
+            ),
+            WorkItem::new_internal(
+                2,
+                String::from("Team meeting"),
+                Status::Done,
+                HashSet::new(),
+                vec![
+                    Event::new(EventType::Started, 2 * 60 * 60 * 1000),
+                    Event::new(EventType::Finished, 3 * 60 * 60 * 1000),
+                ],
+            ),
+        ];
+        items[0].set_project(Some(String::from("website")));
+
+        let mut output = Vec::new();
+        MarkdownExporter {}
+            .export(
+                &items,
+                &ReportContext::new(0, 24 * 60 * 60 * 1000).with_projects(vec![Project::new(
+                    String::from("website"),
+                    Some(String::from("ACME")),
+                    Some(95.0),
+                )]),
+                &mut output,
+            )
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("## Projects\n\n| Project | Client | Time | Items | Amount |"));
+        assert!(output.contains(
+            "| website | ACME | 1h 30m | 1 | 142.50 |\n| (no project) | - | 1h | 1 | - |\n\n"
+        ));
+        assert!(output.contains("Tags: **. Project: *website*.\n"));
+    }
+
+    #[test]
+    fn test_export_notes() {
+        let mut items = vec![WorkItem::new_internal(
+            1,
+            String::from("Investigate"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 0),
+                Event::new(EventType::Finished, 60 * 60 * 1000),
+            ],
+        )];
+        items[0].add_note(String::from("Reproduced it"), Some(15 * 60 * 1000));
+        items[0].add_note(String::from("Tomorrow"), Some(24 * 60 * 60 * 1000));
+
+        let export = |context: &ReportContext| {
+            let mut output = Vec::new();
+            MarkdownExporter {}
+                .export(&items, context, &mut output)
+                .unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        let context = ReportContext::new(0, 24 * 60 * 60 * 1000);
+        let note = format!(
+            "  - {}: Reproduced it\n",
+            shared::time::get_local_date_time(15 * 60 * 1000).format(TIME_FORMAT)
+        );
+
+        assert!(!export(&context).contains("Reproduced it"));
+
+        let output = export(&context.with_notes(true));
+        assert!(output.contains(&format!("Tags: **.\n{}", note)));
+        assert!(!output.contains("Tomorrow"));
+    }
+}
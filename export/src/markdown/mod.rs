@@ -0,0 +1,3 @@
+mod markdown_exporter;
+
+pub use markdown_exporter::MarkdownExporter;
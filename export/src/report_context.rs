@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use persistence::calc::Project;
+use shared::calc::RoundingPolicy;
+
+/// Context of a report the work items are exported for.
+#[derive(Debug, Clone)]
+pub struct ReportContext {
+    /// Inclusive lower bound of the reported time range (timestamp in milliseconds).
+    from_timestamp: i64,
+    /// Exclusive upper bound of the reported time range (timestamp in milliseconds).
+    to_timestamp: i64,
+    /// Format specific options (e. g. the delimiter of CSV exports).
+    options: HashMap<String, String>,
+    /// Policy used to round the time worked (if any).
+    rounding_policy: Option<RoundingPolicy>,
+    /// Projects the work items may be assigned to (providing their client and hourly rate).
+    projects: Vec<Project>,
+    /// Whether to include the notes of the work items in the report.
+    include_notes: bool,
+}
+
+impl ReportContext {
+    /// Create a new report context for the given time range of timestamps (in milliseconds).
+    /// Uses the active rounding policy.
+    pub fn new(from_timestamp: i64, to_timestamp: i64) -> ReportContext {
+        ReportContext {
+            from_timestamp,
+            to_timestamp,
+            options: HashMap::new(),
+            rounding_policy: shared::calc::active_rounding_policy(),
+            projects: Vec::new(),
+            include_notes: false,
+        }
+    }
+
+    /// Set the format specific option with the given name.
+    pub fn with_option(mut self, name: &str, value: &str) -> ReportContext {
+        self.options.insert(name.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Set the policy used to round the time worked (`None` to not round).
+    pub fn with_rounding_policy(
+        mut self,
+        rounding_policy: Option<RoundingPolicy>,
+    ) -> ReportContext {
+        self.rounding_policy = rounding_policy;
+        self
+    }
+
+    /// Set the projects the work items may be assigned to.
+    pub fn with_projects(mut self, projects: Vec<Project>) -> ReportContext {
+        self.projects = projects;
+        self
+    }
+
+    /// Set whether to include the notes of the work items in the report.
+    pub fn with_notes(mut self, include_notes: bool) -> ReportContext {
+        self.include_notes = include_notes;
+        self
+    }
+
+    /// Get the inclusive lower bound of the reported time range.
+    pub fn from_timestamp(&self) -> i64 {
+        self.from_timestamp
+    }
+
+    /// Get the exclusive upper bound of the reported time range.
+    pub fn to_timestamp(&self) -> i64 {
+        self.to_timestamp
+    }
+
+    /// Get the value of the format specific option with the given name.
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|v| v.as_str())
+    }
+
+    /// Get the policy used to round the time worked (if any).
+    pub fn rounding_policy(&self) -> Option<&RoundingPolicy> {
+        self.rounding_policy.as_ref()
+    }
+
+    /// Get the projects the work items may be assigned to.
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    /// Check whether to include the notes of the work items in the report.
+    pub fn include_notes(&self) -> bool {
+        self.include_notes
+    }
+}
@@ -0,0 +1,4 @@
+mod report_model;
+mod template_exporter;
+
+pub use template_exporter::{file_extension_of, TemplateExporter, TEMPLATE_OPTION};
@@ -0,0 +1,310 @@
+use persistence::calc::{
+    break_down_by_week, calculate_project_statistics, calculate_rounded_total_time,
+    calculate_tag_statistics, calculate_total_work_time, round_item_time, ProjectStatistics,
+    TagStatistics, WorkItem,
+};
+use serde::Serialize;
+use shared::calc::RoundingPolicy;
+
+use crate::ReportContext;
+
+/// Report on the work done in a time range passed to report templates.
+/// Templates access its fields by name, e. g. `{{ total_time | duration }}`
+/// or `{% for week in weeks %}{{ week.label }}{% endfor %}`.
+/// All durations are in milliseconds and can be formatted using the `duration` filter (e. g. `1h 30m`).
+/// Times are given in the active time zone.
+/// The rounded times are only set when the time worked is rounded.
+#[derive(Serialize)]
+pub(crate) struct Report {
+    /// Days covered by the report.
+    period: Period,
+    /// Description of how times are rounded like `rounded up to multiples of 15m per work item`.
+    rounding: Option<String>,
+    /// Wall-clock time worked in the time range (parallel work is only counted once).
+    total_time: i64,
+    /// Time worked in the time range rounded using the rounding policy.
+    rounded_total_time: Option<i64>,
+    /// Start of the first interval worked in the time range (if any).
+    started: Option<Time>,
+    /// End of the last interval worked in the time range (if any).
+    finished: Option<Time>,
+    /// Number of days with work in the time range.
+    days_worked: usize,
+    /// Work items with the work done in the whole time range.
+    items: Vec<Item>,
+    /// Work done per week, broken down by day.
+    weeks: Vec<Week>,
+    /// Time worked per tag (`tag`, `item_count`, `total_time`, `average_item_time`,
+    /// `unique_time`, `share` and `rounded_time`) with the most time first. Untagged work has no `tag`.
+    tags: Vec<TagStatistics>,
+    /// Time worked per project (`project`, `client`, `hourly_rate`, `item_count`, `time`, `share`,
+    /// `rounded_time` and `amount`) with the most time first. Work not assigned to a project has no `project`.
+    /// Empty when no work item is assigned to a project.
+    projects: Vec<ProjectStatistics>,
+}
+
+/// Days covered by a report.
+#[derive(Serialize)]
+struct Period {
+    /// Start of the first interval worked (or the start of the time range without work).
+    from: Time,
+    /// Last moment worked (or of the time range without work).
+    to: Time,
+    /// Whether the time range is a single day.
+    single_day: bool,
+}
+
+/// Point in time formatted in different ways.
+#[derive(Serialize)]
+struct Time {
+    /// Milliseconds since the epoch.
+    timestamp: i64,
+    /// Date like `2021-01-31`.
+    date: String,
+    /// Time like `09:15`.
+    time: String,
+    /// Date and time like `2021-01-31 09:15`.
+    date_time: String,
+    /// Name of the weekday like `Sunday`.
+    weekday: String,
+}
+
+/// Work done on a work item in a time range.
+#[derive(Serialize)]
+struct Item {
+    /// ID of the work item (if stored).
+    id: Option<i32>,
+    /// Description of the work item.
+    description: String,
+    /// Status of the work item (`DONE`, `IN_PROGRESS` or `PAUSED`).
+    status: String,
+    /// Sorted tags of the work item.
+    tags: Vec<String>,
+    /// Name of the project the work item is assigned to (if any).
+    project: Option<String>,
+    /// Time worked on the work item in the time range.
+    time_taken: i64,
+    /// Time worked on the work item rounded using the rounding policy
+    /// (not set either when rounding the time worked per day).
+    rounded_time_taken: Option<i64>,
+    /// Intervals worked on the work item clipped to the time range.
+    intervals: Vec<Interval>,
+    /// Notes written on the work item in the time range
+    /// (empty unless notes are included in the report).
+    notes: Vec<Note>,
+}
+
+/// Note written on a work item.
+#[derive(Serialize)]
+struct Note {
+    /// When the note has been written.
+    time: Time,
+    /// Text of the note.
+    text: String,
+}
+
+/// Interval worked on a work item.
+#[derive(Serialize)]
+struct Interval {
+    /// Start of the interval.
+    start: Time,
+    /// End of the interval.
+    end: Time,
+    /// Length of the interval.
+    duration: i64,
+}
+
+/// Work done in a week.
+#[derive(Serialize)]
+struct Week {
+    /// ISO week-numbering year.
+    year: i32,
+    /// ISO week number.
+    week: u32,
+    /// Week like `2021-W04`.
+    label: String,
+    /// Wall-clock time worked in the week.
+    total_time: i64,
+    /// Sum of the rounded time worked per day of the week.
+    rounded_total_time: Option<i64>,
+    /// Days of the week with work.
+    days: Vec<Day>,
+}
+
+/// Work done on a day.
+#[derive(Serialize)]
+struct Day {
+    /// Start of the day.
+    date: Time,
+    /// Wall-clock time worked on the day.
+    total_time: i64,
+    /// Time worked on the day rounded using the rounding policy.
+    rounded_total_time: Option<i64>,
+    /// Work items with the work done on the day.
+    items: Vec<Item>,
+}
+
+impl Report {
+    /// Build the report on the work done on the given items in the time range of the passed context.
+    pub(crate) fn new(items: &[WorkItem], context: &ReportContext) -> Report {
+        let from_timestamp = context.from_timestamp();
+        let to_timestamp = context.to_timestamp();
+        let rounding_policy = context.rounding_policy();
+        let include_notes = context.include_notes();
+
+        let item_refs: Vec<&WorkItem> = items.iter().collect();
+
+        let intervals: Vec<(i64, i64)> = items
+            .iter()
+            .flat_map(|i| i.intervals_in_range(from_timestamp, to_timestamp))
+            .collect();
+        let start_timestamp = intervals.iter().map(|(start, _)| *start).min();
+        let end_timestamp = intervals.iter().map(|(_, end)| *end).max();
+
+        let (_, end_of_day) =
+            shared::time::day_range(shared::time::get_local_date_time(from_timestamp).date_naive());
+
+        let weeks: Vec<Week> = break_down_by_week(&item_refs, from_timestamp, to_timestamp)
+            .iter()
+            .map(|week| Week {
+                year: week.year(),
+                week: week.week(),
+                label: format!("{}-W{:02}", week.year(), week.week()),
+                total_time: week.total_time(),
+                rounded_total_time: rounding_policy.map(|policy| week.rounded_total_time(policy)),
+                days: week
+                    .days()
+                    .iter()
+                    .map(|day| Day {
+                        date: Time::new(day.from_timestamp()),
+                        total_time: day.total_time(),
+                        rounded_total_time: rounding_policy
+                            .map(|policy| day.rounded_total_time(policy)),
+                        items: day
+                            .items()
+                            .iter()
+                            .map(|item| {
+                                Item::new(
+                                    item,
+                                    day.from_timestamp(),
+                                    day.to_timestamp(),
+                                    rounding_policy,
+                                    include_notes,
+                                )
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Report {
+            period: Period {
+                from: Time::new(start_timestamp.unwrap_or(from_timestamp)),
+                to: Time::new(
+                    end_timestamp
+                        .unwrap_or(if to_timestamp == i64::MAX {
+                            from_timestamp + 1
+                        } else {
+                            to_timestamp
+                        })
+                        .saturating_sub(1),
+                ),
+                single_day: to_timestamp <= end_of_day,
+            },
+            rounding: rounding_policy.map(|policy| policy.to_string()),
+            total_time: calculate_total_work_time(&item_refs, from_timestamp, to_timestamp),
+            rounded_total_time: rounding_policy.map(|policy| {
+                calculate_rounded_total_time(&item_refs, from_timestamp, to_timestamp, policy)
+            }),
+            started: start_timestamp.map(Time::new),
+            finished: end_timestamp.map(Time::new),
+            days_worked: weeks.iter().map(|w| w.days.len()).sum(),
+            items: items
+                .iter()
+                .map(|item| {
+                    Item::new(
+                        item,
+                        from_timestamp,
+                        to_timestamp,
+                        rounding_policy,
+                        include_notes,
+                    )
+                })
+                .collect(),
+            weeks,
+            tags: calculate_tag_statistics(
+                &item_refs,
+                from_timestamp,
+                to_timestamp,
+                rounding_policy,
+            ),
+            projects: match items.iter().any(|i| i.project().is_some()) {
+                true => calculate_project_statistics(
+                    &item_refs,
+                    context.projects(),
+                    from_timestamp,
+                    to_timestamp,
+                    rounding_policy,
+                ),
+                false => Vec::new(),
+            },
+        }
+    }
+}
+
+impl Time {
+    fn new(timestamp: i64) -> Time {
+        let date_time = shared::time::get_local_date_time(timestamp);
+
+        Time {
+            timestamp,
+            date: date_time.format("%Y-%m-%d").to_string(),
+            time: date_time.format("%H:%M").to_string(),
+            date_time: date_time.format("%Y-%m-%d %H:%M").to_string(),
+            weekday: date_time.format("%A").to_string(),
+        }
+    }
+}
+
+impl Item {
+    fn new(
+        item: &WorkItem,
+        from_timestamp: i64,
+        to_timestamp: i64,
+        rounding_policy: Option<&RoundingPolicy>,
+        include_notes: bool,
+    ) -> Item {
+        Item {
+            id: item.id(),
+            description: item.description().to_owned(),
+            status: item.status().to_string(),
+            tags: item.tags(),
+            project: item.project().map(|p| p.to_owned()),
+            time_taken: item.time_taken_in_range(from_timestamp, to_timestamp),
+            rounded_time_taken: rounding_policy
+                .and_then(|policy| round_item_time(item, from_timestamp, to_timestamp, policy)),
+            intervals: item
+                .intervals_in_range(from_timestamp, to_timestamp)
+                .into_iter()
+                .map(|(start, end)| Interval {
+                    start: Time::new(start),
+                    end: Time::new(end),
+                    duration: end - start,
+                })
+                .collect(),
+            notes: match include_notes {
+                true => item
+                    .notes()
+                    .iter()
+                    .filter(|n| n.timestamp() >= from_timestamp && n.timestamp() < to_timestamp)
+                    .map(|n| Note {
+                        time: Time::new(n.timestamp()),
+                        text: n.text().to_owned(),
+                    })
+                    .collect(),
+                false => Vec::new(),
+            },
+        }
+    }
+}
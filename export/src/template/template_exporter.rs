@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use persistence::calc::WorkItem;
+use tera::{Context, Tera, Value};
+
+use crate::template::report_model::Report;
+use crate::{Exporter, ReportContext};
+
+/// Name of the report context option holding the path of the template to render.
+pub const TEMPLATE_OPTION: &str = "template";
+
+/// Built-in template used when no template is given (the layout of the markdown export).
+const DEFAULT_TEMPLATE: &str = include_str!("default.md.tera");
+
+/// Name of the built-in template.
+const DEFAULT_TEMPLATE_NAME: &str = "default.md";
+
+/// File extension of templates that is not part of the rendered file name.
+const TEMPLATE_EXTENSION: &str = ".tera";
+
+/// Exporter rendering a Tera template (https://tera.netlify.app) with the report model
+/// (see `report_model.rs`).
+/// Templates whose name ends with `.html`, `.htm` or `.xml` (ignoring `.tera`) are escaped automatically.
+pub struct TemplateExporter {}
+
+impl Exporter for TemplateExporter {
+    fn name(&self) -> &str {
+        "template"
+    }
+
+    fn file_extension(&self) -> &str {
+        "md"
+    }
+
+    fn export(
+        &self,
+        items: &[WorkItem],
+        context: &ReportContext,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<()> {
+        let (name, template) = match context.option(TEMPLATE_OPTION) {
+            Some(path) => (template_name(path), fs::read_to_string(path)?),
+            None => (
+                String::from(DEFAULT_TEMPLATE_NAME),
+                String::from(DEFAULT_TEMPLATE),
+            ),
+        };
+
+        let report = Report::new(items, context);
+
+        let mut tera = Tera::default();
+        tera.register_filter("duration", duration_filter);
+        tera.register_filter("amount", amount_filter);
+        tera.add_raw_template(&name, &template)
+            .map_err(template_error)?;
+
+        let context = Context::from_serialize(&report).map_err(template_error)?;
+        tera.render_to(&name, &context, writer)
+            .map_err(template_error)
+    }
+}
+
+/// Get the extension of the files rendered from the template at the given path
+/// (e. g. `md` for `weekly.md.tera`).
+pub fn file_extension_of(template_path: &str) -> Option<&str> {
+    Path::new(template_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.strip_suffix(TEMPLATE_EXTENSION).unwrap_or(name))
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, extension)| extension)
+        .filter(|extension| !extension.is_empty())
+}
+
+/// Get the name of the template at the given path (its file name without `.tera`).
+fn template_name(template_path: &str) -> String {
+    let file_name = Path::new(template_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(template_path);
+
+    file_name
+        .strip_suffix(TEMPLATE_EXTENSION)
+        .unwrap_or(file_name)
+        .to_owned()
+}
+
+/// Filter formatting a duration in milliseconds (e. g. `{{ total_time | duration }}` gives `1h 30m`).
+fn duration_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let duration = value
+        .as_i64()
+        .ok_or_else(|| tera::Error::msg("The duration filter expects milliseconds"))?;
+
+    Ok(Value::String(shared::time::format_duration(
+        (duration / 1000) as u32,
+    )))
+}
+
+/// Filter formatting an amount of money with two decimals (e. g. `{{ project.amount | amount }}` gives `142.50`).
+fn amount_filter(value: &Value, _args: &HashMap<String, Value>) -> tera::Result<Value> {
+    let amount = value
+        .as_f64()
+        .ok_or_else(|| tera::Error::msg("The amount filter expects a number"))?;
+
+    Ok(Value::String(format!("{:.2}", amount)))
+}
+
+/// Convert the given template error including its causes to an I/O error.
+fn template_error(error: tera::Error) -> io::Error {
+    let mut message = error.to_string();
+
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {}", cause));
+        source = cause.source();
+    }
+
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MarkdownExporter;
+    use persistence::calc::event::{Event, EventType};
+    use persistence::calc::{Project, Status};
+    use shared::calc::RoundingPolicy;
+    use std::collections::HashSet;
+
+    fn export(exporter: &dyn Exporter, items: &[WorkItem], context: &ReportContext) -> String {
+        let mut output = Vec::new();
+        exporter.export(items, context, &mut output).unwrap();
+
+        String::from_utf8(output).unwrap()
+    }
+
+    fn test_items() -> Vec<WorkItem> {
+        let mut tags = HashSet::new();
+        tags.insert(String::from("dev"));
+        tags.insert(String::from("export"));
+
+        let mut items = vec![
+            WorkItem::new_internal(
+                1,
+                String::from("Write exporter"),
+                Status::Done,
+                tags,
+                vec![
+                    Event::new(EventType::Started, 1612080000000),
+                    Event::new(EventType::Paused, 1612083600000),
+                    Event::new(EventType::Continued, 1612087200000),
+                    Event::new(EventType::Finished, 1612089000000),
+                ],
+            ),
+            WorkItem::new_internal(
+                2,
+                String::from("Deploy"),
+                Status::Done,
+                HashSet::new(),
+                vec![
+                    Event::new(EventType::Started, 1612130400000),
+                    Event::new(EventType::Finished, 1612137600000),
+                ],
+            ),
+        ];
+        items[1].set_project(Some(String::from("website")));
+        items[0].add_note(String::from("Found the\nbug"), Some(1612082000000));
+        items[1].add_note(String::from("Rolled out"), Some(1612137000000));
+
+        items
+    }
+
+    #[test]
+    fn test_default_template_matches_markdown_export() {
+        shared::time::select_time_zone(
+            shared::time::TimeZoneSetting::from_name("Europe/Berlin").unwrap(),
+        );
+
+        let items = test_items();
+        let day = 24 * 60 * 60 * 1000;
+        for context in [
+            // Sunday 2021-01-31
+            ReportContext::new(1612047600000, 1612047600000 + day),
+            ReportContext::new(1612047600000, 1612047600000 + 7 * day),
+            ReportContext::new(1612047600000, i64::MAX),
+            ReportContext::new(1612047600000, 1612047600000 + day)
+                .with_rounding_policy(RoundingPolicy::from_spec("up:15m:interval").unwrap()),
+            ReportContext::new(1612047600000, 1612047600000 + 7 * day)
+                .with_rounding_policy(RoundingPolicy::from_spec("nearest:6m:day:1h").unwrap()),
+            ReportContext::new(1612047600000, 1612047600000 + 7 * day).with_projects(vec![
+                Project::new(String::from("website"), None, Some(42.5)),
+            ]),
+            ReportContext::new(1612047600000, 1612047600000 + day).with_notes(true),
+            ReportContext::new(1612047600000, 1612047600000 + 7 * day).with_notes(true),
+        ] {
+            assert_eq!(
+                export(&TemplateExporter {}, &items, &context),
+                export(&MarkdownExporter {}, &items, &context)
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_template() {
+        let path = std::env::temp_dir().join("worklog_test_custom_template.csv.tera");
+        fs::write(
+            &path,
+            "{% for tag in tags %}{{ tag.tag | default(value=\"none\") }};{{ tag.unique_time | duration }}\n{% endfor %}",
+        )
+        .unwrap();
+
+        let context =
+            ReportContext::new(0, i64::MAX).with_option(TEMPLATE_OPTION, path.to_str().unwrap());
+        let output = export(&TemplateExporter {}, &test_items(), &context);
+        fs::remove_file(&path).unwrap();
+
+        // Most time first
+        assert_eq!(output, "none;2h\ndev;1h 30m\nexport;1h 30m\n");
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let path = std::env::temp_dir().join("worklog_test_invalid_template.md.tera");
+        fs::write(&path, "{{ total_time | duration").unwrap();
+
+        let context =
+            ReportContext::new(0, i64::MAX).with_option(TEMPLATE_OPTION, path.to_str().unwrap());
+        let mut output = Vec::new();
+        let result = TemplateExporter {}.export(&test_items(), &context, &mut output);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_file_extension_of() {
+        assert_eq!(file_extension_of("~/templates/weekly.md.tera"), Some("md"));
+        assert_eq!(file_extension_of("report.html"), Some("html"));
+        assert_eq!(file_extension_of("report.tera"), None);
+    }
+}
@@ -0,0 +1,7 @@
+use persistence::calc::WorkItem;
+
+/// Check whether the given work items are duplicates of each other.
+/// That is the case when they have the same description and the same events.
+pub fn is_duplicate(item: &WorkItem, other: &WorkItem) -> bool {
+    item.description() == other.description() && item.events() == other.events()
+}
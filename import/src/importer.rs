@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+use std::io;
+
+use persistence::calc::{Status, WorkItem};
+
+/// Importer reading work items from a specific format.
+pub trait Importer {
+    /// Name of the format used to select the importer (e. g. 'json').
+    fn name(&self) -> &str;
+
+    /// Path of the file to import from when none is given.
+    fn default_path(&self) -> Option<&str> {
+        None
+    }
+
+    /// Read the work items from the passed reader.
+    fn import(&self, reader: &mut dyn io::Read) -> io::Result<Vec<WorkItem>>;
+}
+
+/// Create a work item worked on from the given start to the end timestamp (in milliseconds).
+/// The work item is still in progress when there is no end.
+pub(crate) fn work_item(
+    description: String,
+    tags: HashSet<String>,
+    start_timestamp: i64,
+    end_timestamp: Option<i64>,
+) -> io::Result<WorkItem> {
+    let mut item = WorkItem::new_at(description, Status::InProgress, tags, start_timestamp);
+
+    if let Some(end_timestamp) = end_timestamp {
+        item.finish_working(Some(end_timestamp)).map_err(|e| {
+            invalid_data(format!(
+                "Invalid interval of '{}': {}",
+                item.description(),
+                e
+            ))
+        })?;
+    }
+
+    Ok(item)
+}
+
+/// Create an error signalling invalid import data.
+pub(crate) fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,28 @@
+use crate::{Importer, JsonImporter, TimewarriorImporter, TogglImporter, WatsonImporter};
+
+/// All available importers.
+pub const IMPORTERS: [&dyn Importer; 4] = [
+    &JsonImporter {},
+    &TimewarriorImporter {},
+    &WatsonImporter {},
+    &TogglImporter {},
+];
+
+/// Find the importer for the format with the given name (case insensitive).
+pub fn find_importer(name: &str) -> Option<&'static dyn Importer> {
+    IMPORTERS
+        .iter()
+        .find(|i| i.name().eq_ignore_ascii_case(name.trim()))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_importer() {
+        assert_eq!(find_importer("Toggl ").unwrap().name(), "toggl");
+        assert!(find_importer("hamster").is_none());
+    }
+}
@@ -0,0 +1,22 @@
+use std::io;
+
+use persistence::calc::WorkItem;
+
+use crate::Importer;
+
+/// Importer reading a JSON backup written by the JSON exporter.
+pub struct JsonImporter {}
+
+impl Importer for JsonImporter {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn default_path(&self) -> Option<&str> {
+        Some("log_export.json")
+    }
+
+    fn import(&self, reader: &mut dyn io::Read) -> io::Result<Vec<WorkItem>> {
+        export::json::read_backup(reader)
+    }
+}
@@ -0,0 +1,3 @@
+mod json_importer;
+
+pub use json_importer::JsonImporter;
@@ -0,0 +1,15 @@
+mod duplicates;
+mod importer;
+mod importers;
+mod json;
+mod timewarrior;
+mod toggl;
+mod watson;
+
+pub use duplicates::is_duplicate;
+pub use importer::Importer;
+pub use importers::{find_importer, IMPORTERS};
+pub use json::JsonImporter;
+pub use timewarrior::TimewarriorImporter;
+pub use toggl::TogglImporter;
+pub use watson::WatsonImporter;
@@ -0,0 +1,3 @@
+mod timewarrior_importer;
+
+pub use timewarrior_importer::TimewarriorImporter;
@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+use chrono::NaiveDateTime;
+use persistence::calc::WorkItem;
+
+use crate::importer::{invalid_data, work_item};
+use crate::Importer;
+
+/// Format of the timestamps in Timewarrior data files (always UTC).
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Description of intervals without tags and annotation.
+const DEFAULT_DESCRIPTION: &str = "Imported from Timewarrior";
+
+/// Importer reading Timewarrior data files (e. g. `~/.timewarrior/data/2021-01.data`).
+/// Each line like `inc 20210131T080000Z - 20210131T090000Z # "Fix bug" dev # "annotation"`
+/// is turned into a work item.
+/// The annotation is used as description if present, otherwise the first tag.
+pub struct TimewarriorImporter {}
+
+/// Word of the tags or annotation of an interval line.
+struct Token {
+    /// Text of the word (without quotes).
+    text: String,
+    /// Whether the word has been quoted.
+    quoted: bool,
+}
+
+impl Importer for TimewarriorImporter {
+    fn name(&self) -> &str {
+        "timewarrior"
+    }
+
+    fn import(&self, reader: &mut dyn io::Read) -> io::Result<Vec<WorkItem>> {
+        let mut items = Vec::new();
+
+        for (index, line) in io::BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let item =
+                parse_line(line).map_err(|e| invalid_data(format!("Line {}: {}", index + 1, e)))?;
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+}
+
+/// Parse a single interval line of a Timewarrior data file.
+fn parse_line(line: &str) -> Result<WorkItem, String> {
+    let line = line.strip_prefix("inc ").ok_or_else(|| {
+        format!(
+            "Expected an interval starting with 'inc' but got '{}'",
+            line
+        )
+    })?;
+
+    let (range, rest) = match line.find(" #") {
+        Some(index) => (&line[..index], &line[index + 2..]),
+        None => (line, ""),
+    };
+
+    let mut timestamps = range.split(" - ");
+    let start_timestamp = parse_timestamp(timestamps.next().unwrap_or(""))?;
+    let end_timestamp = match timestamps.next() {
+        Some(end) => Some(parse_timestamp(end)?),
+        None => None,
+    };
+
+    // Tags are followed by an optional annotation separated by another '#'
+    let tokens = tokenize(rest)?;
+    let separator = tokens
+        .iter()
+        .position(|t| !t.quoted && t.text == "#")
+        .unwrap_or(tokens.len());
+    let mut tags: Vec<String> = tokens[..separator].iter().map(|t| t.text.clone()).collect();
+    let annotation: Vec<&str> = tokens
+        .iter()
+        .skip(separator + 1)
+        .map(|t| t.text.as_str())
+        .collect();
+
+    let description = if !annotation.is_empty() {
+        annotation.join(" ")
+    } else if !tags.is_empty() {
+        tags.remove(0)
+    } else {
+        String::from(DEFAULT_DESCRIPTION)
+    };
+
+    work_item(
+        description,
+        tags.into_iter().collect::<HashSet<String>>(),
+        start_timestamp,
+        end_timestamp,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Parse the given Timewarrior timestamp to milliseconds since the epoch.
+fn parse_timestamp(src: &str) -> Result<i64, String> {
+    NaiveDateTime::parse_from_str(src.trim(), TIMESTAMP_FORMAT)
+        .map(|date_time| date_time.and_utc().timestamp_millis())
+        .map_err(|_| format!("Could not parse timestamp '{}'", src.trim()))
+}
+
+/// Split the given text into whitespace separated words.
+/// Words may be quoted using double quotes and contain escaped quotes (`\"`).
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+
+    let mut chars = src.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut text = String::new();
+        if c == '"' {
+            chars.next();
+
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => text.extend(chars.next()),
+                    Some(c) => text.push(c),
+                    None => return Err(format!("Missing closing quote in '{}'", src.trim())),
+                }
+            }
+
+            tokens.push(Token { text, quoted: true });
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+
+                text.push(c);
+                chars.next();
+            }
+
+            tokens.push(Token {
+                text,
+                quoted: false,
+            });
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use persistence::calc::Status;
+
+    #[test]
+    fn test_import() {
+        let data = "inc 20210131T080000Z - 20210131T093000Z # \"Fix \\\"the\\\" bug\" dev\n\
+                    \n\
+                    inc 20210131T100000Z - 20210131T110000Z # meeting team # \"Sprint planning\"\n\
+                    inc 20210131T120000Z\n";
+
+        let items = TimewarriorImporter {}.import(&mut data.as_bytes()).unwrap();
+        assert_eq!(items.len(), 3);
+
+        assert_eq!(items[0].description(), "Fix \"the\" bug");
+        assert_eq!(items[0].tags(), vec![String::from("dev")]);
+        assert_eq!(items[0].status(), Status::Done);
+        assert_eq!(items[0].time_taken(), 90 * 60 * 1000);
+        assert_eq!(items[0].created_timestamp(), 1612080000000);
+
+        assert_eq!(items[1].description(), "Sprint planning");
+        assert_eq!(
+            items[1].tags(),
+            vec![String::from("meeting"), String::from("team")]
+        );
+
+        assert_eq!(items[2].description(), DEFAULT_DESCRIPTION);
+        assert_eq!(items[2].status(), Status::InProgress);
+    }
+
+    #[test]
+    fn test_import_invalid_line() {
+        let data = "inc 20210131T080000Z - 20210131T093000Z\ninc yesterday\n";
+
+        let error = TimewarriorImporter {}
+            .import(&mut data.as_bytes())
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Line 2"));
+    }
+}
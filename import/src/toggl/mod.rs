@@ -0,0 +1,3 @@
+mod toggl_importer;
+
+pub use toggl_importer::TogglImporter;
@@ -0,0 +1,135 @@
+use std::collections::HashSet;
+use std::io;
+
+use chrono::{NaiveDate, NaiveTime};
+use persistence::calc::WorkItem;
+
+use crate::importer::{invalid_data, work_item};
+use crate::Importer;
+
+/// Format of the dates in Toggl CSV exports.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Format of the times in Toggl CSV exports.
+const TIME_FORMAT: &str = "%H:%M:%S";
+
+/// Description of time entries without description and project.
+const DEFAULT_DESCRIPTION: &str = "Imported from Toggl";
+
+/// Importer reading the CSV export of the Toggl detailed report.
+/// Each time entry is turned into a work item tagged with its tags and project.
+/// Dates and times are interpreted in the active time zone.
+pub struct TogglImporter {}
+
+impl Importer for TogglImporter {
+    fn name(&self) -> &str {
+        "toggl"
+    }
+
+    fn import(&self, reader: &mut dyn io::Read) -> io::Result<Vec<WorkItem>> {
+        let mut csv_reader = csv::Reader::from_reader(reader);
+
+        let headers = csv_reader.headers()?.clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.trim() == name)
+                .ok_or_else(|| invalid_data(format!("Missing column '{}'", name)))
+        };
+        let description_column = column("Description")?;
+        let project_column = column("Project")?;
+        let tags_column = column("Tags")?;
+        let start_date_column = column("Start date")?;
+        let start_time_column = column("Start time")?;
+        let end_date_column = column("End date")?;
+        let end_time_column = column("End time")?;
+
+        let mut items = Vec::new();
+        for (index, record) in csv_reader.records().enumerate() {
+            let record = record?;
+            let field = |column: usize| record.get(column).unwrap_or("").trim();
+            let row_error = |e: String| invalid_data(format!("Row {}: {}", index + 1, e));
+
+            let start_timestamp =
+                parse_timestamp(field(start_date_column), field(start_time_column))
+                    .map_err(row_error)?;
+            let end_timestamp = parse_timestamp(field(end_date_column), field(end_time_column))
+                .map_err(row_error)?;
+
+            let project = field(project_column);
+            let mut tags: HashSet<String> = field(tags_column)
+                .split(',')
+                .map(|t| t.trim().to_owned())
+                .filter(|t| !t.is_empty())
+                .collect();
+            if !project.is_empty() {
+                tags.insert(project.to_owned());
+            }
+
+            let description = match (field(description_column), project) {
+                ("", "") => DEFAULT_DESCRIPTION,
+                ("", project) => project,
+                (description, _) => description,
+            };
+
+            items.push(work_item(
+                description.to_owned(),
+                tags,
+                start_timestamp,
+                Some(end_timestamp),
+            )?);
+        }
+
+        Ok(items)
+    }
+}
+
+/// Parse the given date and time to a timestamp (in milliseconds) in the active time zone.
+fn parse_timestamp(date: &str, time: &str) -> Result<i64, String> {
+    let date = NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .map_err(|_| format!("Could not parse date '{}'", date))?;
+    let time = NaiveTime::parse_from_str(time, TIME_FORMAT)
+        .map_err(|_| format!("Could not parse time '{}'", time))?;
+
+    Ok(shared::time::timestamp_at(date, time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::time::{select_time_zone, TimeZoneSetting};
+
+    #[test]
+    fn test_import() {
+        select_time_zone(TimeZoneSetting::from_name("Europe/Berlin").unwrap());
+
+        let data = "User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags,Amount ()\n\
+                    Jane,jane@example.com,ACME,Website,,\"Fix header, footer\",Yes,2021-01-31,09:00:00,2021-01-31,10:30:00,01:30:00,\"dev, frontend\",\n\
+                    Jane,jane@example.com,,Internal,,,No,2021-01-31,23:30:00,2021-02-01,00:15:00,00:45:00,,\n";
+
+        let items = TogglImporter {}.import(&mut data.as_bytes()).unwrap();
+        assert_eq!(items.len(), 2);
+
+        assert_eq!(items[0].description(), "Fix header, footer");
+        assert_eq!(
+            items[0].tags(),
+            vec![
+                String::from("Website"),
+                String::from("dev"),
+                String::from("frontend")
+            ]
+        );
+        assert_eq!(items[0].created_timestamp(), 1612080000000);
+        assert_eq!(items[0].time_taken(), 90 * 60 * 1000);
+
+        assert_eq!(items[1].description(), "Internal");
+        assert_eq!(items[1].time_taken(), 45 * 60 * 1000);
+    }
+
+    #[test]
+    fn test_import_missing_column() {
+        let data = "Description,Start date\nTest,2021-01-31\n";
+
+        assert!(TogglImporter {}.import(&mut data.as_bytes()).is_err());
+    }
+}
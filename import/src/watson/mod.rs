@@ -0,0 +1,3 @@
+mod watson_importer;
+
+pub use watson_importer::WatsonImporter;
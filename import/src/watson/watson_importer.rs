@@ -0,0 +1,74 @@
+use std::io;
+
+use persistence::calc::WorkItem;
+use serde::Deserialize;
+
+use crate::importer::work_item;
+use crate::Importer;
+
+/// Importer reading the `frames` JSON file of Watson (e. g. `~/.config/watson/frames`).
+/// Each frame is turned into a work item described by its project.
+pub struct WatsonImporter {}
+
+/// Frame as stored by Watson: `[start, stop, project, id, tags, updated_at]`.
+/// Timestamps are in seconds since the epoch.
+/// The ID and update timestamp are only parsed to keep the positions of the other fields.
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct Frame(
+    i64,
+    i64,
+    String,
+    #[serde(default)] Option<String>,
+    #[serde(default)] Vec<String>,
+    #[serde(default)] Option<i64>,
+);
+
+impl Importer for WatsonImporter {
+    fn name(&self) -> &str {
+        "watson"
+    }
+
+    fn import(&self, reader: &mut dyn io::Read) -> io::Result<Vec<WorkItem>> {
+        let frames: Vec<Frame> = serde_json::from_reader(reader)?;
+
+        frames
+            .into_iter()
+            .map(|Frame(start, stop, project, _, tags, _)| {
+                work_item(
+                    project,
+                    tags.into_iter().collect(),
+                    start * 1000,
+                    Some(stop * 1000),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import() {
+        let frames = r#"[
+            [1612080000, 1612083600, "worklog", "3c6a3e8c", ["dev", "import"], 1612083600],
+            [1612090000, 1612091800, "meeting", "8d0c1a2b"]
+        ]"#;
+
+        let items = WatsonImporter {}.import(&mut frames.as_bytes()).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].description(), "worklog");
+        assert_eq!(
+            items[0].tags(),
+            vec![String::from("dev"), String::from("import")]
+        );
+        assert_eq!(items[0].time_taken(), 60 * 60 * 1000);
+        assert!(items[1].tags().is_empty());
+
+        // Frames ending before they start are rejected
+        let invalid = r#"[[1612083600, 1612080000, "worklog", "3c6a3e8c", []]]"#;
+        assert!(WatsonImporter {}.import(&mut invalid.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::calc::{calculate_total_work_time, DayBreakdown, WeekBreakdown, WorkItem};
+
+/// Break down the work done on the passed items in the given time range by day (in the active time zone).
+/// Only days work has been done on are included (sorted by date).
+/// Items worked on over midnight are part of every day they have been worked on.
+pub fn break_down_by_day<'a>(
+    items: &[&'a WorkItem],
+    from_timestamp: i64,
+    to_timestamp: i64,
+) -> Vec<DayBreakdown<'a>> {
+    let mut dates = BTreeSet::new();
+    for item in items {
+        for (start, end) in item.intervals() {
+            let (clipped_start, clipped_end) = (start.max(from_timestamp), end.min(to_timestamp));
+
+            if clipped_start < clipped_end {
+                let last_date = date_of(clipped_end - 1);
+
+                let mut date = date_of(clipped_start);
+                while date <= last_date {
+                    dates.insert(date);
+                    date = match date.succ_opt() {
+                        Some(next_date) => next_date,
+                        None => break,
+                    };
+                }
+            } else if start == end && start >= from_timestamp && start < to_timestamp {
+                dates.insert(date_of(start));
+            }
+        }
+    }
+
+    dates
+        .into_iter()
+        .map(|date| {
+            let (day_from_timestamp, day_to_timestamp) = shared::time::day_range(date);
+            let day_from_timestamp = day_from_timestamp.max(from_timestamp);
+            let day_to_timestamp = day_to_timestamp.min(to_timestamp);
+
+            let mut day_items: Vec<&WorkItem> = items
+                .iter()
+                .copied()
+                .filter(|i| first_activity(i, day_from_timestamp, day_to_timestamp).is_some())
+                .collect();
+            day_items.sort_by_key(|i| first_activity(i, day_from_timestamp, day_to_timestamp));
+
+            let total_time =
+                calculate_total_work_time(&day_items, day_from_timestamp, day_to_timestamp);
+
+            DayBreakdown::new(
+                date,
+                day_from_timestamp,
+                day_to_timestamp,
+                day_items,
+                total_time,
+            )
+        })
+        .collect()
+}
+
+/// Break down the work done on the passed items in the given time range by ISO week and day.
+/// Only weeks and days work has been done on are included (sorted by date).
+pub fn break_down_by_week<'a>(
+    items: &[&'a WorkItem],
+    from_timestamp: i64,
+    to_timestamp: i64,
+) -> Vec<WeekBreakdown<'a>> {
+    let mut weeks: Vec<WeekBreakdown<'a>> = Vec::new();
+    let mut week_days: Vec<DayBreakdown<'a>> = Vec::new();
+
+    for day in break_down_by_day(items, from_timestamp, to_timestamp) {
+        let is_another_week = week_days
+            .last()
+            .is_some_and(|d| d.date().iso_week() != day.date().iso_week());
+        if is_another_week {
+            weeks.push(week_breakdown(week_days));
+            week_days = Vec::new();
+        }
+
+        week_days.push(day);
+    }
+
+    if !week_days.is_empty() {
+        weeks.push(week_breakdown(week_days));
+    }
+
+    weeks
+}
+
+/// Create the breakdown of the week the given days belong to.
+fn week_breakdown(days: Vec<DayBreakdown>) -> WeekBreakdown {
+    let iso_week = days[0].date().iso_week();
+
+    // Count work done on multiple days (e. g. over midnight) only once
+    let mut items: Vec<&WorkItem> = Vec::new();
+    for item in days.iter().flat_map(|d| d.items()) {
+        if !items.iter().any(|i| std::ptr::eq(*i, *item)) {
+            items.push(item);
+        }
+    }
+    let total_time = calculate_total_work_time(
+        &items,
+        days.first().unwrap().from_timestamp(),
+        days.last().unwrap().to_timestamp(),
+    );
+
+    WeekBreakdown::new(iso_week.year(), iso_week.week(), days, total_time)
+}
+
+/// Get the timestamp the passed item has first been worked on in the given time range (if at all).
+/// Items started and immediately paused or finished count as worked on at that moment.
+fn first_activity(item: &WorkItem, from_timestamp: i64, to_timestamp: i64) -> Option<i64> {
+    item.intervals()
+        .into_iter()
+        .filter(|(start, end)| {
+            *start < to_timestamp
+                && (*end > from_timestamp || (start == end && *start >= from_timestamp))
+        })
+        .map(|(start, _)| start.max(from_timestamp))
+        .next()
+}
+
+/// Get the date of the given timestamp in the active time zone.
+fn date_of(timestamp: i64) -> NaiveDate {
+    shared::time::get_local_date_time(timestamp).date_naive()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::event::{Event, EventType};
+    use crate::calc::Status;
+    use shared::time::{select_time_zone, TimeZoneSetting};
+    use std::collections::HashSet;
+
+    /// Timestamp of 2021-01-31 00:00 in Europe/Berlin (a Sunday).
+    const SUNDAY: i64 = 1612047600000;
+
+    /// One hour in milliseconds.
+    const HOUR: i64 = 60 * 60 * 1000;
+
+    fn item(id: i32, start: i64, end: i64) -> WorkItem {
+        WorkItem::new_internal(
+            id,
+            format!("Item {}", id),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, start),
+                Event::new(EventType::Finished, end),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_break_down_by_week() {
+        select_time_zone(TimeZoneSetting::from_name("Europe/Berlin").unwrap());
+
+        // Over midnight from Sunday to Monday (next ISO week)
+        let late = item(1, SUNDAY + 23 * HOUR, SUNDAY + 25 * HOUR);
+        let parallel = item(2, SUNDAY + 22 * HOUR, SUNDAY + 23 * HOUR + HOUR / 2);
+        let tuesday = item(3, SUNDAY + 58 * HOUR, SUNDAY + 60 * HOUR);
+        let items = vec![&tuesday, &late, &parallel];
+
+        let days = break_down_by_day(&items, SUNDAY, SUNDAY + 7 * 24 * HOUR);
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].date(), NaiveDate::from_ymd(2021, 1, 31));
+        assert_eq!(days[0].total_time(), 2 * HOUR);
+        assert_eq!(days[0].items()[0].id(), Some(2));
+        assert_eq!(days[1].total_time(), HOUR);
+        assert_eq!(days[1].items()[0].id(), Some(1));
+
+        let weeks = break_down_by_week(&items, SUNDAY, SUNDAY + 7 * 24 * HOUR);
+        assert_eq!(weeks.len(), 2);
+        assert_eq!((weeks[0].year(), weeks[0].week()), (2021, 4));
+        assert_eq!(weeks[0].total_time(), 2 * HOUR);
+        assert_eq!((weeks[1].year(), weeks[1].week()), (2021, 5));
+        assert_eq!(weeks[1].days().len(), 2);
+        assert_eq!(weeks[1].total_time(), 3 * HOUR);
+
+        // Clipped to the time range
+        let days = break_down_by_day(&items, SUNDAY + 24 * HOUR, SUNDAY + 24 * HOUR + HOUR / 2);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].total_time(), HOUR / 2);
+    }
+}
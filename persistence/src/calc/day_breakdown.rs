@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use shared::calc::RoundingPolicy;
+
+use crate::calc::{calculate_rounded_total_time, WorkItem};
+
+/// Work done on a single day of a time range.
+#[derive(Debug)]
+pub struct DayBreakdown<'a> {
+    /// Date of the day in the active time zone.
+    date: NaiveDate,
+    /// Start of the day (clipped to the time range).
+    from_timestamp: i64,
+    /// End of the day (clipped to the time range).
+    to_timestamp: i64,
+    /// Work items worked on that day sorted by the start of the first work on the day.
+    items: Vec<&'a WorkItem>,
+    /// Total time worked on that day (in milliseconds).
+    total_time: i64,
+}
+
+impl<'a> DayBreakdown<'a> {
+    /// Create a new day breakdown.
+    pub(crate) fn new(
+        date: NaiveDate,
+        from_timestamp: i64,
+        to_timestamp: i64,
+        items: Vec<&'a WorkItem>,
+        total_time: i64,
+    ) -> DayBreakdown<'a> {
+        DayBreakdown {
+            date,
+            from_timestamp,
+            to_timestamp,
+            items,
+            total_time,
+        }
+    }
+
+    /// Get the date of the day.
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Get the inclusive start of the day (clipped to the time range).
+    pub fn from_timestamp(&self) -> i64 {
+        self.from_timestamp
+    }
+
+    /// Get the exclusive end of the day (clipped to the time range).
+    pub fn to_timestamp(&self) -> i64 {
+        self.to_timestamp
+    }
+
+    /// Get the work items worked on that day.
+    pub fn items(&self) -> &[&'a WorkItem] {
+        &self.items
+    }
+
+    /// Get the total time worked on that day (in milliseconds).
+    /// Time worked on multiple items in parallel is only counted once.
+    pub fn total_time(&self) -> i64 {
+        self.total_time
+    }
+
+    /// Get the time worked on that day rounded using the given policy (in milliseconds).
+    pub fn rounded_total_time(&self, rounding_policy: &RoundingPolicy) -> i64 {
+        calculate_rounded_total_time(
+            &self.items,
+            self.from_timestamp,
+            self.to_timestamp,
+            rounding_policy,
+        )
+    }
+}
@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::calc::event::EventType;
 
 /// Event that may occur on a work item.
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Event {
     /// Type of the event.
+    #[serde(rename = "type")]
     event_type: EventType,
     /// Timestamp this event occurred.
     timestamp: i64,
@@ -1,8 +1,11 @@
 use std::fmt;
 use std::str;
 
+use serde::{Deserialize, Serialize};
+
 /// Type of event that may occur on a work item.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EventType {
     /// The work item has been started.
     Started,
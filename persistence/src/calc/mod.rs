@@ -1,6 +1,30 @@
+mod breakdown;
+mod day_breakdown;
 pub mod event;
+mod note;
+mod project;
+mod project_statistics;
+mod rounding;
+mod statistics;
 mod status;
+mod tag_statistics;
+mod timeline;
+mod trashed_item;
+mod week_breakdown;
 mod work_item;
+mod work_time;
 
+pub use breakdown::{break_down_by_day, break_down_by_week};
+pub use day_breakdown::DayBreakdown;
+pub use note::Note;
+pub use project::Project;
+pub use project_statistics::ProjectStatistics;
+pub use rounding::{calculate_rounded_total_time, round_item_time};
+pub use statistics::{calculate_project_statistics, calculate_tag_statistics};
 pub use status::Status;
+pub use tag_statistics::TagStatistics;
+pub use timeline::validate_events;
+pub use trashed_item::TrashedItem;
+pub use week_breakdown::WeekBreakdown;
 pub use work_item::WorkItem;
+pub use work_time::calculate_total_work_time;
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Timestamped free-form note on a work item (e. g. what has been found or decided).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    /// Timestamp the note has been taken (in milliseconds).
+    timestamp: i64,
+    /// Text of the note.
+    text: String,
+}
+
+impl Note {
+    /// Create a new note.
+    pub fn new(text: String, timestamp: i64) -> Note {
+        Note { timestamp, text }
+    }
+
+    /// Get the timestamp the note has been taken (in milliseconds).
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Get the text of the note.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
@@ -0,0 +1,106 @@
+use serde::Serialize;
+
+/// Project work items may be assigned to (e. g. to bill a client).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Project {
+    /// ID of the project (only present when already stored in the database).
+    id: Option<i32>,
+    /// Unique name of the project.
+    name: String,
+    /// Client the project is done for (if any).
+    client: Option<String>,
+    /// Rate billed per hour worked on the project (if any).
+    hourly_rate: Option<f64>,
+    /// Whether the project is archived (no new work is assigned to archived projects).
+    archived: bool,
+}
+
+impl Project {
+    /// Create a new project.
+    pub fn new(name: String, client: Option<String>, hourly_rate: Option<f64>) -> Project {
+        Project {
+            id: None,
+            name,
+            client,
+            hourly_rate,
+            archived: false,
+        }
+    }
+
+    /// Create a new project for internal use.
+    pub fn new_internal(
+        id: i32,
+        name: String,
+        client: Option<String>,
+        hourly_rate: Option<f64>,
+        archived: bool,
+    ) -> Project {
+        Project {
+            id: Some(id),
+            name,
+            client,
+            hourly_rate,
+            archived,
+        }
+    }
+
+    /// Get the projects ID.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// Get the unique name of the project.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the client the project is done for (if any).
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    /// Set the client the project is done for.
+    pub fn set_client(&mut self, client: Option<String>) {
+        self.client = client;
+    }
+
+    /// Get the rate billed per hour worked on the project (if any).
+    pub fn hourly_rate(&self) -> Option<f64> {
+        self.hourly_rate
+    }
+
+    /// Set the rate billed per hour worked on the project.
+    pub fn set_hourly_rate(&mut self, hourly_rate: Option<f64>) {
+        self.hourly_rate = hourly_rate;
+    }
+
+    /// Check whether the project is archived.
+    pub fn is_archived(&self) -> bool {
+        self.archived
+    }
+
+    /// Archive the project (or restore it from the archive).
+    pub fn set_archived(&mut self, archived: bool) {
+        self.archived = archived;
+    }
+
+    /// Get the amount to bill for the given time (in milliseconds) using the hourly rate (if any).
+    pub fn amount_for(&self, duration: i64) -> Option<f64> {
+        self.hourly_rate
+            .map(|rate| rate * duration as f64 / (60 * 60 * 1000) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_for() {
+        let project = Project::new(String::from("Website"), None, Some(80.0));
+        assert_eq!(project.amount_for(90 * 60 * 1000), Some(120.0));
+
+        let internal = Project::new(String::from("Internal"), None, None);
+        assert_eq!(internal.amount_for(90 * 60 * 1000), None);
+    }
+}
@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+use crate::calc::Project;
+
+/// Statistics about the work done on the items assigned to a project.
+#[derive(Debug, Serialize)]
+pub struct ProjectStatistics {
+    /// Name of the project (None for items not assigned to any project).
+    project: Option<String>,
+    /// Client the project is done for (if any).
+    client: Option<String>,
+    /// Rate billed per hour worked on the project (if any).
+    hourly_rate: Option<f64>,
+    /// Number of items assigned to the project.
+    item_count: usize,
+    /// Wall-clock time worked on the items (in milliseconds).
+    /// Time worked on multiple items in parallel is only counted once.
+    time: i64,
+    /// Share of the time in the wall-clock time worked on all items (between 0 and 1).
+    share: f64,
+    /// Time worked on the items rounded using the rounding policy (in milliseconds, if any).
+    rounded_time: Option<i64>,
+    /// Amount to bill for the (rounded) time using the hourly rate (if any).
+    amount: Option<f64>,
+}
+
+impl ProjectStatistics {
+    /// Create new project statistics for the given project (None for items without project).
+    pub(crate) fn new(
+        project: Option<&Project>,
+        item_count: usize,
+        time: i64,
+        share: f64,
+        rounded_time: Option<i64>,
+    ) -> ProjectStatistics {
+        ProjectStatistics {
+            project: project.map(|p| p.name().to_owned()),
+            client: project.and_then(|p| p.client()).map(|c| c.to_owned()),
+            hourly_rate: project.and_then(|p| p.hourly_rate()),
+            item_count,
+            time,
+            share,
+            rounded_time,
+            amount: project.and_then(|p| p.amount_for(rounded_time.unwrap_or(time))),
+        }
+    }
+
+    /// Get the name of the project (None for items not assigned to any project).
+    pub fn project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
+
+    /// Get the client the project is done for (if any).
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    /// Get the rate billed per hour worked on the project (if any).
+    pub fn hourly_rate(&self) -> Option<f64> {
+        self.hourly_rate
+    }
+
+    /// Get the number of items assigned to the project.
+    pub fn item_count(&self) -> usize {
+        self.item_count
+    }
+
+    /// Get the wall-clock time worked on the items (in milliseconds).
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// Get the share of the time in the wall-clock time worked on all items (between 0 and 1).
+    pub fn share(&self) -> f64 {
+        self.share
+    }
+
+    /// Get the time worked on the items rounded using the rounding policy (in milliseconds, if any).
+    pub fn rounded_time(&self) -> Option<i64> {
+        self.rounded_time
+    }
+
+    /// Get the amount to bill for the rounded time (or the time worked when not rounding)
+    /// using the hourly rate of the project (if any).
+    pub fn amount(&self) -> Option<f64> {
+        self.amount
+    }
+}
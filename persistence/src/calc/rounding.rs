@@ -0,0 +1,114 @@
+use shared::calc::{RoundingPolicy, RoundingScope};
+
+use crate::calc::{break_down_by_day, WorkItem};
+
+/// Round the time worked on the given item in the passed time range using the rounding policy.
+/// Returns `None` when the policy rounds the time worked per day, as the time of a single
+/// item is not rounded then.
+pub fn round_item_time(
+    item: &WorkItem,
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: &RoundingPolicy,
+) -> Option<i64> {
+    match rounding_policy.scope() {
+        RoundingScope::Interval => Some(
+            item.intervals_in_range(from_timestamp, to_timestamp)
+                .into_iter()
+                .map(|(start, end)| rounding_policy.round(end - start))
+                .sum(),
+        ),
+        RoundingScope::Item => {
+            Some(rounding_policy.round(item.time_taken_in_range(from_timestamp, to_timestamp)))
+        }
+        RoundingScope::Day => None,
+    }
+}
+
+/// Calculate the total time worked on the passed items in the given time range rounded
+/// using the rounding policy.
+/// This is the sum of the rounded intervals or items (where work done in parallel is counted
+/// for every item) or the sum of the rounded wall-clock time worked per day.
+pub fn calculate_rounded_total_time(
+    items: &[&WorkItem],
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: &RoundingPolicy,
+) -> i64 {
+    match rounding_policy.scope() {
+        RoundingScope::Interval | RoundingScope::Item => items
+            .iter()
+            .filter_map(|item| round_item_time(item, from_timestamp, to_timestamp, rounding_policy))
+            .sum(),
+        RoundingScope::Day => break_down_by_day(items, from_timestamp, to_timestamp)
+            .iter()
+            .map(|day| rounding_policy.round(day.total_time()))
+            .sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::event::{Event, EventType};
+    use crate::calc::Status;
+    use shared::calc::RoundingMode;
+    use std::collections::HashSet;
+
+    const MINUTE: i64 = 60 * 1000;
+
+    fn item(events: Vec<Event>) -> WorkItem {
+        WorkItem::new_internal(
+            1,
+            String::from("Item"),
+            Status::Done,
+            HashSet::new(),
+            events,
+        )
+    }
+
+    #[test]
+    fn test_rounding_scopes() {
+        // Two intervals of 5 minutes and one parallel item of 20 minutes
+        let paused = item(vec![
+            Event::new(EventType::Started, 0),
+            Event::new(EventType::Paused, 5 * MINUTE),
+            Event::new(EventType::Continued, 10 * MINUTE),
+            Event::new(EventType::Finished, 15 * MINUTE),
+        ]);
+        let parallel = item(vec![
+            Event::new(EventType::Started, 0),
+            Event::new(EventType::Finished, 20 * MINUTE),
+        ]);
+        let items = [&paused, &parallel];
+        let policy = |scope| RoundingPolicy::new(RoundingMode::Up, scope, 15 * MINUTE, 0);
+
+        let per_interval = policy(RoundingScope::Interval);
+        assert_eq!(
+            round_item_time(&paused, 0, i64::MAX, &per_interval),
+            Some(30 * MINUTE)
+        );
+        assert_eq!(
+            calculate_rounded_total_time(&items, 0, i64::MAX, &per_interval),
+            60 * MINUTE
+        );
+
+        let per_item = policy(RoundingScope::Item);
+        assert_eq!(
+            round_item_time(&paused, 0, i64::MAX, &per_item),
+            Some(15 * MINUTE)
+        );
+        assert_eq!(
+            calculate_rounded_total_time(&items, 0, i64::MAX, &per_item),
+            45 * MINUTE
+        );
+
+        // The wall-clock time of the day is rounded
+        let per_day = policy(RoundingScope::Day);
+        assert_eq!(round_item_time(&paused, 0, i64::MAX, &per_day), None);
+        assert_eq!(
+            calculate_rounded_total_time(&items, 0, i64::MAX, &per_day),
+            30 * MINUTE
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use shared::calc::RoundingPolicy;
+
+use crate::calc::{
+    calculate_rounded_total_time, calculate_total_work_time, Project, ProjectStatistics,
+    TagStatistics, WorkItem,
+};
+
+/// Calculate statistics per tag about the work done on the passed items in the given time range.
+/// Items without tags are summarized under no tag.
+/// The time worked per tag is rounded as well when a rounding policy is given.
+/// The statistics are sorted by the wall-clock time worked (most first).
+pub fn calculate_tag_statistics(
+    items: &[&WorkItem],
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: Option<&RoundingPolicy>,
+) -> Vec<TagStatistics> {
+    let mut items_per_tag: BTreeMap<Option<String>, Vec<&WorkItem>> = BTreeMap::new();
+    for item in items {
+        let tags = item.tags();
+        if tags.is_empty() {
+            items_per_tag.entry(None).or_default().push(item);
+        }
+
+        for tag in tags {
+            items_per_tag.entry(Some(tag)).or_default().push(item);
+        }
+    }
+
+    let overall_time = calculate_total_work_time(items, from_timestamp, to_timestamp);
+
+    let mut statistics: Vec<TagStatistics> = items_per_tag
+        .into_iter()
+        .map(|(tag, tag_items)| {
+            let total_time = tag_items
+                .iter()
+                .map(|i| i.time_taken_in_range(from_timestamp, to_timestamp))
+                .sum();
+            let unique_time = calculate_total_work_time(&tag_items, from_timestamp, to_timestamp);
+            let share = match overall_time {
+                0 => 0.0,
+                _ => unique_time as f64 / overall_time as f64,
+            };
+
+            let rounded_time = rounding_policy.map(|policy| {
+                calculate_rounded_total_time(&tag_items, from_timestamp, to_timestamp, policy)
+            });
+
+            TagStatistics::new(
+                tag,
+                tag_items.len(),
+                total_time,
+                unique_time,
+                share,
+                rounded_time,
+            )
+        })
+        .collect();
+
+    statistics.sort_by_key(|s| Reverse(s.unique_time()));
+
+    statistics
+}
+
+/// Calculate statistics per project about the work done on the passed items in the given time range.
+/// Items not assigned to any project are summarized under no project.
+/// The client and hourly rate are taken from the given projects.
+/// The time worked per project is rounded as well when a rounding policy is given.
+/// The statistics are sorted by the wall-clock time worked (most first).
+pub fn calculate_project_statistics(
+    items: &[&WorkItem],
+    projects: &[Project],
+    from_timestamp: i64,
+    to_timestamp: i64,
+    rounding_policy: Option<&RoundingPolicy>,
+) -> Vec<ProjectStatistics> {
+    let mut items_per_project: BTreeMap<Option<&str>, Vec<&WorkItem>> = BTreeMap::new();
+    for item in items {
+        items_per_project
+            .entry(item.project())
+            .or_default()
+            .push(item);
+    }
+
+    let overall_time = calculate_total_work_time(items, from_timestamp, to_timestamp);
+
+    let mut statistics: Vec<ProjectStatistics> = items_per_project
+        .into_iter()
+        .map(|(name, project_items)| {
+            let time = calculate_total_work_time(&project_items, from_timestamp, to_timestamp);
+            let share = match overall_time {
+                0 => 0.0,
+                _ => time as f64 / overall_time as f64,
+            };
+
+            let rounded_time = rounding_policy.map(|policy| {
+                calculate_rounded_total_time(&project_items, from_timestamp, to_timestamp, policy)
+            });
+
+            // Projects missing in the given list are reported by their name only
+            let project = name.map(|name| {
+                projects
+                    .iter()
+                    .find(|p| p.name() == name)
+                    .cloned()
+                    .unwrap_or_else(|| Project::new(name.to_owned(), None, None))
+            });
+
+            ProjectStatistics::new(
+                project.as_ref(),
+                project_items.len(),
+                time,
+                share,
+                rounded_time,
+            )
+        })
+        .collect();
+
+    statistics.sort_by_key(|s| Reverse(s.time()));
+
+    statistics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::event::{Event, EventType};
+    use crate::calc::Status;
+    use shared::calc::{RoundingMode, RoundingScope};
+    use std::collections::HashSet;
+
+    fn item(tags: &[&str], start: i64, end: i64) -> WorkItem {
+        WorkItem::new_internal(
+            1,
+            String::from("Item"),
+            Status::Done,
+            tags.iter()
+                .map(|t| String::from(*t))
+                .collect::<HashSet<String>>(),
+            vec![
+                Event::new(EventType::Started, start),
+                Event::new(EventType::Finished, end),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_tag_statistics() {
+        let review = item(&["dev", "review"], 0, 100);
+        let coding = item(&["dev"], 50, 250);
+        let lunch = item(&[], 300, 400);
+
+        let statistics = calculate_tag_statistics(&[&review, &coding, &lunch], 0, 400, None);
+        assert_eq!(statistics.len(), 3);
+
+        // Parallel work is counted once for the unique time only
+        let dev = &statistics[0];
+        assert_eq!(dev.tag(), Some("dev"));
+        assert_eq!(dev.item_count(), 2);
+        assert_eq!(dev.total_time(), 300);
+        assert_eq!(dev.unique_time(), 250);
+        assert_eq!(dev.average_item_time(), 150);
+        assert!((dev.share() - 250.0 / 350.0).abs() < 1e-9);
+
+        assert_eq!(statistics[1].tag(), None);
+        assert_eq!(statistics[1].unique_time(), 100);
+        assert_eq!(statistics[2].tag(), Some("review"));
+        assert_eq!(dev.rounded_time(), None);
+    }
+
+    #[test]
+    fn test_project_statistics() {
+        let mut website = item(&[], 0, 60 * 60 * 1000);
+        website.set_project(Some(String::from("website")));
+        let mut hotfix = item(&[], 60 * 60 * 1000, 90 * 60 * 1000);
+        hotfix.set_project(Some(String::from("website")));
+        let lunch = item(&[], 2 * 60 * 60 * 1000, 3 * 60 * 60 * 1000);
+
+        let projects = vec![Project::new(
+            String::from("website"),
+            Some(String::from("ACME")),
+            Some(100.0),
+        )];
+        let policy = RoundingPolicy::new(RoundingMode::Up, RoundingScope::Item, 60 * 60 * 1000, 0);
+        let statistics = calculate_project_statistics(
+            &[&website, &hotfix, &lunch],
+            &projects,
+            0,
+            4 * 60 * 60 * 1000,
+            Some(&policy),
+        );
+        assert_eq!(statistics.len(), 2);
+
+        let website = &statistics[0];
+        assert_eq!(website.project(), Some("website"));
+        assert_eq!(website.client(), Some("ACME"));
+        assert_eq!(website.item_count(), 2);
+        assert_eq!(website.time(), 90 * 60 * 1000);
+        assert!((website.share() - 0.6).abs() < 1e-9);
+
+        // Both items are rounded up to an hour and billed as such
+        assert_eq!(website.rounded_time(), Some(2 * 60 * 60 * 1000));
+        assert_eq!(website.amount(), Some(200.0));
+
+        assert_eq!(statistics[1].project(), None);
+        assert_eq!(statistics[1].amount(), None);
+    }
+
+    #[test]
+    fn test_tag_statistics_rounded() {
+        let review = item(&["dev", "review"], 0, 100);
+        let coding = item(&["dev"], 50, 250);
+
+        let policy = RoundingPolicy::new(RoundingMode::Up, RoundingScope::Item, 60, 0);
+        let statistics = calculate_tag_statistics(&[&review, &coding], 0, 400, Some(&policy));
+
+        // 100 and 200 are rounded up to 120 and 240
+        assert_eq!(statistics[0].rounded_time(), Some(360));
+        assert_eq!(statistics[1].rounded_time(), Some(120));
+    }
+}
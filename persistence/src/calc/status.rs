@@ -1,8 +1,11 @@
 use std::fmt;
 use std::str;
 
+use serde::{Deserialize, Serialize};
+
 /// Status of a log calc.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Status {
     Done,
     InProgress,
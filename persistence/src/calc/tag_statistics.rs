@@ -0,0 +1,85 @@
+use serde::Serialize;
+
+/// Statistics about the work done on the items having a tag.
+#[derive(Debug, Serialize)]
+pub struct TagStatistics {
+    /// The tag (None for items without tags).
+    tag: Option<String>,
+    /// Number of items having the tag.
+    item_count: usize,
+    /// Sum of the time worked on the items (in milliseconds).
+    /// Time worked on multiple items in parallel is counted for every item.
+    total_time: i64,
+    /// Average time worked per item (in milliseconds).
+    average_item_time: i64,
+    /// Wall-clock time worked on the items (in milliseconds).
+    /// Time worked on multiple items in parallel is only counted once.
+    unique_time: i64,
+    /// Share of the unique time in the unique time worked on all items (between 0 and 1).
+    share: f64,
+    /// Time worked on the items rounded using the rounding policy (in milliseconds, if any).
+    rounded_time: Option<i64>,
+}
+
+impl TagStatistics {
+    /// Create new tag statistics.
+    pub(crate) fn new(
+        tag: Option<String>,
+        item_count: usize,
+        total_time: i64,
+        unique_time: i64,
+        share: f64,
+        rounded_time: Option<i64>,
+    ) -> TagStatistics {
+        let average_item_time = match item_count {
+            0 => 0,
+            count => total_time / count as i64,
+        };
+
+        TagStatistics {
+            tag,
+            item_count,
+            total_time,
+            average_item_time,
+            unique_time,
+            share,
+            rounded_time,
+        }
+    }
+
+    /// Get the tag (None for items without tags).
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    /// Get the number of items having the tag.
+    pub fn item_count(&self) -> usize {
+        self.item_count
+    }
+
+    /// Get the sum of the time worked on the items (in milliseconds).
+    pub fn total_time(&self) -> i64 {
+        self.total_time
+    }
+
+    /// Get the wall-clock time worked on the items (in milliseconds).
+    pub fn unique_time(&self) -> i64 {
+        self.unique_time
+    }
+
+    /// Get the share of the unique time in the unique time worked on all items (between 0 and 1).
+    /// The shares of all tags may add up to more than 1 as items can have multiple tags.
+    pub fn share(&self) -> f64 {
+        self.share
+    }
+
+    /// Get the average time worked per item (in milliseconds).
+    pub fn average_item_time(&self) -> i64 {
+        self.average_item_time
+    }
+
+    /// Get the time worked on the items rounded using the rounding policy (in milliseconds, if any).
+    pub fn rounded_time(&self) -> Option<i64> {
+        self.rounded_time
+    }
+}
@@ -0,0 +1,131 @@
+use crate::calc::event::{Event, EventType};
+use crate::calc::Status;
+use crate::Error;
+
+/// Validate the given event timeline of a work item.
+/// A valid timeline
+/// - starts with a single `Started` event,
+/// - is sorted by the events timestamps,
+/// - alternates between start (`Started`, `Continued`) and end (`Paused`, `Finished`) events,
+///   except that a paused work item may be finished directly,
+/// - contains `Finished` at most once as the last event.
+///
+/// Returns the status of the work item matching the last event.
+pub fn validate_events(events: &[Event]) -> Result<Status, Error> {
+    match events.first().map(|e| e.event_type()) {
+        Some(EventType::Started) => {}
+        Some(_) => {
+            return Err(Error::InvalidTimeline(String::from(
+                "The first event must be the start of the work item",
+            )))
+        }
+        None => {
+            return Err(Error::InvalidTimeline(String::from(
+                "A work item must have at least a start event",
+            )))
+        }
+    }
+
+    for (index, pair) in events.windows(2).enumerate() {
+        let (previous, event) = (&pair[0], &pair[1]);
+
+        if event.timestamp() < previous.timestamp() {
+            return Err(Error::InvalidTimeline(format!(
+                "Event {} ({}) must not happen before event {} ({})",
+                index + 2,
+                event.event_type(),
+                index + 1,
+                previous.event_type()
+            )));
+        }
+
+        let allowed = matches!(
+            (previous.event_type(), event.event_type()),
+            (EventType::Started, EventType::Paused)
+                | (EventType::Started, EventType::Finished)
+                | (EventType::Continued, EventType::Paused)
+                | (EventType::Continued, EventType::Finished)
+                | (EventType::Paused, EventType::Continued)
+                | (EventType::Paused, EventType::Finished)
+        );
+
+        if !allowed {
+            return Err(Error::InvalidTimeline(format!(
+                "Event {} ({}) must not follow event {} ({})",
+                index + 2,
+                event.event_type(),
+                index + 1,
+                previous.event_type()
+            )));
+        }
+    }
+
+    Ok(status_after(events.last().unwrap()))
+}
+
+/// Get the status of a work item whose last event is the given one.
+fn status_after(event: &Event) -> Status {
+    match event.event_type() {
+        EventType::Started | EventType::Continued => Status::InProgress,
+        EventType::Paused => Status::Paused,
+        EventType::Finished => Status::Done,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeline(events: &[(EventType, i64)]) -> Vec<Event> {
+        events.iter().map(|(t, ts)| Event::new(*t, *ts)).collect()
+    }
+
+    #[test]
+    fn test_valid_timelines() {
+        assert_eq!(
+            validate_events(&timeline(&[(EventType::Started, 10)])).unwrap(),
+            Status::InProgress
+        );
+        assert_eq!(
+            validate_events(&timeline(&[
+                (EventType::Started, 10),
+                (EventType::Paused, 20),
+                (EventType::Continued, 30),
+                (EventType::Paused, 40),
+            ]))
+            .unwrap(),
+            Status::Paused
+        );
+        assert_eq!(
+            validate_events(&timeline(&[
+                (EventType::Started, 10),
+                (EventType::Paused, 20),
+                (EventType::Finished, 20),
+            ]))
+            .unwrap(),
+            Status::Done
+        );
+    }
+
+    #[test]
+    fn test_invalid_timelines() {
+        assert!(validate_events(&[]).is_err());
+        assert!(validate_events(&timeline(&[(EventType::Paused, 10)])).is_err());
+        assert!(validate_events(&timeline(&[
+            (EventType::Started, 10),
+            (EventType::Paused, 5),
+        ]))
+        .is_err());
+        assert!(validate_events(&timeline(&[
+            (EventType::Started, 10),
+            (EventType::Continued, 20),
+        ]))
+        .is_err());
+        assert!(validate_events(&timeline(&[
+            (EventType::Started, 10),
+            (EventType::Finished, 20),
+            (EventType::Continued, 30),
+        ]))
+        .is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::calc::WorkItem;
+
+/// Work item moved to the trash.
+#[derive(Debug)]
+pub struct TrashedItem {
+    /// The deleted work item.
+    item: WorkItem,
+    /// Timestamp the work item has been moved to the trash (in milliseconds).
+    deleted_timestamp: i64,
+}
+
+impl TrashedItem {
+    pub(crate) fn new(item: WorkItem, deleted_timestamp: i64) -> TrashedItem {
+        TrashedItem {
+            item,
+            deleted_timestamp,
+        }
+    }
+
+    /// Get the deleted work item.
+    pub fn item(&self) -> &WorkItem {
+        &self.item
+    }
+
+    /// Get the timestamp the work item has been moved to the trash (in milliseconds).
+    pub fn deleted_timestamp(&self) -> i64 {
+        self.deleted_timestamp
+    }
+}
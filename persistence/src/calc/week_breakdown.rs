@@ -0,0 +1,63 @@
+use shared::calc::RoundingPolicy;
+
+use crate::calc::DayBreakdown;
+
+/// Work done in a single ISO week (Monday to Sunday) of a time range.
+#[derive(Debug)]
+pub struct WeekBreakdown<'a> {
+    /// ISO year the week belongs to.
+    year: i32,
+    /// ISO week number.
+    week: u32,
+    /// Days of the week work has been done on.
+    days: Vec<DayBreakdown<'a>>,
+    /// Total time worked in the week (in milliseconds).
+    total_time: i64,
+}
+
+impl<'a> WeekBreakdown<'a> {
+    /// Create a new week breakdown.
+    pub(crate) fn new(
+        year: i32,
+        week: u32,
+        days: Vec<DayBreakdown<'a>>,
+        total_time: i64,
+    ) -> WeekBreakdown<'a> {
+        WeekBreakdown {
+            year,
+            week,
+            days,
+            total_time,
+        }
+    }
+
+    /// Get the ISO year the week belongs to.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Get the ISO week number.
+    pub fn week(&self) -> u32 {
+        self.week
+    }
+
+    /// Get the days of the week work has been done on.
+    pub fn days(&self) -> &[DayBreakdown<'a>] {
+        &self.days
+    }
+
+    /// Get the total time worked in the week (in milliseconds).
+    /// Time worked on multiple items in parallel is only counted once.
+    pub fn total_time(&self) -> i64 {
+        self.total_time
+    }
+
+    /// Get the time worked in the week rounded using the given policy (in milliseconds).
+    /// This is the sum of the rounded time worked per day.
+    pub fn rounded_total_time(&self, rounding_policy: &RoundingPolicy) -> i64 {
+        self.days
+            .iter()
+            .map(|day| day.rounded_total_time(rounding_policy))
+            .sum()
+    }
+}
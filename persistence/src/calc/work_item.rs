@@ -1,32 +1,54 @@
 use std::collections::HashSet;
 
+use serde::{Deserialize, Serialize, Serializer};
+
 use crate::calc::event::{Event, EventType};
-use crate::calc::Status;
+use crate::calc::{validate_events, Note, Status};
+use crate::Error;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct WorkItem {
     /// ID of the work item (only present when already stored in the database).
     id: Option<i32>,
     /// Description of the work item.
     description: String,
     /// Tags to further classify the work item.
+    #[serde(serialize_with = "serialize_sorted")]
     tags: HashSet<String>,
+    /// Name of the project the work item is assigned to (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
     /// Status of the work item.
     status: Status,
     /// Events the work item was undergoing in its lifetime.
     /// They must be sorted by their timestamp all the time.
     events: Vec<Event>,
+    /// Notes taken while working on the work item sorted by their timestamp.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<Note>,
 }
 
 impl WorkItem {
     /// Create a new log calc.
     pub fn new(description: String, status: Status, tags: HashSet<String>) -> WorkItem {
+        WorkItem::new_at(description, status, tags, get_current_timestamp())
+    }
+
+    /// Create a new log calc started at the given timestamp (in milliseconds).
+    pub fn new_at(
+        description: String,
+        status: Status,
+        tags: HashSet<String>,
+        timestamp: i64,
+    ) -> WorkItem {
         WorkItem {
             id: None,
             description,
             status,
             tags,
-            events: vec![Event::new(EventType::Started, get_current_timestamp())],
+            project: None,
+            events: vec![Event::new(EventType::Started, timestamp)],
+            notes: Vec::new(),
         }
     }
 
@@ -43,7 +65,9 @@ impl WorkItem {
             description,
             status,
             tags,
+            project: None,
             events,
+            notes: Vec::new(),
         }
     }
 
@@ -86,9 +110,38 @@ impl WorkItem {
         self.tags.remove(tag);
     }
 
+    /// Get the name of the project the work item is assigned to (if any).
+    pub fn project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
+
+    /// Assign the work item to the project with the given name (or to no project).
+    pub fn set_project(&mut self, project: Option<String>) {
+        self.project = project;
+    }
+
     /// Get the time the user has been working on the work item (in milliseconds).
     pub fn time_taken(&self) -> i64 {
-        let mut time_taken: i64 = 0;
+        self.intervals()
+            .iter()
+            .map(|(start, end)| end - start)
+            .sum()
+    }
+
+    /// Get the time the user has been working on the work item in the given
+    /// time range of timestamps (in milliseconds).
+    /// The lower bound is inclusive, the upper bound exclusive.
+    pub fn time_taken_in_range(&self, from_timestamp: i64, to_timestamp: i64) -> i64 {
+        self.intervals_in_range(from_timestamp, to_timestamp)
+            .iter()
+            .map(|(start, end)| end - start)
+            .sum()
+    }
+
+    /// Get the time intervals (start and end timestamp) the user has been working on the work item.
+    /// An interval still in progress ends now.
+    pub fn intervals(&self) -> Vec<(i64, i64)> {
+        let mut intervals = Vec::new();
 
         let mut cur_start_timestamp: Option<i64> = None;
         for event in &self.events {
@@ -100,22 +153,33 @@ impl WorkItem {
                     // When work item has been finished after paused,
                     // there is no cur_start_timestamp.
                     if let Some(start) = cur_start_timestamp {
-                        let end = event.timestamp();
-
-                        time_taken += end - start;
+                        intervals.push((start, event.timestamp()));
                         cur_start_timestamp = None;
                     }
                 }
             }
         }
 
-        // Check whether item is currently in progress and calculate time taken to now!
+        // Check whether item is currently in progress and end the last interval now!
         if let Status::InProgress = self.status {
-            time_taken +=
-                chrono::Utc::now().timestamp_millis() - self.events.last().unwrap().timestamp();
+            intervals.push((
+                self.events.last().unwrap().timestamp(),
+                chrono::Utc::now().timestamp_millis(),
+            ));
         }
 
-        return time_taken;
+        intervals
+    }
+
+    /// Get the time intervals the user has been working on the work item
+    /// clipped to the given time range of timestamps.
+    /// Intervals outside of the range are omitted.
+    pub fn intervals_in_range(&self, from_timestamp: i64, to_timestamp: i64) -> Vec<(i64, i64)> {
+        self.intervals()
+            .into_iter()
+            .map(|(start, end)| (start.max(from_timestamp), end.min(to_timestamp)))
+            .filter(|(start, end)| start < end)
+            .collect()
     }
 
     /// Get the timestamp the item was created.
@@ -146,59 +210,262 @@ impl WorkItem {
         self.events = events;
     }
 
-    /// Pause working on the work item.
-    /// Will result in an error if the work item is in an invalid state.
-    pub fn pause_working(&mut self) -> Result<(), &'static str> {
-        if let Status::InProgress = self.status {
-            self.status = Status::Paused;
+    /// Get the notes taken on the work item sorted by their timestamp.
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
 
-            // Add paused event
-            self.events
-                .push(Event::new(EventType::Paused, get_current_timestamp()));
+    /// Set the notes of the work item.
+    pub fn set_notes(&mut self, mut notes: Vec<Note>) {
+        notes.sort_by_key(|n| n.timestamp());
+        self.notes = notes;
+    }
+
+    /// Add a note with the given text taken at the passed timestamp (or now).
+    pub fn add_note(&mut self, text: String, timestamp: Option<i64>) {
+        let note = Note::new(text, timestamp.unwrap_or_else(get_current_timestamp));
+
+        // Keep the notes sorted (after all notes with the same timestamp)
+        let index = self
+            .notes
+            .iter()
+            .position(|n| n.timestamp() > note.timestamp())
+            .unwrap_or(self.notes.len());
+        self.notes.insert(index, note);
+    }
+
+    /// Check whether the events of the item form a valid timeline
+    /// and the status matches the last event.
+    pub fn validate(&self) -> Result<(), Error> {
+        let status = validate_events(&self.events)?;
+        if status != self.status {
+            return Err(Error::InvalidTimeline(format!(
+                "The status {} does not match the last event (expected {})",
+                self.status, status
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Edit the events of the item using the given function.
+    /// The edited events are only applied when they form a valid timeline
+    /// (see `validate_events`). The status is updated to match the last event.
+    pub fn edit_events<F>(&mut self, edit: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Vec<Event>),
+    {
+        let mut events = self.events.clone();
+        edit(&mut events);
+
+        self.status = validate_events(&events)?;
+        self.events = events;
+
+        Ok(())
+    }
 
-            Ok(())
+    /// Insert the given event into the timeline (after all events with the same timestamp).
+    pub fn insert_event(&mut self, event: Event) -> Result<(), Error> {
+        self.edit_events(|events| {
+            let index = events
+                .iter()
+                .position(|e| e.timestamp() > event.timestamp())
+                .unwrap_or(events.len());
+
+            events.insert(index, event);
+        })
+    }
+
+    /// Move the event at the given index of the timeline to the passed timestamp.
+    /// The event keeps its position in the timeline.
+    pub fn move_event(&mut self, index: usize, timestamp: i64) -> Result<(), Error> {
+        let event_type = self.event_at(index)?.event_type();
+
+        self.edit_events(|events| events[index] = Event::new(event_type, timestamp))
+    }
+
+    /// Remove the event at the given index of the timeline.
+    pub fn remove_event(&mut self, index: usize) -> Result<(), Error> {
+        self.event_at(index)?;
+
+        self.edit_events(|events| {
+            events.remove(index);
+        })
+    }
+
+    /// Get the event at the given index of the timeline.
+    fn event_at(&self, index: usize) -> Result<&Event, Error> {
+        self.events.get(index).ok_or_else(|| {
+            Error::InvalidTimeline(format!(
+                "There is no event {} (the work item has {} events)",
+                index + 1,
+                self.events.len()
+            ))
+        })
+    }
+
+    /// Pause working on the work item at the given timestamp (or now).
+    /// Will result in an error if the work item is in an invalid state
+    /// or the timestamp lies before the last event.
+    pub fn pause_working(&mut self, timestamp: Option<i64>) -> Result<(), Error> {
+        if let Status::InProgress = self.status {
+            // Add paused event
+            self.push_event(EventType::Paused, timestamp)
         } else {
-            Err("Can only pause work items that are currently in progress!")
+            Err(Error::InvalidTransition(
+                "Can only pause work items that are currently in progress",
+            ))
         }
     }
 
-    /// Continue working on the work item.
-    /// Will result in an error if the work item is in an invalid state.
-    pub fn continue_working(&mut self) -> Result<(), &'static str> {
+    /// Continue working on the work item at the given timestamp (or now).
+    /// Will result in an error if the work item is in an invalid state
+    /// or the timestamp lies before the last event.
+    pub fn continue_working(&mut self, timestamp: Option<i64>) -> Result<(), Error> {
         if let Status::Paused = self.status {
-            self.status = Status::InProgress;
-
             // Add continue event
-            self.events
-                .push(Event::new(EventType::Continued, get_current_timestamp()));
-
-            Ok(())
+            self.push_event(EventType::Continued, timestamp)
         } else {
-            Err("Can only continue working on work items that are currently paused!")
+            Err(Error::InvalidTransition(
+                "Can only continue working on work items that are currently paused",
+            ))
         }
     }
 
-    /// Finish working on the work item.
-    /// Will result in an error if the work item is in an invalid state.
-    pub fn finish_working(&mut self, timestamp: Option<i64>) -> Result<(), &'static str> {
+    /// Finish working on the work item at the given timestamp (or now).
+    /// Will result in an error if the work item is in an invalid state
+    /// or the timestamp lies before the last event.
+    pub fn finish_working(&mut self, timestamp: Option<i64>) -> Result<(), Error> {
         match self.status {
             Status::InProgress | Status::Paused => {
-                self.status = Status::Done;
-
                 // Add finished event
-                self.events.push(Event::new(
-                    EventType::Finished,
-                    timestamp.unwrap_or(get_current_timestamp()),
-                ));
-
-                Ok(())
+                self.push_event(EventType::Finished, timestamp)
             }
-            Status::Done => Err("Cannot finish working on work item already finished!"),
+            Status::Done => Err(Error::InvalidTransition(
+                "Cannot finish working on work item already finished",
+            )),
         }
     }
+
+    /// Append an event of the given type at the passed timestamp (or now) to the timeline.
+    fn push_event(&mut self, event_type: EventType, timestamp: Option<i64>) -> Result<(), Error> {
+        let timestamp = timestamp.unwrap_or_else(get_current_timestamp);
+
+        self.edit_events(|events| events.push(Event::new(event_type, timestamp)))
+    }
 }
 
 /// Get the current UTC+0 timestamp in milliseconds.
 fn get_current_timestamp() -> i64 {
     chrono::Utc::now().timestamp_millis()
 }
+
+/// Serialize the given tags sorted alphabetically to get a stable output.
+fn serialize_sorted<S: Serializer>(
+    tags: &HashSet<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut sorted: Vec<&String> = tags.iter().collect();
+    sorted.sort();
+
+    serializer.collect_seq(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intervals_in_range() {
+        let item = WorkItem::new_internal(
+            1,
+            String::from("Cross midnight"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 50),
+                Event::new(EventType::Paused, 80),
+                Event::new(EventType::Continued, 120),
+                Event::new(EventType::Finished, 250),
+            ],
+        );
+
+        assert_eq!(item.intervals(), vec![(50, 80), (120, 250)]);
+        assert_eq!(item.intervals_in_range(70, 200), vec![(70, 80), (120, 200)]);
+        assert!(item.intervals_in_range(80, 120).is_empty());
+        assert_eq!(item.time_taken(), 160);
+        assert_eq!(item.time_taken_in_range(100, 300), 130);
+    }
+
+    #[test]
+    fn test_edit_events() {
+        let mut item = WorkItem::new_internal(
+            1,
+            String::from("Forgot to stop"),
+            Status::InProgress,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 10),
+                Event::new(EventType::Paused, 20),
+                Event::new(EventType::Continued, 30),
+            ],
+        );
+
+        item.insert_event(Event::new(EventType::Finished, 50))
+            .unwrap();
+        assert_eq!(item.status(), Status::Done);
+
+        item.move_event(3, 40).unwrap();
+        assert_eq!(item.events()[3], Event::new(EventType::Finished, 40));
+
+        // Moving the finish before the continue is rejected and nothing changes
+        assert!(item.move_event(3, 25).is_err());
+        assert_eq!(item.events()[3].timestamp(), 40);
+
+        // Removing the pause alone would break the timeline
+        assert!(item.remove_event(1).is_err());
+        assert!(item.remove_event(7).is_err());
+
+        item.remove_event(3).unwrap();
+        assert_eq!(item.status(), Status::InProgress);
+    }
+
+    #[test]
+    fn test_backdated_transitions() {
+        let mut item = WorkItem::new_at(
+            String::from("Backdated"),
+            Status::InProgress,
+            HashSet::new(),
+            100,
+        );
+
+        item.pause_working(Some(200)).unwrap();
+        assert_eq!(item.status(), Status::Paused);
+
+        // Cannot continue before the pause
+        assert!(item.continue_working(Some(150)).is_err());
+        assert_eq!(item.status(), Status::Paused);
+
+        item.continue_working(Some(300)).unwrap();
+        item.finish_working(Some(400)).unwrap();
+        assert_eq!(item.status(), Status::Done);
+        assert_eq!(item.time_taken(), 200);
+    }
+
+    #[test]
+    fn test_add_note() {
+        let mut item = WorkItem::new_at(
+            String::from("Investigate"),
+            Status::InProgress,
+            HashSet::new(),
+            100,
+        );
+
+        item.add_note(String::from("Found the cause"), Some(300));
+        item.add_note(String::from("Reproduced it"), Some(200));
+        item.add_note(String::from("Fixed it"), Some(300));
+
+        let texts: Vec<&str> = item.notes().iter().map(|n| n.text()).collect();
+        assert_eq!(texts, vec!["Reproduced it", "Found the cause", "Fixed it"]);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::calc::WorkItem;
+
+/// Calculate the total work time (in milliseconds) of the passed items in the given time range.
+/// Work done outside of the time range is not counted and
+/// time worked on multiple items in parallel is only counted once.
+pub fn calculate_total_work_time(
+    items: &[&WorkItem],
+    from_timestamp: i64,
+    to_timestamp: i64,
+) -> i64 {
+    let mut time_events: Vec<shared::calc::TimeEvent> = items
+        .iter()
+        .flat_map(|i| i.intervals_in_range(from_timestamp, to_timestamp))
+        .flat_map(|(start, end)| {
+            vec![
+                shared::calc::TimeEvent::new(true, start),
+                shared::calc::TimeEvent::new(false, end),
+            ]
+        })
+        .collect();
+
+    shared::calc::calculate_unique_total_time(&mut time_events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::event::{Event, EventType};
+    use crate::calc::Status;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_parallel_work_clipped_to_range() {
+        let first = WorkItem::new_internal(
+            1,
+            String::from("First"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 0),
+                Event::new(EventType::Finished, 100),
+            ],
+        );
+        let second = WorkItem::new_internal(
+            2,
+            String::from("Second"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 50),
+                Event::new(EventType::Finished, 150),
+            ],
+        );
+
+        assert_eq!(calculate_total_work_time(&[&first, &second], 0, 200), 150);
+        assert_eq!(calculate_total_work_time(&[&first, &second], 75, 125), 50);
+    }
+}
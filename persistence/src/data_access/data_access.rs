@@ -1,36 +1,102 @@
-use std::error::Error;
-
-use crate::calc::{Status, WorkItem};
+use crate::calc::{Project, Status, TrashedItem, WorkItem};
+use crate::journal::JournalEntry;
+use crate::query::{ItemQuery, SearchResult};
+use crate::revision::Revision;
+use crate::Error;
 
 /// Common data access interface.
 pub trait DataAccess {
     /// Log a work calc.
     /// Will return the ID of the new work item.
-    fn log_item(&mut self, item: WorkItem) -> Result<i32, Box<dyn Error>>;
+    fn log_item(&mut self, item: WorkItem) -> Result<i32, Error>;
+
+    /// Log a work item keeping its ID (e. g. when restoring a backup).
+    /// Fails with `Error::AlreadyExists` when the ID is already taken.
+    fn restore_item(&mut self, item: WorkItem) -> Result<i32, Error>;
 
     /// Update a bunch of work items.
-    fn update_items(&mut self, items: Vec<&WorkItem>) -> Result<(), Box<dyn Error>>;
+    fn update_items(&mut self, items: Vec<&WorkItem>) -> Result<(), Error>;
+
+    /// Find all work items matching the given query.
+    fn query_items(&self, query: &ItemQuery) -> Result<Vec<WorkItem>, Error>;
 
     /// List all available work items.
-    fn list_items(&self) -> Result<Vec<WorkItem>, Box<dyn Error>>;
+    fn list_items(&self) -> Result<Vec<WorkItem>, Error> {
+        self.query_items(&ItemQuery::new())
+    }
 
     /// List available work items in the given time range.
-    fn filter_items(
-        &self,
-        from_timestamp: i64,
-        to_timestamp: i64,
-    ) -> Result<Vec<WorkItem>, Box<dyn Error>>;
+    fn filter_items(&self, from_timestamp: i64, to_timestamp: i64) -> Result<Vec<WorkItem>, Error> {
+        self.query_items(&ItemQuery::new().time_range(from_timestamp, to_timestamp))
+    }
+
+    /// Search work items by the given text.
+    /// Returns the matching work items ordered by relevance (best match first).
+    fn search_items(&self, text: &str) -> Result<Vec<SearchResult>, Error>;
 
     /// Find a work item by its ID.
-    fn find_item_by_id(&self, id: i32) -> Result<Option<WorkItem>, Box<dyn Error>>;
+    fn find_item_by_id(&self, id: i32) -> Result<Option<WorkItem>, Error>;
 
     /// Find work items by the given status.
-    fn find_items_by_status(&self, status: Status) -> Result<Vec<WorkItem>, Box<dyn Error>>;
+    fn find_items_by_status(&self, status: Status) -> Result<Vec<WorkItem>, Error> {
+        self.query_items(&ItemQuery::new().with_status(status))
+    }
 
-    /// Delete a work item with the given ID.
+    /// Delete a work item with the given ID by moving it to the trash.
     /// Returns the deleted work item or None if there is no item with the given ID.
-    fn delete_item(&mut self, id: i32) -> Result<Option<WorkItem>, Box<dyn Error>>;
+    fn delete_item(&mut self, id: i32) -> Result<Option<WorkItem>, Error>;
+
+    /// Clear the underlying database (Move all work items to the trash).
+    fn clear(&mut self) -> Result<(), Error>;
+
+    /// List all work items in the trash (most recently deleted first).
+    fn list_trash(&self) -> Result<Vec<TrashedItem>, Error>;
+
+    /// Restore the work item with the given ID from the trash.
+    /// Returns the restored work item or None if there is no item with the given ID in the trash.
+    fn restore_from_trash(&mut self, id: i32) -> Result<Option<WorkItem>, Error>;
+
+    /// Permanently delete all work items moved to the trash before the given timestamp.
+    /// Returns the number of deleted work items.
+    fn purge_trash(&mut self, deleted_before: i64) -> Result<usize, Error>;
+
+    /// Undo the latest changes recorded in the journal that have not been undone yet.
+    /// Returns the undone journal entry or None if there is nothing to undo.
+    fn undo(&mut self) -> Result<Option<JournalEntry>, Error>;
+
+    /// Redo the earliest changes recorded in the journal that have been undone.
+    /// Returns the redone journal entry or None if there is nothing to redo.
+    fn redo(&mut self) -> Result<Option<JournalEntry>, Error>;
+
+    /// List the latest journal entries (newest first) up to the given limit.
+    fn list_journal(&self, limit: usize) -> Result<Vec<JournalEntry>, Error>;
+
+    /// Get the revision history of the work item with the given ID (oldest revision first).
+    fn item_history(&self, id: i32) -> Result<Vec<Revision>, Error>;
+
+    /// Create a new project.
+    /// Will return the ID of the new project.
+    /// Fails with `Error::ProjectAlreadyExists` when the name is already taken.
+    fn create_project(&mut self, project: Project) -> Result<i32, Error>;
+
+    /// Update the client, hourly rate and archived flag of a project.
+    fn update_project(&mut self, project: &Project) -> Result<(), Error>;
+
+    /// List all projects (including archived ones) sorted by their name.
+    fn list_projects(&self) -> Result<Vec<Project>, Error>;
+
+    /// Find a project by its name.
+    fn find_project_by_name(&self, name: &str) -> Result<Option<Project>, Error>;
+
+    /// Begin a transaction spanning all following operations until
+    /// it is either committed or rolled back.
+    /// Transactions may be nested, the changes of a nested transaction are only persisted
+    /// when all enclosing transactions are committed as well.
+    fn begin_transaction(&mut self) -> Result<(), Error>;
+
+    /// Commit the transaction previously begun.
+    fn commit_transaction(&mut self) -> Result<(), Error>;
 
-    /// Clear the underlying database (Remove all work items).
-    fn clear(&mut self) -> Result<(), Box<dyn Error>>;
+    /// Roll back the transaction previously begun.
+    fn rollback_transaction(&mut self) -> Result<(), Error>;
 }
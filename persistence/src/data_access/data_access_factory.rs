@@ -1,8 +1,18 @@
+use crate::data_access::location::{self, DatabaseLocation};
 use crate::data_access::sqlite::SQLiteDataAccess;
 use crate::data_access::DataAccess;
-use std::error::Error;
+use crate::Error;
 
 /// Get the data access to use.
-pub fn get_data_access() -> Result<Box<dyn DataAccess>, Box<dyn Error>> {
-    Ok(Box::new(SQLiteDataAccess::new()?))
+/// The database location is resolved from the currently active profile.
+pub fn get_data_access() -> Result<Box<dyn DataAccess>, Error> {
+    get_data_access_at(&location::active_location())
+}
+
+/// Get a data access working on the database at the given location.
+pub fn get_data_access_at(location: &DatabaseLocation) -> Result<Box<dyn DataAccess>, Error> {
+    match location {
+        DatabaseLocation::Memory => Ok(Box::new(SQLiteDataAccess::shared_in_memory()?)),
+        location => Ok(Box::new(SQLiteDataAccess::new(&location.path()?)?)),
+    }
 }
@@ -0,0 +1,170 @@
+use std::cell::RefCell;
+use std::env;
+use std::path;
+
+use crate::Error;
+
+/// Environment variable pointing directly to a logs database file.
+pub const DATABASE_ENV_VARIABLE: &str = "WORKLOG_DB";
+
+/// Environment variable holding the name of the profile to use.
+pub const PROFILE_ENV_VARIABLE: &str = "WORKLOG_PROFILE";
+
+/// Directory under the HOME directory of the current user where
+/// to store the logs database.
+const SUB_HOME_DIRECTORY: &str = ".worklog";
+
+/// Directory under the worklog directory where to store the profile databases.
+const PROFILES_DIRECTORY: &str = "profiles";
+
+/// File name of the default logs database.
+const FILE_NAME: &str = "logs.db";
+
+/// File extension of profile databases.
+const FILE_EXTENSION: &str = "db";
+
+/// Special database path denoting an in-memory database.
+const MEMORY_PATH: &str = ":memory:";
+
+thread_local! {
+    /// Database location explicitly selected (for example by a command line option).
+    static SELECTED_LOCATION: RefCell<Option<DatabaseLocation>> = const { RefCell::new(None) };
+}
+
+/// Location of the logs database to use.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseLocation {
+    /// The default database of the current user under `~/.worklog/logs.db`.
+    Default,
+    /// Database of a named profile under `~/.worklog/profiles/<name>.db`.
+    Profile(String),
+    /// Database at an arbitrary file path.
+    Path(path::PathBuf),
+    /// Volatile in-memory database shared by all operations of the current thread.
+    /// Nothing is written to disk.
+    Memory,
+}
+
+impl DatabaseLocation {
+    /// Get the database location for the given path.
+    /// The special path ":memory:" denotes an in-memory database.
+    pub fn from_path(path: &str) -> DatabaseLocation {
+        if path == MEMORY_PATH {
+            DatabaseLocation::Memory
+        } else {
+            DatabaseLocation::Path(path::PathBuf::from(path))
+        }
+    }
+
+    /// Determine the path to the logs database file.
+    pub fn path(&self) -> Result<path::PathBuf, Error> {
+        match self {
+            DatabaseLocation::Default => Ok(worklog_directory()?.join(FILE_NAME)),
+            DatabaseLocation::Profile(name) => {
+                if !is_valid_profile_name(name) {
+                    return Err(Error::Location(format!(
+                        "Invalid profile name '{}'. Use only letters, digits, '-' and '_'",
+                        name
+                    )));
+                }
+
+                Ok(worklog_directory()?
+                    .join(PROFILES_DIRECTORY)
+                    .join(name)
+                    .with_extension(FILE_EXTENSION))
+            }
+            DatabaseLocation::Path(path) => Ok(path.clone()),
+            DatabaseLocation::Memory => Err(Error::Location(String::from(
+                "An in-memory database is not located in the file system",
+            ))),
+        }
+    }
+}
+
+/// Select the database location to use from now on (in the current thread).
+/// Overrides the locations specified by environment variables.
+pub fn select_location(location: DatabaseLocation) {
+    SELECTED_LOCATION.with(|selected| *selected.borrow_mut() = Some(location));
+}
+
+/// Get the currently active database location.
+/// An explicitly selected location takes precedence over the
+/// `WORKLOG_DB` environment variable, which in turn takes precedence
+/// over the `WORKLOG_PROFILE` environment variable.
+/// When nothing is specified the default location is used.
+pub fn active_location() -> DatabaseLocation {
+    if let Some(location) = SELECTED_LOCATION.with(|selected| selected.borrow().clone()) {
+        return location;
+    }
+
+    if let Ok(path) = env::var(DATABASE_ENV_VARIABLE) {
+        if !path.is_empty() {
+            return DatabaseLocation::from_path(&path);
+        }
+    }
+
+    match env::var(PROFILE_ENV_VARIABLE) {
+        Ok(name) if !name.is_empty() => DatabaseLocation::Profile(name),
+        _ => DatabaseLocation::Default,
+    }
+}
+
+/// Get the worklog directory under the current users HOME directory.
+fn worklog_directory() -> Result<path::PathBuf, Error> {
+    match home::home_dir() {
+        Some(path) => Ok(path.join(SUB_HOME_DIRECTORY)),
+        None => Err(Error::Location(String::from(
+            "Could not determine the current users HOME directory",
+        ))),
+    }
+}
+
+/// Check whether the given profile name is safe to use as a file name.
+fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_path() {
+        let path = DatabaseLocation::Profile(String::from("acme"))
+            .path()
+            .unwrap();
+
+        assert!(path.ends_with("profiles/acme.db"));
+    }
+
+    #[test]
+    fn test_invalid_profile_name() {
+        assert!(DatabaseLocation::Profile(String::from("../acme"))
+            .path()
+            .is_err());
+        assert!(DatabaseLocation::Profile(String::new()).path().is_err());
+    }
+
+    #[test]
+    fn test_memory_path() {
+        assert_eq!(
+            DatabaseLocation::from_path(":memory:"),
+            DatabaseLocation::Memory
+        );
+        assert_eq!(
+            DatabaseLocation::from_path("logs.db"),
+            DatabaseLocation::Path(path::PathBuf::from("logs.db"))
+        );
+    }
+
+    #[test]
+    fn test_selected_location_takes_precedence() {
+        let location = DatabaseLocation::Path(path::PathBuf::from("/tmp/test.db"));
+        select_location(location.clone());
+
+        assert_eq!(active_location(), location);
+    }
+}
@@ -1,6 +1,10 @@
 mod data_access;
 mod data_access_factory;
+mod location;
 mod sqlite;
 
 pub use data_access::DataAccess;
-pub use data_access_factory::get_data_access;
+pub use data_access_factory::{get_data_access, get_data_access_at};
+pub use location::{active_location, select_location, DatabaseLocation};
+pub use location::{DATABASE_ENV_VARIABLE, PROFILE_ENV_VARIABLE};
+pub use sqlite::SQLiteDataAccess;
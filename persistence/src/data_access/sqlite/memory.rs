@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rusqlite::{Connection, OpenFlags};
+
+use crate::Error;
+
+/// Counter used to give every shared in-memory database a unique name.
+static MEMORY_DATABASE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Shared in-memory database of the current thread.
+    static MEMORY_DATABASE: RefCell<Option<MemoryDatabase>> = const { RefCell::new(None) };
+}
+
+/// In-memory database that may be opened by multiple connections.
+/// The database lives as long as at least one connection to it is open,
+/// which is why a connection is kept around for the lifetime of the database.
+struct MemoryDatabase {
+    /// URI of the shared in-memory database.
+    uri: String,
+    /// Connection keeping the in-memory database alive.
+    _keep_alive: Connection,
+}
+
+impl MemoryDatabase {
+    /// Create a new shared in-memory database.
+    fn new() -> Result<MemoryDatabase, Error> {
+        let uri = format!(
+            "file:worklog-memory-{}-{}?mode=memory&cache=shared",
+            std::process::id(),
+            MEMORY_DATABASE_COUNTER.fetch_add(1, Ordering::SeqCst)
+        );
+        let keep_alive = open_uri(&uri)?;
+
+        Ok(MemoryDatabase {
+            uri,
+            _keep_alive: keep_alive,
+        })
+    }
+}
+
+/// Open a new connection to the shared in-memory database of the current thread.
+/// The database is created on first use and lives as long as the thread.
+pub(crate) fn open_shared_memory_connection() -> Result<Connection, Error> {
+    MEMORY_DATABASE.with(|database| {
+        let mut database = database.borrow_mut();
+        if database.is_none() {
+            *database = Some(MemoryDatabase::new()?);
+        }
+
+        open_uri(&database.as_ref().unwrap().uri)
+    })
+}
+
+/// Open a connection to the database with the given URI.
+fn open_uri(uri: &str) -> Result<Connection, Error> {
+    Ok(Connection::open_with_flags(
+        uri,
+        OpenFlags::SQLITE_OPEN_READ_WRITE
+            | OpenFlags::SQLITE_OPEN_CREATE
+            | OpenFlags::SQLITE_OPEN_URI
+            | OpenFlags::SQLITE_OPEN_SHARED_CACHE,
+    )?)
+}
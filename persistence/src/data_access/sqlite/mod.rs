@@ -1,4 +1,7 @@
+mod memory;
 mod patch;
+mod query;
+mod search;
 mod sqlite_data_access;
 
 pub use sqlite_data_access::SQLiteDataAccess;
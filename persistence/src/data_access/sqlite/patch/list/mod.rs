@@ -1,3 +1,19 @@
 mod patch1;
+mod patch2;
+mod patch3;
+mod patch4;
+mod patch5;
+mod patch6;
+mod patch7;
+mod patch8;
+mod patch9;
 
 pub use patch1::Patch1;
+pub use patch2::Patch2;
+pub use patch3::Patch3;
+pub use patch4::Patch4;
+pub use patch5::Patch5;
+pub use patch6::Patch6;
+pub use patch7::Patch7;
+pub use patch8::Patch8;
+pub use patch9::Patch9;
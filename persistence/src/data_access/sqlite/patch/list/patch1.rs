@@ -1,6 +1,6 @@
 use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
 use rusqlite::{Connection, NO_PARAMS};
-use std::error::Error;
 
 /// Patch for adding the basic tables logs, log_tags, log_events.
 pub struct Patch1;
@@ -10,7 +10,7 @@ impl Patch for Patch1 {
         1
     }
 
-    fn patch(&self, con: &mut Connection) -> Result<(), Box<dyn Error>> {
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
         let transaction = con.transaction()?;
 
         // Add logs table
@@ -0,0 +1,46 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::Connection;
+
+/// Patch adding the logs_fts full-text search table indexing the work item descriptions.
+/// The table is kept in sync with the logs table using triggers.
+pub struct Patch2;
+
+impl Patch for Patch2 {
+    fn version(&self) -> i32 {
+        2
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute_batch(
+            "CREATE VIRTUAL TABLE logs_fts USING fts5(\
+                description, \
+                content='logs', \
+                content_rowid='id'\
+            );
+
+            CREATE TRIGGER logs_fts_insert AFTER INSERT ON logs BEGIN \
+                INSERT INTO logs_fts (rowid, description) VALUES (new.id, new.description); \
+            END;
+
+            CREATE TRIGGER logs_fts_delete AFTER DELETE ON logs BEGIN \
+                INSERT INTO logs_fts (logs_fts, rowid, description) \
+                    VALUES ('delete', old.id, old.description); \
+            END;
+
+            CREATE TRIGGER logs_fts_update AFTER UPDATE ON logs BEGIN \
+                INSERT INTO logs_fts (logs_fts, rowid, description) \
+                    VALUES ('delete', old.id, old.description); \
+                INSERT INTO logs_fts (rowid, description) VALUES (new.id, new.description); \
+            END;
+
+            INSERT INTO logs_fts (logs_fts) VALUES ('rebuild');",
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
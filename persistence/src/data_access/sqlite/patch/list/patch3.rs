@@ -0,0 +1,32 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::Connection;
+
+/// Patch adding the projects table and an optional project reference to the logs table.
+pub struct Patch3;
+
+impl Patch for Patch3 {
+    fn version(&self) -> i32 {
+        3
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute_batch(
+            "CREATE TABLE projects (\
+                id INTEGER PRIMARY KEY, \
+                name TEXT NOT NULL UNIQUE, \
+                client TEXT, \
+                hourly_rate REAL, \
+                archived INTEGER NOT NULL DEFAULT 0\
+            );
+
+            ALTER TABLE logs ADD COLUMN project_id INTEGER REFERENCES projects(id);",
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::{Connection, NO_PARAMS};
+
+/// Patch adding the log_notes table holding the timestamped notes of the work items.
+pub struct Patch4;
+
+impl Patch for Patch4 {
+    fn version(&self) -> i32 {
+        4
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute(
+            "CREATE TABLE log_notes (\
+            id INTEGER PRIMARY KEY, \
+            log_id INTEGER NOT NULL, \
+            timestamp INTEGER NOT NULL, \
+            text TEXT NOT NULL, \
+            FOREIGN KEY (log_id) REFERENCES logs(id)\
+            )",
+            NO_PARAMS,
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::Connection;
+
+/// Patch adding the deleted_at column to the logs table.
+/// Work items with a deletion timestamp are in the trash.
+pub struct Patch5;
+
+impl Patch for Patch5 {
+    fn version(&self) -> i32 {
+        5
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute_batch(
+            "ALTER TABLE logs ADD COLUMN deleted_at INTEGER;
+
+            CREATE INDEX logs_deleted_at ON logs (deleted_at);",
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::Connection;
+
+/// Patch adding the journal tables recording the states of work items before and after
+/// every change so that changes can be undone and redone.
+pub struct Patch6;
+
+impl Patch for Patch6 {
+    fn version(&self) -> i32 {
+        6
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute_batch(
+            "CREATE TABLE journal (\
+                id INTEGER PRIMARY KEY, \
+                timestamp INTEGER NOT NULL, \
+                undone INTEGER NOT NULL DEFAULT 0\
+            );
+
+            CREATE TABLE journal_changes (\
+                id INTEGER PRIMARY KEY, \
+                journal_id INTEGER NOT NULL, \
+                log_id INTEGER NOT NULL, \
+                before_state TEXT, \
+                after_state TEXT, \
+                FOREIGN KEY (journal_id) REFERENCES journal(id)\
+            );",
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::Connection;
+
+/// Patch adding the append-only revision history recording every change of the
+/// description, tags, status and events of work items.
+/// Revisions are kept even when their work item is purged from the trash.
+pub struct Patch7;
+
+impl Patch for Patch7 {
+    fn version(&self) -> i32 {
+        7
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute_batch(
+            "CREATE TABLE log_revisions (\
+                id INTEGER PRIMARY KEY, \
+                log_id INTEGER NOT NULL, \
+                timestamp INTEGER NOT NULL, \
+                origin TEXT NOT NULL, \
+                field TEXT NOT NULL, \
+                old_value TEXT, \
+                new_value TEXT\
+            );
+
+            CREATE INDEX log_revisions_log_id ON log_revisions (log_id);",
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::Connection;
+
+/// Patch adding the restored column to the journal_changes table.
+/// Marks changes restoring a work item from the trash, which move it back to the trash when undone.
+pub struct Patch8;
+
+impl Patch for Patch8 {
+    fn version(&self) -> i32 {
+        8
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute_batch(
+            "ALTER TABLE journal_changes ADD COLUMN restored INTEGER NOT NULL DEFAULT 0;",
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
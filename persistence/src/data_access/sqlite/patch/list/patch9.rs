@@ -0,0 +1,46 @@
+use crate::data_access::sqlite::patch::patch::Patch;
+use crate::Error;
+use rusqlite::Connection;
+
+/// Patch adding the log_notes_fts full-text search table indexing the notes of work items.
+/// The table is kept in sync with the log_notes table using triggers.
+pub struct Patch9;
+
+impl Patch for Patch9 {
+    fn version(&self) -> i32 {
+        9
+    }
+
+    fn patch(&self, con: &mut Connection) -> Result<(), Error> {
+        let transaction = con.transaction()?;
+
+        transaction.execute_batch(
+            "CREATE VIRTUAL TABLE log_notes_fts USING fts5(\
+                text, \
+                content='log_notes', \
+                content_rowid='id'\
+            );
+
+            CREATE TRIGGER log_notes_fts_insert AFTER INSERT ON log_notes BEGIN \
+                INSERT INTO log_notes_fts (rowid, text) VALUES (new.id, new.text); \
+            END;
+
+            CREATE TRIGGER log_notes_fts_delete AFTER DELETE ON log_notes BEGIN \
+                INSERT INTO log_notes_fts (log_notes_fts, rowid, text) \
+                    VALUES ('delete', old.id, old.text); \
+            END;
+
+            CREATE TRIGGER log_notes_fts_update AFTER UPDATE ON log_notes BEGIN \
+                INSERT INTO log_notes_fts (log_notes_fts, rowid, text) \
+                    VALUES ('delete', old.id, old.text); \
+                INSERT INTO log_notes_fts (rowid, text) VALUES (new.id, new.text); \
+            END;
+
+            INSERT INTO log_notes_fts (log_notes_fts) VALUES ('rebuild');",
+        )?;
+
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
+use crate::Error;
 use rusqlite::Connection;
-use std::error::Error;
 
 /// Patch that will patch the database to another version.
 pub trait Patch {
@@ -7,5 +7,5 @@ pub trait Patch {
     fn version(&self) -> i32;
 
     /// Try to patch the database to the patches version.
-    fn patch(&self, con: &mut Connection) -> Result<(), Box<dyn Error>>;
+    fn patch(&self, con: &mut Connection) -> Result<(), Error>;
 }
@@ -1,6 +1,6 @@
 use super::PATCHES;
+use crate::Error;
 use rusqlite::{params, Connection};
-use std::error::Error;
 
 /// Patcher patching a database.
 pub struct Patcher<'a> {
@@ -15,7 +15,7 @@ impl Patcher<'_> {
     }
 
     /// Patch the database.
-    pub fn patch(&mut self, from_version: i32, to_version: i32) -> Result<(), Box<dyn Error>> {
+    pub fn patch(&mut self, from_version: i32, to_version: i32) -> Result<(), Error> {
         for patch in &PATCHES {
             let patch_needed = from_version < to_version
                 && patch.version() > from_version
@@ -31,7 +31,7 @@ impl Patcher<'_> {
     }
 
     /// Change the version of the database.
-    fn change_version(&self, version: i32) -> Result<(), Box<dyn Error>> {
+    fn change_version(&self, version: i32) -> Result<(), Error> {
         self.connection
             .execute("UPDATE info SET version = ?1", params![version])?;
 
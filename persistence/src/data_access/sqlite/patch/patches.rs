@@ -1,5 +1,17 @@
-use crate::data_access::sqlite::patch::list::Patch1;
+use crate::data_access::sqlite::patch::list::{
+    Patch1, Patch2, Patch3, Patch4, Patch5, Patch6, Patch7, Patch8, Patch9,
+};
 use crate::data_access::sqlite::patch::patch::Patch;
 
 /// List of available database patches.
-pub(crate) const LIST: [&dyn Patch; 1] = [&Patch1 {}];
+pub(crate) const LIST: [&dyn Patch; 9] = [
+    &Patch1 {},
+    &Patch2 {},
+    &Patch3 {},
+    &Patch4 {},
+    &Patch5 {},
+    &Patch6 {},
+    &Patch7 {},
+    &Patch8 {},
+    &Patch9 {},
+];
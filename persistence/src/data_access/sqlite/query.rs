@@ -0,0 +1,176 @@
+use rusqlite::types::Value;
+
+use crate::query::{ItemQuery, SortOrder};
+
+/// Item query compiled to SQL.
+pub(crate) struct CompiledQuery {
+    /// SQL selecting the ID, description, status and project name of all matching
+    /// work items from the logs table in the requested order.
+    pub sql: String,
+    /// Parameters to bind to the SQL.
+    pub params: Vec<Value>,
+}
+
+/// Compile the given item query to a single parameterised SQL query.
+pub(crate) fn compile(query: &ItemQuery) -> CompiledQuery {
+    // Work items in the trash are never matched
+    let mut conditions: Vec<String> = vec![String::from("logs.deleted_at IS NULL")];
+    let mut params: Vec<Value> = Vec::new();
+
+    if query.from_timestamp().is_some() || query.to_timestamp().is_some() {
+        conditions.push(activity_condition(
+            query.from_timestamp(),
+            query.to_timestamp(),
+            &mut params,
+        ));
+    }
+
+    if !query.statuses().is_empty() {
+        let mut placeholders = Vec::new();
+        for status in query.statuses() {
+            params.push(Value::Text(format!("{}", status)));
+            placeholders.push(format!("?{}", params.len()));
+        }
+
+        conditions.push(format!("logs.status IN ({})", placeholders.join(", ")));
+    }
+
+    for tag in query.required_tags() {
+        params.push(Value::Text(tag.to_owned()));
+        conditions.push(format!(
+            "logs.id IN (SELECT log_id FROM log_tags WHERE tag = ?{})",
+            params.len()
+        ));
+    }
+
+    for tag in query.excluded_tags() {
+        params.push(Value::Text(tag.to_owned()));
+        conditions.push(format!(
+            "logs.id NOT IN (SELECT log_id FROM log_tags WHERE tag = ?{})",
+            params.len()
+        ));
+    }
+
+    if let Some(project) = query.project() {
+        params.push(Value::Text(project.to_owned()));
+        conditions.push(format!("projects.name = ?{}", params.len()));
+    }
+
+    if let Some(description) = query.description() {
+        params.push(Value::Text(format!("%{}%", escape_like(description))));
+        conditions.push(format!(
+            "logs.description LIKE ?{} ESCAPE '\\'",
+            params.len()
+        ));
+    }
+
+    let mut sql = String::from(
+        "SELECT logs.id, logs.description, logs.status, projects.name \
+        FROM logs \
+        LEFT JOIN projects ON projects.id = logs.project_id \
+        LEFT JOIN log_events AS started \
+            ON started.log_id = logs.id AND started.event = 'STARTED'",
+    );
+
+    sql.push_str(" WHERE ");
+    sql.push_str(&conditions.join(" AND "));
+
+    sql.push_str(match query.sort_order() {
+        SortOrder::OldestFirst => " ORDER BY started.timestamp ASC, logs.id ASC",
+        SortOrder::NewestFirst => " ORDER BY started.timestamp DESC, logs.id DESC",
+    });
+
+    if query.max_items().is_some() || query.skipped_items().is_some() {
+        // A negative limit means no limit in SQLite
+        params.push(Value::Integer(query.max_items().map_or(-1, |v| v as i64)));
+        sql.push_str(&format!(" LIMIT ?{}", params.len()));
+
+        params.push(Value::Integer(query.skipped_items().unwrap_or(0) as i64));
+        sql.push_str(&format!(" OFFSET ?{}", params.len()));
+    }
+
+    CompiledQuery { sql, params }
+}
+
+/// Build the condition matching work items with any active interval overlapping the given time range.
+/// An active interval starts with a STARTED or CONTINUED event and ends with the next
+/// PAUSED or FINISHED event. Intervals without an end are still in progress.
+fn activity_condition(
+    from_timestamp: Option<i64>,
+    to_timestamp: Option<i64>,
+    params: &mut Vec<Value>,
+) -> String {
+    let mut interval_conditions = vec![
+        String::from("interval_start.log_id = logs.id"),
+        String::from("interval_start.event IN ('STARTED', 'CONTINUED')"),
+    ];
+
+    if let Some(to_timestamp) = to_timestamp {
+        params.push(Value::Integer(to_timestamp));
+        interval_conditions.push(format!("interval_start.timestamp < ?{}", params.len()));
+    }
+
+    if let Some(from_timestamp) = from_timestamp {
+        params.push(Value::Integer(from_timestamp));
+        interval_conditions.push(format!(
+            "IFNULL(\
+                (SELECT MIN(interval_end.timestamp) \
+                FROM log_events AS interval_end \
+                WHERE interval_end.log_id = logs.id \
+                    AND interval_end.event IN ('PAUSED', 'FINISHED') \
+                    AND interval_end.timestamp >= interval_start.timestamp), \
+                {}\
+            ) > ?{}",
+            i64::MAX,
+            params.len()
+        ));
+    }
+
+    format!(
+        "EXISTS (SELECT 1 FROM log_events AS interval_start WHERE {})",
+        interval_conditions.join(" AND ")
+    )
+}
+
+/// Escape the wildcard characters of a LIKE pattern.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::Status;
+
+    #[test]
+    fn test_compile_empty_query() {
+        let compiled = compile(&ItemQuery::new());
+
+        assert!(compiled
+            .sql
+            .contains(" WHERE logs.deleted_at IS NULL ORDER BY"));
+        assert!(compiled.params.is_empty());
+    }
+
+    #[test]
+    fn test_compile_complex_query() {
+        let compiled = compile(
+            &ItemQuery::new()
+                .time_range(10, 20)
+                .with_status(Status::Done)
+                .with_status(Status::Paused)
+                .with_tag("a")
+                .without_tag("b")
+                .description_contains("50%")
+                .limit(5),
+        );
+
+        assert!(compiled.sql.contains("logs.status IN (?3, ?4)"));
+        assert!(compiled.sql.contains("LIMIT ?8 OFFSET ?9"));
+        assert_eq!(compiled.params.len(), 9);
+        assert_eq!(compiled.params[6], Value::Text(String::from("%50\\%%")));
+    }
+}
@@ -0,0 +1,35 @@
+/// Convert the given search text entered by a user to a FTS5 match expression.
+/// Every whitespace separated term is quoted so that characters with a special
+/// meaning in the FTS5 query syntax are matched literally.
+/// All terms must be present, the last one may be the prefix of a word.
+/// Returns None if the text does not contain any term.
+pub(crate) fn to_match_expression(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+
+    if terms.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}*", terms.join(" ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_match_expression() {
+        assert_eq!(
+            to_match_expression("fix \"login\" bug"),
+            Some(String::from("\"fix\" \"\"\"login\"\"\" \"bug\"*"))
+        );
+        assert_eq!(
+            to_match_expression("api-v2"),
+            Some(String::from("\"api-v2\"*"))
+        );
+        assert_eq!(to_match_expression("  "), None);
+    }
+}
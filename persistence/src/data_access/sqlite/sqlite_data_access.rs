@@ -1,39 +1,42 @@
 use std::collections::{HashMap, HashSet};
-use std::error::Error;
 use std::str::FromStr;
 use std::{fs, path};
 
-use rusqlite::{params, Connection, Rows, Transaction, NO_PARAMS};
+use rusqlite::{params, Connection, Rows, ToSql, NO_PARAMS};
 
 use crate::calc::event::{Event, EventType};
-use crate::calc::{Status, WorkItem};
+use crate::calc::{Note, Project, Status, TrashedItem, WorkItem};
+use crate::data_access::sqlite::memory;
 use crate::data_access::sqlite::patch::Patcher;
+use crate::data_access::sqlite::query;
+use crate::data_access::sqlite::search;
 use crate::data_access::DataAccess;
+use crate::journal::{ChangeKind, JournalChange, JournalEntry};
+use crate::query::{ItemQuery, SearchResult, HIGHLIGHT_END, HIGHLIGHT_START};
+use crate::revision::{self, Origin, Revision, RevisionField, PURGED, TRASHED};
+use crate::Error;
 use std::collections::hash_map::Entry;
 
 /// Latest database version to patch to.
-const LATEST_VERSION: i32 = 1;
+const LATEST_VERSION: i32 = 9;
 
-/// Directory under the HOME directory of the current user where
-/// to store the logs database.
-const SUB_HOME_DIRECTORY: &str = ".worklog";
+/// SQL selecting the ID, description, status and project name of work items from the logs table.
+const SELECT_ITEMS_SQL: &str = "SELECT logs.id, logs.description, logs.status, projects.name \
+    FROM logs \
+    LEFT JOIN projects ON projects.id = logs.project_id";
 
-/// File name of the logs database.
-const FILE_NAME: &str = "logs.db";
+/// Name of the savepoints used for explicit (possibly nested) transactions.
+const TRANSACTION_SAVEPOINT: &str = "explicit_transaction";
+
+/// SQL selecting all columns of the projects table.
+const SELECT_PROJECTS_SQL: &str = "SELECT id, name, client, hourly_rate, archived FROM projects";
 
 /// Data access using SQLite.
 pub struct SQLiteDataAccess {
     connection: Connection,
-}
-
-/// Determine the path to the logs database.
-fn determine_database_path() -> Result<path::PathBuf, &'static str> {
-    Ok(match home::home_dir() {
-        Some(path) => Ok(path),
-        None => Err("Could not determine the current users HOME directory"),
-    }?
-    .join(SUB_HOME_DIRECTORY)
-    .join(FILE_NAME))
+    /// IDs of the journal entries recording all changes of the nested explicit transactions
+    /// in progress (empty outside of explicit transactions and `None` until the first change).
+    transaction_journal_ids: Vec<Option<i32>>,
 }
 
 /// Work item to be filled with more data.
@@ -41,18 +44,46 @@ struct TmpWorkItem {
     id: i32,
     description: String,
     status: Status,
+    project: Option<String>,
 }
 
-impl SQLiteDataAccess {
-    /// Create a new SQLite data access.
-    pub fn new() -> Result<SQLiteDataAccess, Box<dyn Error>> {
-        let db_path = determine_database_path()?;
+/// Change of a work item to record in the journal with its states serialized as JSON.
+struct TmpJournalChange {
+    id: i32,
+    before: Option<String>,
+    after: Option<String>,
+    /// Whether the work item has been restored from the trash.
+    restored: bool,
+}
 
+impl SQLiteDataAccess {
+    /// Create a new SQLite data access working on the database file at the given path.
+    pub fn new(db_path: &path::Path) -> Result<SQLiteDataAccess, Error> {
         // Create directories if they do not exist
-        fs::create_dir_all(&db_path.parent().unwrap())?;
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
 
+        SQLiteDataAccess::from_connection(Connection::open(db_path)?)
+    }
+
+    /// Create a new SQLite data access working on a private in-memory database.
+    /// The database is gone as soon as the data access is dropped.
+    pub fn in_memory() -> Result<SQLiteDataAccess, Error> {
+        SQLiteDataAccess::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Create a new SQLite data access working on the in-memory database
+    /// shared by all data accesses of the current thread.
+    pub(crate) fn shared_in_memory() -> Result<SQLiteDataAccess, Error> {
+        SQLiteDataAccess::from_connection(memory::open_shared_memory_connection()?)
+    }
+
+    /// Create a new SQLite data access using the given connection.
+    fn from_connection(connection: Connection) -> Result<SQLiteDataAccess, Error> {
         let mut data_access = SQLiteDataAccess {
-            connection: Connection::open(&db_path)?,
+            connection,
+            transaction_journal_ids: Vec::new(),
         };
 
         data_access.prepare_database()?;
@@ -61,7 +92,7 @@ impl SQLiteDataAccess {
     }
 
     /// Prepare the database for usage.
-    fn prepare_database(&mut self) -> Result<(), Box<dyn Error>> {
+    fn prepare_database(&mut self) -> Result<(), Error> {
         // Check info table for the database version
         let version = match self.check_version() {
             Ok(version) => version,
@@ -74,7 +105,12 @@ impl SQLiteDataAccess {
         }
 
         let version = self.check_version()?;
-        if version < LATEST_VERSION {
+        if version > LATEST_VERSION {
+            return Err(Error::SchemaTooNew {
+                version,
+                latest_version: LATEST_VERSION,
+            });
+        } else if version < LATEST_VERSION {
             let mut patcher = Patcher::new(&mut self.connection);
             patcher.patch(version, LATEST_VERSION)?;
         }
@@ -83,7 +119,7 @@ impl SQLiteDataAccess {
     }
 
     /// Check the current database version.
-    fn check_version(&self) -> Result<i32, Box<dyn Error>> {
+    fn check_version(&self) -> Result<i32, Error> {
         let version: i32 =
             self.connection
                 .query_row("SELECT version FROM info", NO_PARAMS, |row| row.get(0))?;
@@ -92,7 +128,7 @@ impl SQLiteDataAccess {
     }
 
     /// Initialize the database with a info table.
-    fn initialize_database(&self) -> Result<(), Box<dyn Error>> {
+    fn initialize_database(&self) -> Result<(), Error> {
         self.connection.execute(
             "CREATE TABLE info (
                 version INTEGER PRIMARY KEY
@@ -105,16 +141,26 @@ impl SQLiteDataAccess {
 
         Ok(())
     }
+
+    /// Remember the given journal entry to record all further changes of the
+    /// explicit transaction in progress in (if any).
+    fn remember_journal_id(&mut self, journal_id: i32) {
+        if let Some(transaction_journal_id) = self.transaction_journal_ids.last_mut() {
+            *transaction_journal_id = Some(journal_id);
+        }
+    }
 }
 
 impl DataAccess for SQLiteDataAccess {
-    fn log_item(&mut self, item: WorkItem) -> Result<i32, Box<dyn Error>> {
-        let transaction = self.connection.transaction()?;
+    fn log_item(&mut self, item: WorkItem) -> Result<i32, Error> {
+        let transaction = self.connection.savepoint()?;
+
+        let project_id = project_id_of(&transaction, item.project())?;
 
         // Insert work item information to logs table
         transaction.execute(
-            "INSERT INTO logs (description, status) VALUES (?1, ?2)",
-            params![item.description(), format!("{}", item.status())],
+            "INSERT INTO logs (description, status, project_id) VALUES (?1, ?2, ?3)",
+            params![item.description(), format!("{}", item.status()), project_id],
         )?;
 
         // Check ID of the new log calc
@@ -127,24 +173,107 @@ impl DataAccess for SQLiteDataAccess {
         // Insert events in the log_events table
         insert_events(&transaction, id, item.events())?;
 
+        // Insert notes in the log_notes table
+        insert_notes(&transaction, id, item.notes())?;
+
+        // Record the new work item in the revision history and the journal
+        let after = stored_item(&transaction, id)?;
+        record_revisions(&transaction, id, None, after.as_ref())?;
+
+        let journal_id = record_changes(
+            &transaction,
+            self.transaction_journal_ids.last().copied().flatten(),
+            &[TmpJournalChange {
+                id,
+                before: None,
+                after: after.as_ref().map(to_journal_state),
+                restored: false,
+            }],
+        )?;
+
+        transaction.commit()?;
+        self.remember_journal_id(journal_id);
+
+        Ok(id)
+    }
+
+    fn restore_item(&mut self, item: WorkItem) -> Result<i32, Error> {
+        let id = match item.id() {
+            Some(id) => id,
+            None => return self.log_item(item),
+        };
+
+        let transaction = self.connection.savepoint()?;
+
+        let exists: bool = transaction.query_row(
+            "SELECT EXISTS (SELECT 1 FROM logs WHERE id = ?1)",
+            params![id],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Err(Error::AlreadyExists(id));
+        }
+
+        let project_id = project_id_of(&transaction, item.project())?;
+
+        // Insert work item information to logs table keeping the ID
+        transaction.execute(
+            "INSERT INTO logs (id, description, status, project_id) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                id,
+                item.description(),
+                format!("{}", item.status()),
+                project_id
+            ],
+        )?;
+
+        insert_tags(&transaction, id, &item.tags())?;
+        insert_events(&transaction, id, item.events())?;
+        insert_notes(&transaction, id, item.notes())?;
+
+        // Record the restored work item in the revision history and the journal
+        let after = stored_item(&transaction, id)?;
+        record_revisions(&transaction, id, None, after.as_ref())?;
+
+        let journal_id = record_changes(
+            &transaction,
+            self.transaction_journal_ids.last().copied().flatten(),
+            &[TmpJournalChange {
+                id,
+                before: None,
+                after: after.as_ref().map(to_journal_state),
+                restored: false,
+            }],
+        )?;
+
         transaction.commit()?;
+        self.remember_journal_id(journal_id);
 
         Ok(id)
     }
 
-    fn update_items(&mut self, items: Vec<&WorkItem>) -> Result<(), Box<dyn Error>> {
-        let transaction = self.connection.transaction()?;
+    fn update_items(&mut self, items: Vec<&WorkItem>) -> Result<(), Error> {
+        let transaction = self.connection.savepoint()?;
 
+        let mut changes = Vec::new();
         for item in items {
             let id = item.id().expect("ID must be present at this point!");
+            let project_id = project_id_of(&transaction, item.project())?;
+            let before = stored_item(&transaction, id)?;
 
             // Update in logs table
             transaction.execute(
                 "UPDATE logs \
         SET description = ?2, \
-        status = ?3 \
+        status = ?3, \
+        project_id = ?4 \
         WHERE id = ?1",
-                params![id, item.description(), format!("{}", item.status())],
+                params![
+                    id,
+                    item.description(),
+                    format!("{}", item.status()),
+                    project_id
+                ],
             )?;
 
             // Delete all tags for the work item in the log_tags table
@@ -158,101 +287,201 @@ impl DataAccess for SQLiteDataAccess {
 
             // Enter new events in the log_events table
             insert_events(&transaction, id, item.events())?;
+
+            // Replace the notes for the work item in the log_notes table
+            delete_notes(&transaction, id)?;
+            insert_notes(&transaction, id, item.notes())?;
+
+            // Only record actual changes in the revision history and the journal
+            let after = stored_item(&transaction, id)?;
+            record_revisions(&transaction, id, before.as_ref(), after.as_ref())?;
+
+            let before = before.as_ref().map(to_journal_state);
+            let after = after.as_ref().map(to_journal_state);
+            if before != after {
+                changes.push(TmpJournalChange {
+                    id,
+                    before,
+                    after,
+                    restored: false,
+                });
+            }
         }
 
+        let journal_id = match changes.is_empty() {
+            true => None,
+            false => Some(record_changes(
+                &transaction,
+                self.transaction_journal_ids.last().copied().flatten(),
+                &changes,
+            )?),
+        };
+
         transaction.commit()?;
+        if let Some(journal_id) = journal_id {
+            self.remember_journal_id(journal_id);
+        }
 
         Ok(())
     }
 
-    fn list_items(&self) -> Result<Vec<WorkItem>, Box<dyn Error>> {
-        // Fetch all tmp work items from the logs table
-        let item_lookup = tmp_item_lookup_from_rows(
+    fn query_items(&self, query: &ItemQuery) -> Result<Vec<WorkItem>, Error> {
+        let compiled = query::compile(query);
+
+        // Fetch all matching tmp work items from the logs table
+        let tmp_items = tmp_items_from_rows(
             self.connection
-                .prepare("SELECT id, description, status FROM logs")?
-                .query(NO_PARAMS)?,
+                .prepare(&compiled.sql)?
+                .query(&compiled.params)?,
         )?;
 
         // Fetch and cache tags for later lookup
         let tags_lookup: HashMap<i32, HashSet<String>> = tags_lookup_from_rows(
             self.connection
-                .prepare("SELECT log_id, tag FROM log_tags")?
-                .query(NO_PARAMS)?,
+                .prepare(&format!(
+                    "SELECT log_id, tag FROM log_tags WHERE log_id IN (SELECT id FROM ({}))",
+                    compiled.sql
+                ))?
+                .query(&compiled.params)?,
         )?;
 
         // Fetch and cache events for later lookup
         let events_lookup = events_lookup_from_rows(
             self.connection
-                .prepare("SELECT log_id, timestamp, event FROM log_events")?
-                .query(NO_PARAMS)?,
+                .prepare(&format!(
+                    "SELECT log_id, timestamp, event FROM log_events WHERE log_id IN (SELECT id FROM ({}))",
+                    compiled.sql
+                ))?
+                .query(&compiled.params)?,
+        )?;
+
+        // Fetch and cache notes for later lookup
+        let notes_lookup = notes_lookup_from_rows(
+            self.connection
+                .prepare(&format!(
+                    "SELECT log_id, timestamp, text FROM log_notes WHERE log_id IN (SELECT id FROM ({})) ORDER BY timestamp, id",
+                    compiled.sql
+                ))?
+                .query(&compiled.params)?,
         )?;
 
         // Create work items from the cached data
-        Ok(create_work_items(item_lookup, tags_lookup, events_lookup))
+        create_work_items(tmp_items, tags_lookup, events_lookup, notes_lookup)
     }
 
-    fn filter_items(
-        &self,
-        from_timestamp: i64,
-        to_timestamp: i64,
-    ) -> Result<Vec<WorkItem>, Box<dyn Error>> {
-        // Fetch all tmp work items from the logs table
-        let item_lookup = tmp_item_lookup_from_rows(
+    fn search_items(&self, text: &str) -> Result<Vec<SearchResult>, Error> {
+        let expression = match search::to_match_expression(text) {
+            Some(expression) => expression,
+            None => return Ok(Vec::new()),
+        };
+
+        // Fetch the IDs of the work items with matching descriptions or notes ordered by relevance
+        // along with a snippet (only keeping the best match per work item)
+        let mut matches: Vec<(i32, String, f64, bool)> = Vec::new();
+        {
+            let mut statement = self.connection.prepare(
+                "SELECT log_id, snippet, rank, in_note FROM (\
+                    SELECT rowid AS log_id, \
+                        snippet(logs_fts, 0, ?2, ?3, '...', 16) AS snippet, \
+                        bm25(logs_fts) AS rank, \
+                        0 AS in_note \
+                    FROM logs_fts \
+                    WHERE logs_fts MATCH ?1 \
+                    UNION ALL \
+                    SELECT log_notes.log_id, \
+                        snippet(log_notes_fts, 0, ?2, ?3, '...', 16), \
+                        bm25(log_notes_fts), \
+                        1 \
+                    FROM log_notes_fts \
+                    INNER JOIN log_notes ON log_notes.id = log_notes_fts.rowid \
+                    WHERE log_notes_fts MATCH ?1\
+                ) \
+                ORDER BY rank, in_note, log_id",
+            )?;
+            let mut rows = statement.query(params![expression, HIGHLIGHT_START, HIGHLIGHT_END])?;
+            let mut seen_ids = HashSet::new();
+            while let Some(row) = rows.next()? {
+                let id: i32 = row.get(0)?;
+                if !seen_ids.insert(id) {
+                    continue;
+                }
+
+                // BM25 yields better matches as lower (negative) numbers
+                let rank: f64 = row.get(2)?;
+                matches.push((id, row.get(1)?, -rank, row.get(3)?));
+            }
+        }
+
+        let matching_ids_sql = "SELECT rowid FROM logs_fts WHERE logs_fts MATCH ?1 \
+            UNION \
+            SELECT log_notes.log_id FROM log_notes_fts \
+            INNER JOIN log_notes ON log_notes.id = log_notes_fts.rowid \
+            WHERE log_notes_fts MATCH ?1";
+
+        // Fetch all matching tmp work items from the logs table
+        let tmp_items = tmp_items_from_rows(
             self.connection
-                .prepare(
-                    "SELECT logs.id, logs.description, logs.status \
-            FROM logs, log_events \
-            WHERE logs.id = log_events.log_id \
-                AND log_events.event = 'STARTED' \
-                AND log_events.timestamp >= ?1 \
-                AND log_events.timestamp < ?2",
-                )?
-                .query(params![from_timestamp, to_timestamp])?,
+                .prepare(&format!(
+                    "{} WHERE logs.id IN ({}) AND logs.deleted_at IS NULL",
+                    SELECT_ITEMS_SQL, matching_ids_sql
+                ))?
+                .query(params![expression])?,
         )?;
 
         // Fetch and cache tags for later lookup
         let tags_lookup: HashMap<i32, HashSet<String>> = tags_lookup_from_rows(
             self.connection
-                .prepare(
-                    "SELECT log_id, tag \
-            FROM log_tags \
-            WHERE log_id IN (\
-                SELECT logs.id \
-                FROM logs, log_events \
-                WHERE logs.id = log_events.log_id \
-                    AND log_events.event = 'STARTED' \
-                    AND log_events.timestamp >= ?1 \
-                    AND log_events.timestamp < ?2\
-            )",
-                )?
-                .query(params![from_timestamp, to_timestamp])?,
+                .prepare(&format!(
+                    "SELECT log_id, tag FROM log_tags WHERE log_id IN ({})",
+                    matching_ids_sql
+                ))?
+                .query(params![expression])?,
         )?;
 
         // Fetch and cache events for later lookup
         let events_lookup = events_lookup_from_rows(
             self.connection
-                .prepare(
-                    "SELECT log_id, timestamp, event \
-            FROM log_events WHERE log_id IN (\
-                SELECT logs.id \
-                FROM logs, log_events \
-                WHERE logs.id = log_events.log_id \
-                    AND log_events.event = 'STARTED' \
-                    AND log_events.timestamp >= ?1 \
-                    AND log_events.timestamp < ?2\
-            )",
-                )?
-                .query(params![from_timestamp, to_timestamp])?,
+                .prepare(&format!(
+                    "SELECT log_id, timestamp, event FROM log_events WHERE log_id IN ({})",
+                    matching_ids_sql
+                ))?
+                .query(params![expression])?,
         )?;
 
-        // Create work items from the cached data
-        Ok(create_work_items(item_lookup, tags_lookup, events_lookup))
+        // Fetch and cache notes for later lookup
+        let notes_lookup = notes_lookup_from_rows(
+            self.connection
+                .prepare(&format!(
+                    "SELECT log_id, timestamp, text FROM log_notes WHERE log_id IN ({}) ORDER BY timestamp, id",
+                    matching_ids_sql
+                ))?
+                .query(params![expression])?,
+        )?;
+
+        // Create work items from the cached data and attach them to the matches
+        let mut items_lookup: HashMap<i32, WorkItem> =
+            create_work_items(tmp_items, tags_lookup, events_lookup, notes_lookup)?
+                .into_iter()
+                .map(|item| (item.id().unwrap(), item))
+                .collect();
+
+        Ok(matches
+            .into_iter()
+            .filter_map(|(id, snippet, score, in_note)| {
+                items_lookup
+                    .remove(&id)
+                    .map(|item| SearchResult::new(item, snippet, score, in_note))
+            })
+            .collect())
     }
 
-    fn find_item_by_id(&self, id: i32) -> Result<Option<WorkItem>, Box<dyn Error>> {
-        let item_lookup = tmp_item_lookup_from_rows(
+    fn find_item_by_id(&self, id: i32) -> Result<Option<WorkItem>, Error> {
+        let tmp_items = tmp_items_from_rows(
             self.connection
-                .prepare("SELECT id, description, status FROM logs WHERE id = ?1")?
+                .prepare(&format!(
+                    "{} WHERE logs.id = ?1 AND logs.deleted_at IS NULL",
+                    SELECT_ITEMS_SQL
+                ))?
                 .query(params![id])?,
         )?;
 
@@ -270,51 +499,30 @@ impl DataAccess for SQLiteDataAccess {
                 .query(params![id])?,
         )?;
 
-        // Create work items from the cached data
-        Ok(create_work_items(item_lookup, tags_lookup, events_lookup).pop())
-    }
-
-    fn find_items_by_status(&self, status: Status) -> Result<Vec<WorkItem>, Box<dyn Error>> {
-        let item_lookup = tmp_item_lookup_from_rows(
-            self.connection
-                .prepare("SELECT id, description, status FROM logs WHERE status = ?1")?
-                .query(params![format!("{}", status)])?,
-        )?;
-
-        // Fetch and cache tags for later lookup
-        let tags_lookup: HashMap<i32, HashSet<String>> = tags_lookup_from_rows(
-            self.connection
-                .prepare(
-                    "SELECT log_id, tag FROM log_tags WHERE log_id IN (\
-                    SELECT id FROM logs WHERE status = ?1
-                )",
-                )?
-                .query(params![format!("{}", status)])?,
-        )?;
-
-        // Fetch and cache events for later lookup
-        let events_lookup = events_lookup_from_rows(
+        // Fetch and cache notes for later lookup
+        let notes_lookup = notes_lookup_from_rows(
             self.connection
                 .prepare(
-                    "SELECT log_id, timestamp, event FROM log_events WHERE log_id IN (\
-                    SELECT id FROM logs WHERE status = ?1
-                )",
+                    "SELECT log_id, timestamp, text FROM log_notes WHERE log_id = ?1 ORDER BY timestamp, id",
                 )?
-                .query(params![format!("{}", status)])?,
+                .query(params![id])?,
         )?;
 
         // Create work items from the cached data
-        Ok(create_work_items(item_lookup, tags_lookup, events_lookup))
+        Ok(create_work_items(tmp_items, tags_lookup, events_lookup, notes_lookup)?.pop())
     }
 
-    fn delete_item(&mut self, id: i32) -> Result<Option<WorkItem>, Box<dyn Error>> {
-        let transaction = self.connection.transaction()?;
+    fn delete_item(&mut self, id: i32) -> Result<Option<WorkItem>, Error> {
+        let transaction = self.connection.savepoint()?;
 
         // First and foremost find work item
         let item = {
-            let item_lookup = tmp_item_lookup_from_rows(
+            let tmp_items = tmp_items_from_rows(
                 transaction
-                    .prepare("SELECT id, description, status FROM logs WHERE id = ?1")?
+                    .prepare(&format!(
+                        "{} WHERE logs.id = ?1 AND logs.deleted_at IS NULL",
+                        SELECT_ITEMS_SQL
+                    ))?
                     .query(params![id])?,
             )?;
 
@@ -332,98 +540,457 @@ impl DataAccess for SQLiteDataAccess {
                     .query(params![id])?,
             )?;
 
+            // Fetch and cache notes for later lookup
+            let notes_lookup = notes_lookup_from_rows(
+                transaction
+                    .prepare(
+                        "SELECT log_id, timestamp, text FROM log_notes WHERE log_id = ?1 ORDER BY timestamp, id",
+                    )?
+                    .query(params![id])?,
+            )?;
+
             // Create work items from the cached data
-            create_work_items(item_lookup, tags_lookup, events_lookup).pop()
+            create_work_items(tmp_items, tags_lookup, events_lookup, notes_lookup)?.pop()
         };
 
-        // Delete from log_tags table first
-        transaction.execute("DELETE FROM log_tags WHERE log_id = ?1", params![id])?;
+        let before = match &item {
+            Some(item) => Some(to_journal_state(item)),
+            None => return Ok(None),
+        };
 
-        // Delete from log_events table second
-        transaction.execute("DELETE FROM log_events WHERE log_id = ?1", params![id])?;
+        // Move the work item to the trash and record it in the journal
+        trash_item(&transaction, id)?;
 
-        // Delete from logs table third
-        transaction.execute("DELETE FROM logs WHERE id = ?1", params![id])?;
+        let journal_id = record_changes(
+            &transaction,
+            self.transaction_journal_ids.last().copied().flatten(),
+            &[TmpJournalChange {
+                id,
+                before,
+                after: None,
+                restored: false,
+            }],
+        )?;
 
         transaction.commit()?;
+        self.remember_journal_id(journal_id);
 
         Ok(item)
     }
 
-    fn clear(&mut self) -> Result<(), Box<dyn Error>> {
-        let transaction = self.connection.transaction()?;
+    fn clear(&mut self) -> Result<(), Error> {
+        let transaction = self.connection.savepoint()?;
+
+        // Record all work items to move to the trash in the journal
+        let changes = find_items(
+            &transaction,
+            "SELECT id FROM logs WHERE deleted_at IS NULL",
+            NO_PARAMS,
+        )?
+        .iter()
+        .map(|item| TmpJournalChange {
+            id: item.id().unwrap(),
+            before: Some(to_journal_state(item)),
+            after: None,
+            restored: false,
+        })
+        .collect::<Vec<_>>();
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let journal_id = record_changes(
+            &transaction,
+            self.transaction_journal_ids.last().copied().flatten(),
+            &changes,
+        )?;
+
+        // Move all work items to the trash
+        record_trash_revisions(
+            &transaction,
+            "SELECT id FROM logs WHERE deleted_at IS NULL",
+            NO_PARAMS,
+            Some(TRASHED),
+        )?;
+        transaction.execute(
+            "UPDATE logs SET deleted_at = ?1 WHERE deleted_at IS NULL",
+            params![get_current_timestamp()],
+        )?;
+
+        transaction.commit()?;
+        self.remember_journal_id(journal_id);
+
+        Ok(())
+    }
+
+    fn list_trash(&self) -> Result<Vec<TrashedItem>, Error> {
+        let mut deleted_timestamps: Vec<(i32, i64)> = Vec::new();
+        {
+            let mut statement = self.connection.prepare(
+                "SELECT id, deleted_at FROM logs \
+                WHERE deleted_at IS NOT NULL \
+                ORDER BY deleted_at DESC, id DESC",
+            )?;
+            let mut rows = statement.query(NO_PARAMS)?;
+            while let Some(row) = rows.next()? {
+                deleted_timestamps.push((row.get(0)?, row.get(1)?));
+            }
+        }
+
+        let mut items_lookup: HashMap<i32, WorkItem> = find_items(
+            &self.connection,
+            "SELECT id FROM logs WHERE deleted_at IS NOT NULL",
+            NO_PARAMS,
+        )?
+        .into_iter()
+        .map(|item| (item.id().unwrap(), item))
+        .collect();
+
+        Ok(deleted_timestamps
+            .into_iter()
+            .filter_map(|(id, deleted_timestamp)| {
+                items_lookup
+                    .remove(&id)
+                    .map(|item| TrashedItem::new(item, deleted_timestamp))
+            })
+            .collect())
+    }
+
+    fn restore_from_trash(&mut self, id: i32) -> Result<Option<WorkItem>, Error> {
+        let transaction = self.connection.savepoint()?;
+
+        let item = match find_items(
+            &transaction,
+            "SELECT id FROM logs WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+        )?
+        .pop()
+        {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        record_trash_revisions(&transaction, "SELECT ?1", params![id], None)?;
+        transaction.execute(
+            "UPDATE logs SET deleted_at = NULL WHERE id = ?1",
+            params![id],
+        )?;
+
+        // Record the restored work item in the journal, so that undoing moves it back to the trash
+        let state = Some(to_journal_state(&item));
+        let journal_id = record_changes(
+            &transaction,
+            self.transaction_journal_ids.last().copied().flatten(),
+            &[TmpJournalChange {
+                id,
+                before: state.clone(),
+                after: state,
+                restored: true,
+            }],
+        )?;
+
+        transaction.commit()?;
+        self.remember_journal_id(journal_id);
+
+        Ok(Some(item))
+    }
+
+    fn purge_trash(&mut self, deleted_before: i64) -> Result<usize, Error> {
+        let transaction = self.connection.savepoint()?;
+
+        // Purging is permanent, thus forget all journal entries that could bring the work items back
+        let ids_sql = "SELECT id FROM logs WHERE deleted_at < ?1";
+        transaction.execute(
+            &format!(
+                "DELETE FROM journal_changes WHERE journal_id IN \
+                (SELECT journal_id FROM journal_changes WHERE log_id IN ({}))",
+                ids_sql
+            ),
+            params![deleted_before],
+        )?;
+        transaction.execute(
+            "DELETE FROM journal WHERE id NOT IN (SELECT journal_id FROM journal_changes)",
+            NO_PARAMS,
+        )?;
+
+        let count = purge_items(&transaction, ids_sql, params![deleted_before])?;
+
+        transaction.commit()?;
+
+        Ok(count)
+    }
+
+    fn undo(&mut self) -> Result<Option<JournalEntry>, Error> {
+        let transaction = self.connection.savepoint()?;
+
+        // Find the latest journal entry that has not been undone yet
+        let entry = match find_journal_entries(
+            &transaction,
+            "SELECT id FROM journal WHERE undone = 0 ORDER BY id DESC LIMIT 1",
+            NO_PARAMS,
+        )?
+        .pop()
+        {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        // Restore the states before the changes in reverse order
+        for change in entry.changes().iter().rev() {
+            if change.kind() == ChangeKind::Restored {
+                trash_item(&transaction, change.item_id())?;
+                continue;
+            }
+
+            match change.before() {
+                Some(item) => write_state(&transaction, change.item_id(), item)?,
+                None => {
+                    // The work item did not exist before
+                    purge_items(&transaction, "SELECT ?1", params![change.item_id()])?;
+                }
+            }
+        }
+
+        transaction.execute(
+            "UPDATE journal SET undone = 1 WHERE id = ?1",
+            params![entry.id()],
+        )?;
+
+        let entry = find_journal_entries(&transaction, "SELECT ?1", params![entry.id()])?.pop();
+
+        transaction.commit()?;
+
+        Ok(entry)
+    }
+
+    fn redo(&mut self) -> Result<Option<JournalEntry>, Error> {
+        let transaction = self.connection.savepoint()?;
+
+        // Find the earliest journal entry that has been undone
+        let entry = match find_journal_entries(
+            &transaction,
+            "SELECT id FROM journal WHERE undone = 1 ORDER BY id ASC LIMIT 1",
+            NO_PARAMS,
+        )?
+        .pop()
+        {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        // Apply the states after the changes again
+        for change in entry.changes() {
+            match change.after() {
+                Some(item) => write_state(&transaction, change.item_id(), item)?,
+                None => trash_item(&transaction, change.item_id())?,
+            }
+        }
+
+        transaction.execute(
+            "UPDATE journal SET undone = 0 WHERE id = ?1",
+            params![entry.id()],
+        )?;
+
+        let entry = find_journal_entries(&transaction, "SELECT ?1", params![entry.id()])?.pop();
+
+        transaction.commit()?;
+
+        Ok(entry)
+    }
+
+    fn list_journal(&self, limit: usize) -> Result<Vec<JournalEntry>, Error> {
+        find_journal_entries(
+            &self.connection,
+            "SELECT id FROM journal ORDER BY id DESC LIMIT ?1",
+            params![limit as i64],
+        )
+    }
 
-        // Clear the log_tags table
-        transaction.execute("DELETE FROM log_tags", NO_PARAMS)?;
+    fn item_history(&self, id: i32) -> Result<Vec<Revision>, Error> {
+        revisions_from_rows(
+            self.connection
+                .prepare(
+                    "SELECT timestamp, origin, field, old_value, new_value FROM log_revisions \
+                    WHERE log_id = ?1 ORDER BY id",
+                )?
+                .query(params![id])?,
+        )
+    }
 
-        // Clear the log_events table
-        transaction.execute("DELETE FROM log_events", NO_PARAMS)?;
+    fn create_project(&mut self, project: Project) -> Result<i32, Error> {
+        let transaction = self.connection.savepoint()?;
 
-        // Clear the logs table
-        transaction.execute("DELETE FROM logs", NO_PARAMS)?;
+        let exists: bool = transaction.query_row(
+            "SELECT EXISTS (SELECT 1 FROM projects WHERE name = ?1)",
+            params![project.name()],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Err(Error::ProjectAlreadyExists(project.name().to_owned()));
+        }
+
+        transaction.execute(
+            "INSERT INTO projects (name, client, hourly_rate, archived) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                project.name(),
+                project.client(),
+                project.hourly_rate(),
+                project.is_archived()
+            ],
+        )?;
+
+        let id: i32 =
+            transaction.query_row("SELECT last_insert_rowid()", NO_PARAMS, |row| row.get(0))?;
 
         transaction.commit()?;
 
+        Ok(id)
+    }
+
+    fn update_project(&mut self, project: &Project) -> Result<(), Error> {
+        let id = project.id().expect("ID must be present at this point!");
+
+        self.connection.execute(
+            "UPDATE projects \
+            SET client = ?2, \
+            hourly_rate = ?3, \
+            archived = ?4 \
+            WHERE id = ?1",
+            params![
+                id,
+                project.client(),
+                project.hourly_rate(),
+                project.is_archived()
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn list_projects(&self) -> Result<Vec<Project>, Error> {
+        projects_from_rows(
+            self.connection
+                .prepare(&format!("{} ORDER BY name", SELECT_PROJECTS_SQL))?
+                .query(NO_PARAMS)?,
+        )
+    }
+
+    fn find_project_by_name(&self, name: &str) -> Result<Option<Project>, Error> {
+        Ok(projects_from_rows(
+            self.connection
+                .prepare(&format!("{} WHERE name = ?1", SELECT_PROJECTS_SQL))?
+                .query(params![name])?,
+        )?
+        .pop())
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), Error> {
+        // Savepoints behave like a transaction at the outermost level and may be nested
+        self.connection
+            .execute_batch(&format!("SAVEPOINT {}", TRANSACTION_SAVEPOINT))?;
+
+        // Nested transactions record their changes in the journal entry of the enclosing one
+        let journal_id = self.transaction_journal_ids.last().copied().flatten();
+        self.transaction_journal_ids.push(journal_id);
+
+        Ok(())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), Error> {
+        self.connection
+            .execute_batch(&format!("RELEASE {}", TRANSACTION_SAVEPOINT))?;
+
+        // Hand the journal entry over to the enclosing transaction (if any)
+        let journal_id = self.transaction_journal_ids.pop().flatten();
+        if let Some(journal_id) = journal_id {
+            self.remember_journal_id(journal_id);
+        }
+
+        Ok(())
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), Error> {
+        // The enclosing transaction keeps its own journal entry (if any)
+        self.transaction_journal_ids.pop();
+
+        self.connection.execute_batch(&format!(
+            "ROLLBACK TO {0}; RELEASE {0}",
+            TRANSACTION_SAVEPOINT
+        ))?;
+
         Ok(())
     }
 }
 
 /// Create final work items from the passed caches.
 fn create_work_items(
-    item_lookup: HashMap<i32, TmpWorkItem>,
+    tmp_items: Vec<TmpWorkItem>,
     mut tags_lookup: HashMap<i32, HashSet<String>>,
     mut events_lookup: HashMap<i32, Vec<Event>>,
-) -> Vec<WorkItem> {
-    item_lookup
+    mut notes_lookup: HashMap<i32, Vec<Note>>,
+) -> Result<Vec<WorkItem>, Error> {
+    tmp_items
         .into_iter()
-        .map(|(id, tmp_item)| {
+        .map(|tmp_item| {
+            let id = tmp_item.id;
+
             // Retrieve cached tags
             let tags = tags_lookup.remove(&id).unwrap_or(HashSet::new());
 
             // Retrieve cached events and sort them by their timestamp
-            let mut events = events_lookup
-                .remove(&id)
-                .expect("Found no events for a work item, which must not be possible");
+            let mut events = events_lookup.remove(&id).ok_or_else(|| {
+                Error::Corrupted(format!("Found no events for work item with ID {}", id))
+            })?;
             events.sort_by_key(|e| e.timestamp());
 
-            WorkItem::new_internal(
+            let mut item = WorkItem::new_internal(
                 tmp_item.id,
                 tmp_item.description,
                 tmp_item.status,
                 tags,
                 events,
-            )
+            );
+            item.set_project(tmp_item.project);
+
+            // Retrieve cached notes (already sorted by their timestamp)
+            item.set_notes(notes_lookup.remove(&id).unwrap_or_default());
+
+            Ok(item)
         })
         .collect()
 }
 
-/// Create a tmp work item lookup from the given logs table rows.
-fn tmp_item_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, TmpWorkItem>, Box<dyn Error>> {
-    let mut item_lookup = HashMap::new();
+/// Create tmp work items from the given logs table rows (keeping their order).
+fn tmp_items_from_rows(mut rows: Rows) -> Result<Vec<TmpWorkItem>, Error> {
+    let mut tmp_items = Vec::new();
     while let Some(row) = rows.next()? {
         let id: i32 = row.get(0)?;
 
         let description = row.get(1)?;
 
         let status_str: String = row.get(2)?;
-        let status = Status::from_str(&status_str)
-            .expect("Could not interpret Status string stored in the database");
+        let status = Status::from_str(&status_str).map_err(|_| {
+            Error::Corrupted(format!(
+                "Could not interpret status '{}' of work item with ID {}",
+                status_str, id
+            ))
+        })?;
 
-        item_lookup.insert(
+        let project = row.get(3)?;
+
+        tmp_items.push(TmpWorkItem {
             id,
-            TmpWorkItem {
-                id,
-                description,
-                status,
-            },
-        );
+            description,
+            status,
+            project,
+        });
     }
 
-    Ok(item_lookup)
+    Ok(tmp_items)
 }
 
 /// Create a tags lookup from the passed log_tags table rows.
-fn tags_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, HashSet<String>>, Box<dyn Error>> {
+fn tags_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, HashSet<String>>, Error> {
     let mut tags_lookup: HashMap<i32, HashSet<String>> = HashMap::new();
     while let Some(row) = rows.next()? {
         let log_id: i32 = row.get(0)?;
@@ -445,7 +1012,7 @@ fn tags_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, HashSet<String>>
 }
 
 /// Create an events lookup from the passed log_events table rows.
-fn events_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, Vec<Event>>, Box<dyn Error>> {
+fn events_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, Vec<Event>>, Error> {
     let mut events_lookup: HashMap<i32, Vec<Event>> = HashMap::new();
     while let Some(row) = rows.next()? {
         let log_id: i32 = row.get(0)?;
@@ -453,8 +1020,12 @@ fn events_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, Vec<Event>>, B
         let timestamp: i64 = row.get(1)?;
 
         let event_str: String = row.get(2)?;
-        let event_type =
-            EventType::from_str(&event_str).expect("EventType string could not be interpreted");
+        let event_type = EventType::from_str(&event_str).map_err(|_| {
+            Error::Corrupted(format!(
+                "Could not interpret event '{}' of work item with ID {}",
+                event_str, log_id
+            ))
+        })?;
 
         match events_lookup.entry(log_id) {
             Entry::Occupied(mut e) => {
@@ -469,15 +1040,429 @@ fn events_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, Vec<Event>>, B
     Ok(events_lookup)
 }
 
+/// Create projects from the passed projects table rows (keeping their order).
+fn projects_from_rows(mut rows: Rows) -> Result<Vec<Project>, Error> {
+    let mut projects = Vec::new();
+    while let Some(row) = rows.next()? {
+        projects.push(Project::new_internal(
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+        ));
+    }
+
+    Ok(projects)
+}
+
+/// Get the ID of the project with the given name (if any).
+/// Fails with `Error::ProjectNotFound` when there is no such project.
+fn project_id_of(transaction: &Connection, name: Option<&str>) -> Result<Option<i32>, Error> {
+    let name = match name {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    let mut statement = transaction.prepare("SELECT id FROM projects WHERE name = ?1")?;
+    let mut rows = statement.query(params![name])?;
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Err(Error::ProjectNotFound(name.to_owned())),
+    }
+}
+
+/// Find the work items (including the ones in the trash) with their IDs selected
+/// by the given SQL using the passed parameters.
+fn find_items<P>(connection: &Connection, ids_sql: &str, params: P) -> Result<Vec<WorkItem>, Error>
+where
+    P: IntoIterator + Copy,
+    P::Item: ToSql,
+{
+    let tmp_items = tmp_items_from_rows(
+        connection
+            .prepare(&format!(
+                "{} WHERE logs.id IN ({})",
+                SELECT_ITEMS_SQL, ids_sql
+            ))?
+            .query(params)?,
+    )?;
+
+    let tags_lookup = tags_lookup_from_rows(
+        connection
+            .prepare(&format!(
+                "SELECT log_id, tag FROM log_tags WHERE log_id IN ({})",
+                ids_sql
+            ))?
+            .query(params)?,
+    )?;
+
+    let events_lookup = events_lookup_from_rows(
+        connection
+            .prepare(&format!(
+                "SELECT log_id, timestamp, event FROM log_events WHERE log_id IN ({})",
+                ids_sql
+            ))?
+            .query(params)?,
+    )?;
+
+    let notes_lookup = notes_lookup_from_rows(
+        connection
+            .prepare(&format!(
+                "SELECT log_id, timestamp, text FROM log_notes WHERE log_id IN ({}) ORDER BY timestamp, id",
+                ids_sql
+            ))?
+            .query(params)?,
+    )?;
+
+    create_work_items(tmp_items, tags_lookup, events_lookup, notes_lookup)
+}
+
+/// Move the work item with the given ID to the trash.
+fn trash_item(connection: &Connection, id: i32) -> Result<(), Error> {
+    record_trash_revisions(connection, "SELECT ?1", params![id], Some(TRASHED))?;
+    connection.execute(
+        "UPDATE logs SET deleted_at = ?2 WHERE id = ?1 AND deleted_at IS NULL",
+        params![id, get_current_timestamp()],
+    )?;
+
+    Ok(())
+}
+
+/// Permanently delete the work items with their IDs selected by the given SQL.
+/// Returns the number of deleted work items.
+fn purge_items<P>(connection: &Connection, ids_sql: &str, params: P) -> Result<usize, Error>
+where
+    P: IntoIterator + Copy,
+    P::Item: ToSql,
+{
+    record_trash_revisions(connection, ids_sql, params, Some(PURGED))?;
+
+    // Delete from the log_notes, log_tags and log_events tables first
+    for table in &["log_notes", "log_tags", "log_events"] {
+        connection.execute(
+            &format!("DELETE FROM {} WHERE log_id IN ({})", table, ids_sql),
+            params,
+        )?;
+    }
+
+    // Delete from logs table last
+    let count = connection.execute(
+        &format!("DELETE FROM logs WHERE id IN ({})", ids_sql),
+        params,
+    )?;
+
+    Ok(count)
+}
+
+/// Write the given state of the work item with the passed ID (restoring it from the trash
+/// or inserting it again if necessary) and record the changed fields in the revision history.
+fn write_state(transaction: &Connection, id: i32, item: &WorkItem) -> Result<(), Error> {
+    let project_id = project_id_of(transaction, item.project())?;
+    let before = stored_item(transaction, id)?;
+    record_trash_revisions(transaction, "SELECT ?1", params![id], None)?;
+
+    let exists: bool = transaction.query_row(
+        "SELECT EXISTS (SELECT 1 FROM logs WHERE id = ?1)",
+        params![id],
+        |row| row.get(0),
+    )?;
+    if exists {
+        transaction.execute(
+            "UPDATE logs \
+            SET description = ?2, status = ?3, project_id = ?4, deleted_at = NULL \
+            WHERE id = ?1",
+            params![
+                id,
+                item.description(),
+                format!("{}", item.status()),
+                project_id
+            ],
+        )?;
+    } else {
+        transaction.execute(
+            "INSERT INTO logs (id, description, status, project_id) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                id,
+                item.description(),
+                format!("{}", item.status()),
+                project_id
+            ],
+        )?;
+    }
+
+    delete_tags(transaction, id)?;
+    insert_tags(transaction, id, &item.tags())?;
+    delete_events(transaction, id)?;
+    insert_events(transaction, id, item.events())?;
+    delete_notes(transaction, id)?;
+    insert_notes(transaction, id, item.notes())?;
+
+    record_revisions(
+        transaction,
+        id,
+        before.as_ref(),
+        stored_item(transaction, id)?.as_ref(),
+    )
+}
+
+/// Serialize the given work item state for the journal.
+fn to_journal_state(item: &WorkItem) -> String {
+    serde_json::to_string(item).expect("Work items must always be serializable")
+}
+
+/// Get the stored state of the work item with the given ID (including work items in the trash)
+/// or None if there is no such work item.
+fn stored_item(connection: &Connection, id: i32) -> Result<Option<WorkItem>, Error> {
+    Ok(find_items(connection, "SELECT id FROM logs WHERE id = ?1", params![id])?.pop())
+}
+
+/// Record the fields changed from the given state of the work item with the passed ID
+/// to the other one in the revision history (using the currently active origin).
+/// Nothing is recorded when the work item is removed.
+fn record_revisions(
+    transaction: &Connection,
+    id: i32,
+    before: Option<&WorkItem>,
+    after: Option<&WorkItem>,
+) -> Result<(), Error> {
+    let after = match after {
+        Some(after) => revision::field_values(after),
+        None => return Ok(()),
+    };
+    let before = before.map(revision::field_values);
+
+    let timestamp = get_current_timestamp();
+    let origin = format!("{}", revision::active_origin());
+    for (index, (field, new_value)) in after.into_iter().enumerate() {
+        let old_value = before.as_ref().and_then(|before| before[index].1.clone());
+        if old_value == new_value {
+            continue;
+        }
+
+        transaction.execute(
+            "INSERT INTO log_revisions (log_id, timestamp, origin, field, old_value, new_value) \
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                id,
+                timestamp,
+                origin,
+                format!("{}", field),
+                old_value,
+                new_value
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Record the change of the trash state of the work items with their IDs selected by the given SQL
+/// to the passed value (`TRASHED`, `PURGED` or None when restored) in the revision history.
+/// Must be called before changing the state. Work items already in the new state are skipped.
+fn record_trash_revisions<P>(
+    connection: &Connection,
+    ids_sql: &str,
+    params: P,
+    new_value: Option<&str>,
+) -> Result<(), Error>
+where
+    P: IntoIterator + Copy,
+    P::Item: ToSql,
+{
+    let new_value = match new_value {
+        Some(value) => format!("'{}'", value),
+        None => String::from("NULL"),
+    };
+    let old_value = format!(
+        "CASE WHEN deleted_at IS NULL THEN NULL ELSE '{}' END",
+        TRASHED
+    );
+
+    connection.execute(
+        &format!(
+            "INSERT INTO log_revisions (log_id, timestamp, origin, field, old_value, new_value) \
+            SELECT id, {}, '{}', '{}', {}, {} FROM logs \
+            WHERE id IN ({}) AND {} IS NOT {}",
+            get_current_timestamp(),
+            revision::active_origin(),
+            RevisionField::Trash,
+            old_value,
+            new_value,
+            ids_sql,
+            old_value,
+            new_value
+        ),
+        params,
+    )?;
+
+    Ok(())
+}
+
+/// Read the revisions from the given rows.
+fn revisions_from_rows(mut rows: Rows) -> Result<Vec<Revision>, Error> {
+    let mut revisions = Vec::new();
+    while let Some(row) = rows.next()? {
+        let origin: String = row.get(1)?;
+        let field: String = row.get(2)?;
+
+        revisions.push(Revision::new(
+            row.get(0)?,
+            Origin::from_str(&origin).map_err(|_| {
+                Error::Corrupted(format!(
+                    "Unknown origin '{}' in the revision history",
+                    origin
+                ))
+            })?,
+            RevisionField::from_str(&field).map_err(|_| {
+                Error::Corrupted(format!("Unknown field '{}' in the revision history", field))
+            })?,
+            row.get(3)?,
+            row.get(4)?,
+        ));
+    }
+
+    Ok(revisions)
+}
+
+/// Record the given changes in the journal entry with the passed ID (if any).
+/// Starts a new entry otherwise, discarding all undone entries as they can no longer be redone.
+/// Returns the ID of the journal entry.
+fn record_changes(
+    transaction: &Connection,
+    journal_id: Option<i32>,
+    changes: &[TmpJournalChange],
+) -> Result<i32, Error> {
+    let journal_id = match journal_id {
+        Some(journal_id) => journal_id,
+        None => {
+            transaction.execute(
+                "DELETE FROM journal_changes WHERE journal_id IN (SELECT id FROM journal WHERE undone = 1)",
+                NO_PARAMS,
+            )?;
+            transaction.execute("DELETE FROM journal WHERE undone = 1", NO_PARAMS)?;
+
+            transaction.execute(
+                "INSERT INTO journal (timestamp) VALUES (?1)",
+                params![get_current_timestamp()],
+            )?;
+
+            transaction.query_row("SELECT last_insert_rowid()", NO_PARAMS, |row| row.get(0))?
+        }
+    };
+
+    for change in changes {
+        transaction.execute(
+            "INSERT INTO journal_changes (journal_id, log_id, before_state, after_state, restored) \
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                journal_id,
+                change.id,
+                change.before,
+                change.after,
+                change.restored
+            ],
+        )?;
+    }
+
+    Ok(journal_id)
+}
+
+/// Find the journal entries with their IDs selected by the given SQL (newest first).
+fn find_journal_entries<P>(
+    connection: &Connection,
+    ids_sql: &str,
+    params: P,
+) -> Result<Vec<JournalEntry>, Error>
+where
+    P: IntoIterator + Copy,
+    P::Item: ToSql,
+{
+    let mut changes_lookup: HashMap<i32, Vec<JournalChange>> = HashMap::new();
+    {
+        let mut statement = connection.prepare(&format!(
+            "SELECT journal_id, log_id, before_state, after_state, restored FROM journal_changes \
+            WHERE journal_id IN ({}) \
+            ORDER BY id",
+            ids_sql
+        ))?;
+        let mut rows = statement.query(params)?;
+        while let Some(row) = rows.next()? {
+            let journal_id: i32 = row.get(0)?;
+            let before: Option<String> = row.get(2)?;
+            let after: Option<String> = row.get(3)?;
+
+            changes_lookup
+                .entry(journal_id)
+                .or_default()
+                .push(JournalChange::new(
+                    row.get(1)?,
+                    from_journal_state(before)?,
+                    from_journal_state(after)?,
+                    row.get(4)?,
+                ));
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut statement = connection.prepare(&format!(
+        "SELECT id, timestamp, undone FROM journal WHERE id IN ({}) ORDER BY id DESC",
+        ids_sql
+    ))?;
+    let mut rows = statement.query(params)?;
+    while let Some(row) = rows.next()? {
+        let id: i32 = row.get(0)?;
+
+        entries.push(JournalEntry::new(
+            id,
+            row.get(1)?,
+            row.get(2)?,
+            changes_lookup.remove(&id).unwrap_or_default(),
+        ));
+    }
+
+    Ok(entries)
+}
+
+/// Deserialize the given work item state of the journal.
+fn from_journal_state(state: Option<String>) -> Result<Option<WorkItem>, Error> {
+    state
+        .map(|state| serde_json::from_str(&state))
+        .transpose()
+        .map_err(|e| Error::Corrupted(format!("Invalid work item state in the journal: {}", e)))
+}
+
+/// Get the current timestamp (in milliseconds).
+fn get_current_timestamp() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Create a notes lookup from the passed log_notes table rows.
+fn notes_lookup_from_rows(mut rows: Rows) -> Result<HashMap<i32, Vec<Note>>, Error> {
+    let mut notes_lookup: HashMap<i32, Vec<Note>> = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let log_id: i32 = row.get(0)?;
+        let timestamp: i64 = row.get(1)?;
+        let text: String = row.get(2)?;
+
+        notes_lookup
+            .entry(log_id)
+            .or_default()
+            .push(Note::new(text, timestamp));
+    }
+
+    Ok(notes_lookup)
+}
+
 /// Delete all tags for the work item with the given ID.
-fn delete_tags(transaction: &Transaction, id: i32) -> Result<(), Box<dyn Error>> {
+fn delete_tags(transaction: &Connection, id: i32) -> Result<(), Error> {
     transaction.execute("DELETE FROM log_tags WHERE log_id = ?1", params![id])?;
 
     Ok(())
 }
 
 /// Insert all the given tags for the work item with the passed ID.
-fn insert_tags(transaction: &Transaction, id: i32, tags: &[String]) -> Result<(), Box<dyn Error>> {
+fn insert_tags(transaction: &Connection, id: i32, tags: &[String]) -> Result<(), Error> {
     for tag in tags {
         transaction.execute(
             "INSERT INTO log_tags (log_id, tag) VALUES (?1, ?2)",
@@ -489,18 +1474,14 @@ fn insert_tags(transaction: &Transaction, id: i32, tags: &[String]) -> Result<()
 }
 
 /// Delete all events for the work item with the given ID.
-fn delete_events(transaction: &Transaction, id: i32) -> Result<(), Box<dyn Error>> {
+fn delete_events(transaction: &Connection, id: i32) -> Result<(), Error> {
     transaction.execute("DELETE FROM log_events WHERE log_id = ?1", params![id])?;
 
     Ok(())
 }
 
 /// Insert all the given events for the work item with the passed ID.
-fn insert_events(
-    transaction: &Transaction,
-    id: i32,
-    events: &[Event],
-) -> Result<(), Box<dyn Error>> {
+fn insert_events(transaction: &Connection, id: i32, events: &[Event]) -> Result<(), Error> {
     for event in events {
         transaction.execute(
             "INSERT INTO log_events (log_id, timestamp, event) VALUES (?1, ?2, ?3)",
@@ -510,3 +1491,64 @@ fn insert_events(
 
     Ok(())
 }
+
+/// Delete all notes for the work item with the given ID.
+fn delete_notes(transaction: &Connection, id: i32) -> Result<(), Error> {
+    transaction.execute("DELETE FROM log_notes WHERE log_id = ?1", params![id])?;
+
+    Ok(())
+}
+
+/// Insert all the given notes for the work item with the passed ID.
+fn insert_notes(transaction: &Connection, id: i32, notes: &[Note]) -> Result<(), Error> {
+    for note in notes {
+        transaction.execute(
+            "INSERT INTO log_notes (log_id, timestamp, text) VALUES (?1, ?2, ?3)",
+            params![id, note.timestamp(), note.text()],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_too_new() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE info (version INTEGER PRIMARY KEY); \
+                INSERT INTO info (version) VALUES (999);",
+            )
+            .unwrap();
+
+        match SQLiteDataAccess::from_connection(connection) {
+            Err(Error::SchemaTooNew {
+                version,
+                latest_version,
+            }) => {
+                assert_eq!(version, 999);
+                assert_eq!(latest_version, LATEST_VERSION);
+            }
+            _ => panic!("Expected the schema to be too new"),
+        }
+    }
+
+    #[test]
+    fn test_query_items_without_started_event() {
+        let mut data_access = SQLiteDataAccess::in_memory().unwrap();
+
+        let mut item = WorkItem::new(String::from("Imported"), Status::InProgress, HashSet::new());
+        item.finish_working(None).unwrap();
+        data_access.log_item(item).unwrap();
+        data_access
+            .connection
+            .execute("DELETE FROM log_events WHERE event = 'STARTED'", NO_PARAMS)
+            .unwrap();
+
+        assert_eq!(data_access.query_items(&ItemQuery::new()).unwrap().len(), 1);
+    }
+}
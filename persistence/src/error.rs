@@ -0,0 +1,101 @@
+use std::{error, fmt, io};
+
+use rusqlite::ErrorCode;
+
+/// Errors that may occur in the persistence layer.
+#[derive(Debug)]
+pub enum Error {
+    /// There is no work item with the given ID.
+    NotFound(i32),
+    /// There already is a work item with the given ID.
+    AlreadyExists(i32),
+    /// There is no project with the given name.
+    ProjectNotFound(String),
+    /// There already is a project with the given name.
+    ProjectAlreadyExists(String),
+    /// The requested change is not possible in the current state of the work item.
+    InvalidTransition(&'static str),
+    /// The events of a work item do not form a valid timeline.
+    InvalidTimeline(String),
+    /// The database is locked (or busy) by another connection.
+    Locked(rusqlite::Error),
+    /// The database has been created by a newer version of the application.
+    SchemaTooNew {
+        /// Version of the database.
+        version: i32,
+        /// Latest version this application is able to handle.
+        latest_version: i32,
+    },
+    /// The location of the database could not be determined.
+    Location(String),
+    /// The data stored in the database could not be interpreted.
+    Corrupted(String),
+    /// Any other database error.
+    Database(rusqlite::Error),
+    /// Error accessing the file system.
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotFound(id) => write!(f, "Could not find work item with ID {}", id),
+            Error::AlreadyExists(id) => write!(f, "There already is a work item with ID {}", id),
+            Error::ProjectNotFound(name) => write!(f, "Could not find project '{}'", name),
+            Error::ProjectAlreadyExists(name) => {
+                write!(f, "There already is a project named '{}'", name)
+            }
+            Error::InvalidTransition(message) => write!(f, "{}", message),
+            Error::InvalidTimeline(message) => write!(f, "Invalid event timeline: {}", message),
+            Error::Locked(_) => write!(
+                f,
+                "The database is locked by another process. Please try again later"
+            ),
+            Error::SchemaTooNew {
+                version,
+                latest_version,
+            } => write!(
+                f,
+                "The database has version {} but only versions up to {} are supported. Please update the application",
+                version, latest_version
+            ),
+            Error::Location(message) => write!(f, "{}", message),
+            Error::Corrupted(message) => write!(f, "The database is corrupted: {}", message),
+            Error::Database(e) => write!(f, "Database error: {}", e),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Locked(e) | Error::Database(e) => Some(e),
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        let locked = match &e {
+            rusqlite::Error::SqliteFailure(failure, _) => {
+                failure.code == ErrorCode::DatabaseBusy || failure.code == ErrorCode::DatabaseLocked
+            }
+            _ => false,
+        };
+
+        if locked {
+            Error::Locked(e)
+        } else {
+            Error::Database(e)
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,12 @@
+/// Kind of change made to a work item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    /// The work item has been logged.
+    Logged,
+    /// The work item has been updated.
+    Updated,
+    /// The work item has been deleted (moved to the trash).
+    Deleted,
+    /// The work item has been restored from the trash.
+    Restored,
+}
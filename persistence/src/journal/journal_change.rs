@@ -0,0 +1,59 @@
+use crate::calc::WorkItem;
+use crate::journal::ChangeKind;
+
+/// Change of a single work item recorded in the journal.
+#[derive(Debug)]
+pub struct JournalChange {
+    /// ID of the changed work item.
+    item_id: i32,
+    /// State of the work item before the change (None if it did not exist yet).
+    before: Option<WorkItem>,
+    /// State of the work item after the change (None if it has been deleted).
+    after: Option<WorkItem>,
+    /// Whether the work item has been restored from the trash.
+    restored: bool,
+}
+
+impl JournalChange {
+    pub(crate) fn new(
+        item_id: i32,
+        before: Option<WorkItem>,
+        after: Option<WorkItem>,
+        restored: bool,
+    ) -> JournalChange {
+        JournalChange {
+            item_id,
+            before,
+            after,
+            restored,
+        }
+    }
+
+    /// Get the ID of the changed work item.
+    pub fn item_id(&self) -> i32 {
+        self.item_id
+    }
+
+    /// Get the state of the work item before the change (None if it did not exist yet).
+    pub fn before(&self) -> Option<&WorkItem> {
+        self.before.as_ref()
+    }
+
+    /// Get the state of the work item after the change (None if it has been deleted).
+    pub fn after(&self) -> Option<&WorkItem> {
+        self.after.as_ref()
+    }
+
+    /// Get the kind of the change.
+    pub fn kind(&self) -> ChangeKind {
+        if self.restored {
+            return ChangeKind::Restored;
+        }
+
+        match (&self.before, &self.after) {
+            (None, _) => ChangeKind::Logged,
+            (Some(_), None) => ChangeKind::Deleted,
+            (Some(_), Some(_)) => ChangeKind::Updated,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::journal::JournalChange;
+
+/// Entry of the journal recording the changes made to work items by a single operation
+/// (or transaction) so that they can be undone and redone.
+#[derive(Debug)]
+pub struct JournalEntry {
+    /// ID of the entry.
+    id: i32,
+    /// Timestamp the changes have been made (in milliseconds).
+    timestamp: i64,
+    /// Whether the changes have been undone.
+    undone: bool,
+    /// Changes of the single work items in the order they have been made.
+    changes: Vec<JournalChange>,
+}
+
+impl JournalEntry {
+    pub(crate) fn new(
+        id: i32,
+        timestamp: i64,
+        undone: bool,
+        changes: Vec<JournalChange>,
+    ) -> JournalEntry {
+        JournalEntry {
+            id,
+            timestamp,
+            undone,
+            changes,
+        }
+    }
+
+    /// Get the ID of the entry.
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    /// Get the timestamp the changes have been made (in milliseconds).
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Check whether the changes have been undone.
+    pub fn is_undone(&self) -> bool {
+        self.undone
+    }
+
+    /// Get the changes of the single work items in the order they have been made.
+    pub fn changes(&self) -> &[JournalChange] {
+        &self.changes
+    }
+
+    /// Get the IDs of the changed work items.
+    pub fn item_ids(&self) -> Vec<i32> {
+        let mut ids: Vec<i32> = self.changes.iter().map(|c| c.item_id()).collect();
+        ids.dedup();
+
+        ids
+    }
+}
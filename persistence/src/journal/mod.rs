@@ -0,0 +1,7 @@
+mod change_kind;
+mod journal_change;
+mod journal_entry;
+
+pub use change_kind::ChangeKind;
+pub use journal_change::JournalChange;
+pub use journal_entry::JournalEntry;
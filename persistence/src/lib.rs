@@ -1,70 +1,657 @@
-use std::error::Error;
-
 use calc::WorkItem;
 
-use crate::calc::Status;
+use crate::calc::{Project, Status, TrashedItem};
+use crate::journal::JournalEntry;
+use crate::query::{ItemQuery, SearchResult};
+use crate::revision::{Origin, Revision};
 
 pub mod calc;
-mod data_access;
+pub mod data_access;
+mod error;
+pub mod journal;
+pub mod query;
+mod repository;
+pub mod revision;
+
+pub use data_access::{DatabaseLocation, DATABASE_ENV_VARIABLE, PROFILE_ENV_VARIABLE};
+pub use error::Error;
+pub use repository::Repository;
+
+/// Select the database to use for all following operations.
+pub fn select_database(location: DatabaseLocation) {
+    data_access::select_location(location);
+}
+
+/// Get the location of the database currently in use.
+pub fn active_database() -> DatabaseLocation {
+    data_access::active_location()
+}
+
+/// Select the origin (CLI, UI or import) to record in the revision history
+/// for all following changes.
+pub fn select_origin(origin: Origin) {
+    revision::select_origin(origin);
+}
 
 /// Log a work calc.
 /// Will return the ID of the new item.
-pub fn log_item(item: WorkItem) -> Result<i32, Box<dyn Error>> {
-    let mut data_access = data_access::get_data_access()?;
+pub fn log_item(item: WorkItem) -> Result<i32, Error> {
+    Repository::open()?.log_item(item)
+}
 
-    Ok(data_access.log_item(item)?)
+/// Log a work item keeping its ID (e. g. when restoring a backup).
+/// Fails with `Error::AlreadyExists` when the ID is already taken.
+pub fn restore_item(item: WorkItem) -> Result<i32, Error> {
+    Repository::open()?.restore_item(item)
 }
 
 /// Update a bunch of work items.
-pub fn update_items(items: Vec<&WorkItem>) -> Result<(), Box<dyn Error>> {
-    let mut data_access = data_access::get_data_access()?;
-
-    Ok(data_access.update_items(items)?)
+pub fn update_items(items: Vec<&WorkItem>) -> Result<(), Error> {
+    Repository::open()?.update_items(items)
 }
 
-pub fn list_items() -> Result<Vec<WorkItem>, Box<dyn Error>> {
-    let data_access = data_access::get_data_access()?;
-
-    Ok(data_access.list_items()?)
+pub fn list_items() -> Result<Vec<WorkItem>, Error> {
+    Repository::open()?.list_items()
 }
 
 pub fn find_items_by_timerange(
     from_timestamp: i64,
     to_timestamp: i64,
-) -> Result<Vec<WorkItem>, Box<dyn Error>> {
-    let data_access = data_access::get_data_access()?;
+) -> Result<Vec<WorkItem>, Error> {
+    Repository::open()?.find_items_by_timerange(from_timestamp, to_timestamp)
+}
+
+/// Find all work items matching the given query.
+pub fn query_items(query: &ItemQuery) -> Result<Vec<WorkItem>, Error> {
+    Repository::open()?.query_items(query)
+}
+
+/// Search work items by the given text.
+/// Returns the matching work items ordered by relevance (best match first).
+pub fn search(text: &str) -> Result<Vec<SearchResult>, Error> {
+    Repository::open()?.search(text)
+}
 
-    Ok(data_access.filter_items(from_timestamp, to_timestamp)?)
+pub fn find_item_by_id(id: i32) -> Result<Option<WorkItem>, Error> {
+    Repository::open()?.find_item_by_id(id)
 }
 
-pub fn find_item_by_id(id: i32) -> Result<Option<WorkItem>, Box<dyn Error>> {
-    let data_access = data_access::get_data_access()?;
+/// Get a work item by its ID.
+/// Fails with `Error::NotFound` when there is no work item with the given ID.
+pub fn get_item_by_id(id: i32) -> Result<WorkItem, Error> {
+    Repository::open()?.get_item_by_id(id)
+}
+
+pub fn find_items_by_status(status: Status) -> Result<Vec<WorkItem>, Error> {
+    Repository::open()?.find_items_by_status(status)
+}
+
+/// Delete the work item with the given ID by moving it to the trash.
+pub fn delete_item(id: i32) -> Result<Option<WorkItem>, Error> {
+    Repository::open()?.delete_item(id)
+}
+
+/// Move all work items to the trash.
+pub fn clear() -> Result<(), Error> {
+    Repository::open()?.clear()
+}
+
+/// List all work items in the trash (most recently deleted first).
+pub fn list_trash() -> Result<Vec<TrashedItem>, Error> {
+    Repository::open()?.list_trash()
+}
+
+/// Restore the work item with the given ID from the trash.
+/// Fails with `Error::NotFound` when there is no work item with the given ID in the trash.
+pub fn restore_from_trash(id: i32) -> Result<WorkItem, Error> {
+    Repository::open()?.restore_from_trash(id)
+}
+
+/// Permanently delete all work items moved to the trash before the given timestamp.
+/// Returns the number of deleted work items.
+pub fn purge_trash(deleted_before: i64) -> Result<usize, Error> {
+    Repository::open()?.purge_trash(deleted_before)
+}
+
+/// Undo the latest changes recorded in the journal that have not been undone yet.
+/// Returns the undone journal entry or None if there is nothing to undo.
+pub fn undo() -> Result<Option<JournalEntry>, Error> {
+    Repository::open()?.undo()
+}
 
-    Ok(data_access.find_item_by_id(id)?)
+/// Redo the earliest changes recorded in the journal that have been undone.
+/// Returns the redone journal entry or None if there is nothing to redo.
+pub fn redo() -> Result<Option<JournalEntry>, Error> {
+    Repository::open()?.redo()
 }
 
-pub fn find_items_by_status(status: Status) -> Result<Vec<WorkItem>, Box<dyn Error>> {
-    let data_access = data_access::get_data_access()?;
+/// List the latest journal entries (newest first) up to the given limit.
+pub fn list_journal(limit: usize) -> Result<Vec<JournalEntry>, Error> {
+    Repository::open()?.list_journal(limit)
+}
 
-    Ok(data_access.find_items_by_status(status)?)
+/// Get the revision history of the work item with the given ID (oldest revision first).
+/// The history is kept even when the work item has been deleted.
+pub fn item_history(id: i32) -> Result<Vec<Revision>, Error> {
+    Repository::open()?.item_history(id)
 }
 
-pub fn delete_item(id: i32) -> Result<Option<WorkItem>, Box<dyn Error>> {
-    let mut data_access = data_access::get_data_access()?;
+/// Create a new project.
+/// Will return the ID of the new project.
+/// Fails with `Error::ProjectAlreadyExists` when the name is already taken.
+pub fn create_project(project: Project) -> Result<i32, Error> {
+    Repository::open()?.create_project(project)
+}
 
-    Ok(data_access.delete_item(id)?)
+/// Update the client, hourly rate and archived flag of a project.
+pub fn update_project(project: &Project) -> Result<(), Error> {
+    Repository::open()?.update_project(project)
 }
 
-pub fn clear() -> Result<(), Box<dyn Error>> {
-    let mut data_access = data_access::get_data_access()?;
+/// List all projects (including archived ones) sorted by their name.
+pub fn list_projects() -> Result<Vec<Project>, Error> {
+    Repository::open()?.list_projects()
+}
 
-    Ok(data_access.clear()?)
+/// Get a project by its name.
+/// Fails with `Error::ProjectNotFound` when there is no project with the given name.
+pub fn get_project_by_name(name: &str) -> Result<Project, Error> {
+    Repository::open()?.get_project_by_name(name)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::calc::event::{Event, EventType};
+    use crate::journal::ChangeKind;
+    use crate::revision::RevisionField;
+    use std::collections::HashSet;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn test_log_and_find_item_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        let mut tags = HashSet::new();
+        tags.insert(String::from("test"));
+        let id = log_item(WorkItem::new(
+            String::from("Write tests"),
+            Status::InProgress,
+            tags,
+        ))
+        .unwrap();
+
+        let item = find_item_by_id(id).unwrap().unwrap();
+        assert_eq!(item.description(), "Write tests");
+        assert_eq!(item.tags(), vec![String::from("test")]);
+        assert_eq!(find_items_by_status(Status::InProgress).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_update_and_delete_item_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        let id = log_item(WorkItem::new(
+            String::from("Write tests"),
+            Status::InProgress,
+            HashSet::new(),
+        ))
+        .unwrap();
+
+        let mut item = find_item_by_id(id).unwrap().unwrap();
+        item.pause_working(None).unwrap();
+        update_items(vec![&item]).unwrap();
+        assert_eq!(find_items_by_status(Status::Paused).unwrap().len(), 1);
+
+        assert!(delete_item(id).unwrap().is_some());
+        assert!(list_items().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trash_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        let mut ids = Vec::new();
+        for description in &["Deleted", "Cleared", "Kept"] {
+            ids.push(
+                log_item(WorkItem::new(
+                    String::from(*description),
+                    Status::Done,
+                    HashSet::new(),
+                ))
+                .unwrap(),
+            );
+        }
+
+        delete_item(ids[0]).unwrap();
+        assert!(find_item_by_id(ids[0]).unwrap().is_none());
+        assert!(delete_item(ids[0]).unwrap().is_none());
+        assert!(search("Deleted").unwrap().is_empty());
+        assert_eq!(list_items().unwrap().len(), 2);
+
+        clear().unwrap();
+        assert!(list_items().unwrap().is_empty());
+
+        let trash = list_trash().unwrap();
+        assert_eq!(trash.len(), 3);
+        assert!(trash
+            .windows(2)
+            .all(|w| w[0].deleted_timestamp() >= w[1].deleted_timestamp()));
+
+        let restored = restore_from_trash(ids[2]).unwrap();
+        assert_eq!(restored.description(), "Kept");
+        assert_eq!(list_items().unwrap().len(), 1);
+        match restore_from_trash(ids[2]) {
+            Err(Error::NotFound(id)) => assert_eq!(id, ids[2]),
+            _ => panic!("Expected a not found error"),
+        }
+
+        assert_eq!(purge_trash(0).unwrap(), 0);
+        assert_eq!(purge_trash(i64::MAX).unwrap(), 2);
+        assert!(list_trash().unwrap().is_empty());
+        assert_eq!(list_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_undo_redo_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        let id = log_item(WorkItem::new(
+            String::from("Write tests"),
+            Status::InProgress,
+            HashSet::new(),
+        ))
+        .unwrap();
+
+        let mut item = get_item_by_id(id).unwrap();
+        item.finish_working(None).unwrap();
+        update_items(vec![&item]).unwrap();
+        update_items(vec![&item]).unwrap(); // Unchanged, thus not recorded
+        delete_item(id).unwrap();
+
+        let journal = list_journal(10).unwrap();
+        let kinds: Vec<ChangeKind> = journal.iter().map(|e| e.changes()[0].kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![ChangeKind::Deleted, ChangeKind::Updated, ChangeKind::Logged]
+        );
+        assert_eq!(journal[0].item_ids(), vec![id]);
+
+        assert!(undo().unwrap().unwrap().is_undone());
+        assert_eq!(get_item_by_id(id).unwrap().status(), Status::Done);
+        undo().unwrap();
+        assert_eq!(get_item_by_id(id).unwrap().status(), Status::InProgress);
+        undo().unwrap();
+        assert!(find_item_by_id(id).unwrap().is_none());
+        assert!(list_trash().unwrap().is_empty());
+        assert!(undo().unwrap().is_none());
+
+        assert!(!redo().unwrap().unwrap().is_undone());
+        assert_eq!(get_item_by_id(id).unwrap().status(), Status::InProgress);
+        redo().unwrap();
+        assert_eq!(get_item_by_id(id).unwrap().status(), Status::Done);
+
+        // New changes discard the changes left to redo
+        clear().unwrap();
+        assert!(redo().unwrap().is_none());
+        assert_eq!(list_journal(10).unwrap().len(), 3);
+
+        undo().unwrap();
+        assert_eq!(list_items().unwrap().len(), 1);
+
+        // Undoing a restore moves the work item back to the trash
+        delete_item(id).unwrap();
+        restore_from_trash(id).unwrap();
+        assert_eq!(
+            list_journal(1).unwrap()[0].changes()[0].kind(),
+            ChangeKind::Restored
+        );
+        undo().unwrap();
+        assert!(find_item_by_id(id).unwrap().is_none());
+        assert_eq!(list_trash().unwrap().len(), 1);
+        redo().unwrap();
+        assert!(find_item_by_id(id).unwrap().is_some());
+
+        // Purged work items can not be brought back by undoing
+        delete_item(id).unwrap();
+        purge_trash(i64::MAX).unwrap();
+        assert!(list_journal(10).unwrap().is_empty());
+        assert!(undo().unwrap().is_none());
+        assert!(find_item_by_id(id).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_item_history_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        let id = log_item(WorkItem::new(
+            String::from("Write tests"),
+            Status::InProgress,
+            HashSet::new(),
+        ))
+        .unwrap();
+
+        let history = item_history(id).unwrap();
+        let fields: Vec<RevisionField> = history.iter().map(|r| r.field()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                RevisionField::Description,
+                RevisionField::Status,
+                RevisionField::Events
+            ]
+        );
+        assert!(history.iter().all(|r| r.origin() == Origin::Cli));
+        assert_eq!(history[0].old_value(), None);
+        assert_eq!(history[0].new_value(), Some("Write tests"));
+
+        select_origin(Origin::Ui);
+        let mut item = get_item_by_id(id).unwrap();
+        item.set_description(String::from("Write more tests"));
+        item.set_tags(
+            vec![String::from("b"), String::from("a")]
+                .into_iter()
+                .collect(),
+        );
+        update_items(vec![&item]).unwrap();
+        select_origin(Origin::Cli);
+
+        let history = item_history(id).unwrap();
+        assert_eq!(history.len(), 5);
+        assert_eq!(history[3].origin(), Origin::Ui);
+        assert_eq!(history[3].old_value(), Some("Write tests"));
+        assert_eq!(history[3].new_value(), Some("Write more tests"));
+        assert_eq!(history[4].field(), RevisionField::Tags);
+        assert_eq!(history[4].new_value(), Some("a, b"));
+
+        // Undoing is recorded as well
+        undo().unwrap();
+        assert_eq!(item_history(id).unwrap().len(), 7);
+        assert_eq!(item_history(id).unwrap()[6].new_value(), None);
+
+        create_project(Project::new(String::from("website"), None, None)).unwrap();
+        let mut item = get_item_by_id(id).unwrap();
+        item.set_project(Some(String::from("website")));
+        update_items(vec![&item]).unwrap();
+        let history = item_history(id).unwrap();
+        assert_eq!(history[7].field(), RevisionField::Project);
+        assert_eq!(history[7].new_value(), Some("website"));
+
+        // Moving to the trash, restoring and purging is recorded and the history survives purging
+        select_origin(Origin::Ui);
+        delete_item(id).unwrap();
+        select_origin(Origin::Cli);
+        restore_from_trash(id).unwrap();
+        delete_item(id).unwrap();
+        purge_trash(i64::MAX).unwrap();
+
+        let history = item_history(id).unwrap();
+        let trash: Vec<(Origin, Option<&str>, Option<&str>)> = history[8..]
+            .iter()
+            .map(|r| (r.origin(), r.old_value(), r.new_value()))
+            .collect();
+        assert!(history[8..]
+            .iter()
+            .all(|r| r.field() == RevisionField::Trash));
+        assert_eq!(
+            trash,
+            vec![
+                (Origin::Ui, None, Some("TRASHED")),
+                (Origin::Cli, Some("TRASHED"), None),
+                (Origin::Cli, None, Some("TRASHED")),
+                (Origin::Cli, Some("TRASHED"), Some("PURGED")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_items_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        for (description, tag) in &[("Meeting", "meeting"), ("Coding", "dev"), ("Review", "dev")] {
+            let mut tags = HashSet::new();
+            tags.insert(String::from(*tag));
+
+            log_item(WorkItem::new(
+                String::from(*description),
+                Status::InProgress,
+                tags,
+            ))
+            .unwrap();
+        }
+
+        let items = query_items(
+            &ItemQuery::new()
+                .with_tag("dev")
+                .description_contains("view")
+                .with_status(Status::InProgress),
+        )
+        .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].description(), "Review");
+
+        let items = query_items(&ItemQuery::new().without_tag("dev").limit(5)).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].description(), "Meeting");
+    }
+
+    #[test]
+    fn test_query_items_by_activity_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        // Started before the range, continued within and finished after it
+        log_item(WorkItem::new_internal(
+            -1,
+            String::from("Cross midnight"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 50),
+                Event::new(EventType::Paused, 80),
+                Event::new(EventType::Continued, 120),
+                Event::new(EventType::Finished, 250),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(find_items_by_timerange(100, 200).unwrap().len(), 1);
+        assert_eq!(find_items_by_timerange(0, 60).unwrap().len(), 1);
+
+        // Only paused during the range
+        assert!(find_items_by_timerange(85, 115).unwrap().is_empty());
+        assert!(find_items_by_timerange(250, 300).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        for description in &[
+            "Team meeting",
+            "Fix database migration",
+            "Review migration of the database schema",
+        ] {
+            log_item(WorkItem::new(
+                String::from(*description),
+                Status::Done,
+                HashSet::new(),
+            ))
+            .unwrap();
+        }
+
+        let results = search("migrat").unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].item().description(), "Fix database migration");
+        assert_eq!(
+            results[0].highlighted_snippet(|term| format!("[{}]", term)),
+            "Fix database [migration]"
+        );
+
+        // Keep the index in sync when changing descriptions
+        let mut item = find_item_by_id(results[0].item().id().unwrap())
+            .unwrap()
+            .unwrap();
+        item.set_description(String::from("Fix login"));
+        update_items(vec![&item]).unwrap();
+        assert_eq!(search("migration").unwrap().len(), 1);
+
+        // Notes are searched as well
+        let mut item = find_item_by_id(results[1].item().id().unwrap())
+            .unwrap()
+            .unwrap();
+        item.add_note(String::from("Blocked by the login rework"), None);
+        update_items(vec![&item]).unwrap();
+        let results = search("rework").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].item().id(), item.id());
+        assert!(results[0].is_note_match());
+        assert_eq!(
+            results[0].highlighted_snippet(|term| format!("[{}]", term)),
+            "Blocked by the login [rework]"
+        );
+        assert!(!search("migration").unwrap()[0].is_note_match());
+
+        delete_item(item.id().unwrap()).unwrap();
+        assert!(search("rework").unwrap().is_empty());
+
+        let id = search("login").unwrap()[0].item().id().unwrap();
+        delete_item(id).unwrap();
+        assert_eq!(search("login").unwrap().len(), 0);
+        assert!(search(" ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_item_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        let item = WorkItem::new_internal(
+            42,
+            String::from("Restored"),
+            Status::Done,
+            HashSet::new(),
+            vec![
+                Event::new(EventType::Started, 10),
+                Event::new(EventType::Finished, 20),
+            ],
+        );
+        assert_eq!(restore_item(item).unwrap(), 42);
+        assert_eq!(get_item_by_id(42).unwrap().time_taken(), 10);
+
+        let duplicate = WorkItem::new_internal(
+            42,
+            String::from("Duplicate"),
+            Status::Done,
+            HashSet::new(),
+            vec![],
+        );
+        match restore_item(duplicate) {
+            Err(Error::AlreadyExists(id)) => assert_eq!(id, 42),
+            _ => panic!("Expected an already exists error"),
+        }
+        assert_eq!(list_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_projects_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        create_project(Project::new(
+            String::from("website"),
+            Some(String::from("ACME")),
+            Some(95.0),
+        ))
+        .unwrap();
+        create_project(Project::new(String::from("internal"), None, None)).unwrap();
+        match create_project(Project::new(String::from("website"), None, None)) {
+            Err(Error::ProjectAlreadyExists(name)) => assert_eq!(name, "website"),
+            _ => panic!("Expected a project already exists error"),
+        }
+
+        let names: Vec<String> = list_projects()
+            .unwrap()
+            .iter()
+            .map(|p| p.name().to_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec![String::from("internal"), String::from("website")]
+        );
+
+        let mut project = get_project_by_name("website").unwrap();
+        assert_eq!(project.client(), Some("ACME"));
+        assert_eq!(project.hourly_rate(), Some(95.0));
+        project.set_archived(true);
+        update_project(&project).unwrap();
+        assert!(get_project_by_name("website").unwrap().is_archived());
+
+        let mut item = WorkItem::new(String::from("Landing page"), Status::Done, HashSet::new());
+        item.set_project(Some(String::from("website")));
+        let id = log_item(item).unwrap();
+        log_item(WorkItem::new(
+            String::from("Unassigned"),
+            Status::Done,
+            HashSet::new(),
+        ))
+        .unwrap();
+
+        let mut item = get_item_by_id(id).unwrap();
+        assert_eq!(item.project(), Some("website"));
+        let items = query_items(&ItemQuery::new().in_project("website")).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id(), Some(id));
+
+        item.set_project(Some(String::from("unknown")));
+        match update_items(vec![&item]) {
+            Err(Error::ProjectNotFound(name)) => assert_eq!(name, "unknown"),
+            _ => panic!("Expected a project not found error"),
+        }
+
+        item.set_project(None);
+        update_items(vec![&item]).unwrap();
+        assert_eq!(get_item_by_id(id).unwrap().project(), None);
+    }
+
+    #[test]
+    fn test_notes_in_memory() {
+        select_database(DatabaseLocation::Memory);
+
+        let mut item = WorkItem::new(String::from("Investigate"), Status::Done, HashSet::new());
+        item.add_note(String::from("Reproduced it"), Some(100));
+        let id = log_item(item).unwrap();
+
+        let mut item = get_item_by_id(id).unwrap();
+        assert_eq!(item.notes().len(), 1);
+        assert_eq!(item.notes()[0].timestamp(), 100);
+
+        item.add_note(String::from("Fixed it"), Some(200));
+        update_items(vec![&item]).unwrap();
+
+        let texts: Vec<String> = get_item_by_id(id)
+            .unwrap()
+            .notes()
+            .iter()
+            .map(|n| n.text().to_owned())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![String::from("Reproduced it"), String::from("Fixed it")]
+        );
+
+        let deleted = delete_item(id).unwrap().unwrap();
+        assert_eq!(deleted.notes().len(), 2);
+    }
+
+    #[test]
+    fn test_get_missing_item() {
+        select_database(DatabaseLocation::Memory);
+
+        match get_item_by_id(42) {
+            Err(Error::NotFound(id)) => assert_eq!(id, 42),
+            _ => panic!("Expected a not found error"),
+        }
+    }
 }
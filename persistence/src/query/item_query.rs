@@ -0,0 +1,161 @@
+use crate::calc::Status;
+use crate::query::SortOrder;
+
+/// Query for work items combining several optional filters.
+/// All given filters must match for a work item to be part of the result.
+///
+/// # Example
+///
+/// ```
+/// use persistence::calc::Status;
+/// use persistence::query::{ItemQuery, SortOrder};
+///
+/// let query = ItemQuery::new()
+///     .with_status(Status::Done)
+///     .with_tag("meeting")
+///     .sort(SortOrder::NewestFirst)
+///     .limit(10);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ItemQuery {
+    /// Inclusive lower bound of the time range.
+    from_timestamp: Option<i64>,
+    /// Exclusive upper bound of the time range.
+    to_timestamp: Option<i64>,
+    /// Work items must have one of these statuses (any status if empty).
+    statuses: Vec<Status>,
+    /// Tags a work item must all have.
+    required_tags: Vec<String>,
+    /// Tags a work item must not have.
+    excluded_tags: Vec<String>,
+    /// Name of the project a work item must be assigned to.
+    project: Option<String>,
+    /// Substring the description of a work item must contain.
+    description: Option<String>,
+    /// Order of the resulting work items.
+    sort_order: SortOrder,
+    /// Maximum number of work items to return.
+    limit: Option<u32>,
+    /// Number of work items to skip.
+    offset: Option<u32>,
+}
+
+impl ItemQuery {
+    /// Create a new query matching all work items.
+    pub fn new() -> ItemQuery {
+        ItemQuery::default()
+    }
+
+    /// Only match work items that have been worked on in the given time range of timestamps (in milliseconds).
+    /// A work item matches when any of its active intervals overlaps the range,
+    /// even if it has been started before the range.
+    /// The lower bound is inclusive, the upper bound exclusive.
+    pub fn time_range(mut self, from_timestamp: i64, to_timestamp: i64) -> ItemQuery {
+        self.from_timestamp = Some(from_timestamp);
+        self.to_timestamp = Some(to_timestamp);
+        self
+    }
+
+    /// Only match work items with the given status.
+    /// May be called multiple times to match any of the given statuses.
+    pub fn with_status(mut self, status: Status) -> ItemQuery {
+        if !self.statuses.contains(&status) {
+            self.statuses.push(status);
+        }
+        self
+    }
+
+    /// Only match work items having the given tag.
+    /// May be called multiple times to require all of the given tags.
+    pub fn with_tag(mut self, tag: &str) -> ItemQuery {
+        self.required_tags.push(tag.to_owned());
+        self
+    }
+
+    /// Only match work items not having the given tag.
+    pub fn without_tag(mut self, tag: &str) -> ItemQuery {
+        self.excluded_tags.push(tag.to_owned());
+        self
+    }
+
+    /// Only match work items assigned to the project with the given name.
+    pub fn in_project(mut self, project: &str) -> ItemQuery {
+        self.project = Some(project.to_owned());
+        self
+    }
+
+    /// Only match work items whose description contains the given string
+    /// (ignoring the case of ASCII letters only, as SQLite does not fold other characters).
+    pub fn description_contains(mut self, value: &str) -> ItemQuery {
+        self.description = Some(value.to_owned());
+        self
+    }
+
+    /// Set the order of the resulting work items.
+    pub fn sort(mut self, sort_order: SortOrder) -> ItemQuery {
+        self.sort_order = sort_order;
+        self
+    }
+
+    /// Return at most the given number of work items.
+    pub fn limit(mut self, limit: u32) -> ItemQuery {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the given number of work items.
+    pub fn offset(mut self, offset: u32) -> ItemQuery {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Get the inclusive lower bound of the time range.
+    pub fn from_timestamp(&self) -> Option<i64> {
+        self.from_timestamp
+    }
+
+    /// Get the exclusive upper bound of the time range.
+    pub fn to_timestamp(&self) -> Option<i64> {
+        self.to_timestamp
+    }
+
+    /// Get the statuses to match (any status if empty).
+    pub fn statuses(&self) -> &[Status] {
+        &self.statuses
+    }
+
+    /// Get the tags a work item must all have.
+    pub fn required_tags(&self) -> &[String] {
+        &self.required_tags
+    }
+
+    /// Get the tags a work item must not have.
+    pub fn excluded_tags(&self) -> &[String] {
+        &self.excluded_tags
+    }
+
+    /// Get the name of the project work items must be assigned to.
+    pub fn project(&self) -> Option<&str> {
+        self.project.as_deref()
+    }
+
+    /// Get the substring the description must contain.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the order of the resulting work items.
+    pub fn sort_order(&self) -> SortOrder {
+        self.sort_order
+    }
+
+    /// Get the maximum number of work items to return.
+    pub fn max_items(&self) -> Option<u32> {
+        self.limit
+    }
+
+    /// Get the number of work items to skip.
+    pub fn skipped_items(&self) -> Option<u32> {
+        self.offset
+    }
+}
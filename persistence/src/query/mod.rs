@@ -0,0 +1,8 @@
+mod item_query;
+mod search_result;
+mod sort_order;
+
+pub use item_query::ItemQuery;
+pub use search_result::SearchResult;
+pub(crate) use search_result::{HIGHLIGHT_END, HIGHLIGHT_START};
+pub use sort_order::SortOrder;
@@ -0,0 +1,96 @@
+use crate::calc::WorkItem;
+
+/// Marker starting a highlighted part of a search snippet.
+pub(crate) const HIGHLIGHT_START: &str = "\u{2}";
+
+/// Marker ending a highlighted part of a search snippet.
+pub(crate) const HIGHLIGHT_END: &str = "\u{3}";
+
+/// Work item found by a full-text search.
+#[derive(Debug)]
+pub struct SearchResult {
+    /// The matching work item.
+    item: WorkItem,
+    /// Excerpt of the matching text with the matched terms surrounded by the highlight markers.
+    snippet: String,
+    /// Relevance of the match (higher is better).
+    score: f64,
+    /// Whether the snippet is taken from a note instead of the description.
+    in_note: bool,
+}
+
+impl SearchResult {
+    pub(crate) fn new(item: WorkItem, snippet: String, score: f64, in_note: bool) -> SearchResult {
+        SearchResult {
+            item,
+            snippet,
+            score,
+            in_note,
+        }
+    }
+
+    /// Get the matching work item.
+    pub fn item(&self) -> &WorkItem {
+        &self.item
+    }
+
+    /// Get the excerpt of the matching text without any highlighting.
+    pub fn snippet(&self) -> String {
+        self.highlighted_snippet(|term| term.to_owned())
+    }
+
+    /// Get the excerpt of the matching text with every matched term
+    /// replaced by the result of the given highlight function.
+    pub fn highlighted_snippet<F>(&self, highlight: F) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        let mut parts = self.snippet.split(HIGHLIGHT_START);
+
+        let mut result = String::from(parts.next().unwrap_or(""));
+        for part in parts {
+            let mut split = part.splitn(2, HIGHLIGHT_END);
+
+            result.push_str(&highlight(split.next().unwrap_or("")));
+            result.push_str(split.next().unwrap_or(""));
+        }
+
+        result
+    }
+
+    /// Get the relevance of the match (higher is better).
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Check whether the snippet is taken from a note of the work item instead of its description.
+    pub fn is_note_match(&self) -> bool {
+        self.in_note
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::Status;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_highlighted_snippet() {
+        let result = SearchResult::new(
+            WorkItem::new(String::from("Fix bug"), Status::Done, HashSet::new()),
+            format!(
+                "{}Fix{} a {}bug{}",
+                HIGHLIGHT_START, HIGHLIGHT_END, HIGHLIGHT_START, HIGHLIGHT_END
+            ),
+            1.0,
+            false,
+        );
+
+        assert_eq!(result.snippet(), "Fix a bug");
+        assert_eq!(
+            result.highlighted_snippet(|term| format!("**{}**", term)),
+            "**Fix** a **bug**"
+        );
+    }
+}
@@ -0,0 +1,9 @@
+/// Order in which to return work items.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum SortOrder {
+    /// Work items started first come first.
+    #[default]
+    OldestFirst,
+    /// Work items started last come first.
+    NewestFirst,
+}
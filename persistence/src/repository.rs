@@ -0,0 +1,298 @@
+use crate::calc::{Project, Status, TrashedItem, WorkItem};
+use crate::data_access::{self, DataAccess, DatabaseLocation};
+use crate::journal::JournalEntry;
+use crate::query::{ItemQuery, SearchResult};
+use crate::revision::Revision;
+use crate::Error;
+
+/// Handle to a worklog database.
+/// Keeps a single connection open for its whole lifetime so that
+/// long-running consumers do not need to reopen the database for every operation.
+pub struct Repository {
+    data_access: Box<dyn DataAccess>,
+}
+
+impl Repository {
+    /// Open a repository working on the currently active database.
+    pub fn open() -> Result<Repository, Error> {
+        Ok(Repository::new(data_access::get_data_access()?))
+    }
+
+    /// Open a repository working on the database at the given location.
+    pub fn open_at(location: &DatabaseLocation) -> Result<Repository, Error> {
+        Ok(Repository::new(data_access::get_data_access_at(location)?))
+    }
+
+    /// Create a repository working on the given data access.
+    pub fn new(data_access: Box<dyn DataAccess>) -> Repository {
+        Repository { data_access }
+    }
+
+    /// Log a work item.
+    /// Will return the ID of the new item.
+    pub fn log_item(&mut self, item: WorkItem) -> Result<i32, Error> {
+        self.data_access.log_item(item)
+    }
+
+    /// Log a work item keeping its ID (e. g. when restoring a backup).
+    /// Fails with `Error::AlreadyExists` when the ID is already taken.
+    pub fn restore_item(&mut self, item: WorkItem) -> Result<i32, Error> {
+        self.data_access.restore_item(item)
+    }
+
+    /// Update a bunch of work items.
+    pub fn update_items(&mut self, items: Vec<&WorkItem>) -> Result<(), Error> {
+        self.data_access.update_items(items)
+    }
+
+    /// List all available work items.
+    pub fn list_items(&self) -> Result<Vec<WorkItem>, Error> {
+        self.data_access.list_items()
+    }
+
+    /// Find all work items matching the given query.
+    pub fn query_items(&self, query: &ItemQuery) -> Result<Vec<WorkItem>, Error> {
+        self.data_access.query_items(query)
+    }
+
+    /// Search work items by the given text.
+    /// Returns the matching work items ordered by relevance (best match first).
+    pub fn search(&self, text: &str) -> Result<Vec<SearchResult>, Error> {
+        self.data_access.search_items(text)
+    }
+
+    /// Find work items in the given time range.
+    pub fn find_items_by_timerange(
+        &self,
+        from_timestamp: i64,
+        to_timestamp: i64,
+    ) -> Result<Vec<WorkItem>, Error> {
+        self.data_access.filter_items(from_timestamp, to_timestamp)
+    }
+
+    /// Find a work item by its ID.
+    pub fn find_item_by_id(&self, id: i32) -> Result<Option<WorkItem>, Error> {
+        self.data_access.find_item_by_id(id)
+    }
+
+    /// Get a work item by its ID.
+    /// Fails with `Error::NotFound` when there is no work item with the given ID.
+    pub fn get_item_by_id(&self, id: i32) -> Result<WorkItem, Error> {
+        self.find_item_by_id(id)?.ok_or(Error::NotFound(id))
+    }
+
+    /// Find work items by the given status.
+    pub fn find_items_by_status(&self, status: Status) -> Result<Vec<WorkItem>, Error> {
+        self.data_access.find_items_by_status(status)
+    }
+
+    /// Delete a work item with the given ID by moving it to the trash.
+    /// Returns the deleted work item or None if there is no item with the given ID.
+    pub fn delete_item(&mut self, id: i32) -> Result<Option<WorkItem>, Error> {
+        self.data_access.delete_item(id)
+    }
+
+    /// Clear the database (Move all work items to the trash).
+    pub fn clear(&mut self) -> Result<(), Error> {
+        self.data_access.clear()
+    }
+
+    /// List all work items in the trash (most recently deleted first).
+    pub fn list_trash(&self) -> Result<Vec<TrashedItem>, Error> {
+        self.data_access.list_trash()
+    }
+
+    /// Restore the work item with the given ID from the trash.
+    /// Fails with `Error::NotFound` when there is no work item with the given ID in the trash.
+    pub fn restore_from_trash(&mut self, id: i32) -> Result<WorkItem, Error> {
+        self.data_access
+            .restore_from_trash(id)?
+            .ok_or(Error::NotFound(id))
+    }
+
+    /// Permanently delete all work items moved to the trash before the given timestamp.
+    /// Returns the number of deleted work items.
+    pub fn purge_trash(&mut self, deleted_before: i64) -> Result<usize, Error> {
+        self.data_access.purge_trash(deleted_before)
+    }
+
+    /// Undo the latest changes recorded in the journal that have not been undone yet.
+    /// Returns the undone journal entry or None if there is nothing to undo.
+    pub fn undo(&mut self) -> Result<Option<JournalEntry>, Error> {
+        self.data_access.undo()
+    }
+
+    /// Redo the earliest changes recorded in the journal that have been undone.
+    /// Returns the redone journal entry or None if there is nothing to redo.
+    pub fn redo(&mut self) -> Result<Option<JournalEntry>, Error> {
+        self.data_access.redo()
+    }
+
+    /// List the latest journal entries (newest first) up to the given limit.
+    pub fn list_journal(&self, limit: usize) -> Result<Vec<JournalEntry>, Error> {
+        self.data_access.list_journal(limit)
+    }
+
+    /// Get the revision history of the work item with the given ID (oldest revision first).
+    /// The history is kept even when the work item has been deleted.
+    pub fn item_history(&self, id: i32) -> Result<Vec<Revision>, Error> {
+        self.data_access.item_history(id)
+    }
+
+    /// Create a new project.
+    /// Will return the ID of the new project.
+    /// Fails with `Error::ProjectAlreadyExists` when the name is already taken.
+    pub fn create_project(&mut self, project: Project) -> Result<i32, Error> {
+        self.data_access.create_project(project)
+    }
+
+    /// Update the client, hourly rate and archived flag of a project.
+    pub fn update_project(&mut self, project: &Project) -> Result<(), Error> {
+        self.data_access.update_project(project)
+    }
+
+    /// List all projects (including archived ones) sorted by their name.
+    pub fn list_projects(&self) -> Result<Vec<Project>, Error> {
+        self.data_access.list_projects()
+    }
+
+    /// Find a project by its name.
+    pub fn find_project_by_name(&self, name: &str) -> Result<Option<Project>, Error> {
+        self.data_access.find_project_by_name(name)
+    }
+
+    /// Get a project by its name.
+    /// Fails with `Error::ProjectNotFound` when there is no project with the given name.
+    pub fn get_project_by_name(&self, name: &str) -> Result<Project, Error> {
+        self.find_project_by_name(name)?
+            .ok_or_else(|| Error::ProjectNotFound(name.to_owned()))
+    }
+
+    /// Execute the given operations in a single transaction.
+    /// The transaction is committed when the operations succeed and
+    /// rolled back otherwise.
+    /// Transactions may be nested: Rolling back a nested transaction only undoes its own
+    /// operations, while its committed operations are undone when an enclosing transaction is rolled back.
+    pub fn transaction<T, E, F>(&mut self, operations: F) -> Result<T, E>
+    where
+        E: From<Error>,
+        F: FnOnce(&mut Repository) -> Result<T, E>,
+    {
+        self.data_access.begin_transaction()?;
+
+        match operations(self) {
+            Ok(result) => {
+                self.data_access.commit_transaction()?;
+                Ok(result)
+            }
+            Err(e) => {
+                // The error of the operations is more relevant than a failing rollback
+                let _ = self.data_access.rollback_transaction();
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_access::SQLiteDataAccess;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_transaction_rollback() {
+        let mut repository = Repository::new(Box::new(SQLiteDataAccess::in_memory().unwrap()));
+
+        let result: Result<(), Error> = repository.transaction(|repository| {
+            repository.log_item(WorkItem::new(
+                String::from("Rolled back"),
+                Status::InProgress,
+                HashSet::new(),
+            ))?;
+
+            Err(Error::InvalidTransition("Abort"))
+        });
+
+        assert!(result.is_err());
+        assert!(repository.list_items().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_transaction_commit() {
+        let mut repository = Repository::new(Box::new(SQLiteDataAccess::in_memory().unwrap()));
+
+        repository
+            .transaction::<_, Error, _>(|repository| {
+                for description in &["First", "Second"] {
+                    repository.log_item(WorkItem::new(
+                        String::from(*description),
+                        Status::InProgress,
+                        HashSet::new(),
+                    ))?;
+                }
+
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(repository.list_items().unwrap().len(), 2);
+
+        // All changes of the transaction are undone at once
+        let journal = repository.list_journal(10).unwrap();
+        assert_eq!(journal.len(), 1);
+        assert_eq!(journal[0].changes().len(), 2);
+
+        repository.undo().unwrap().unwrap();
+        assert!(repository.list_items().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_nested_transactions() {
+        let mut repository = Repository::new(Box::new(SQLiteDataAccess::in_memory().unwrap()));
+        let log = |repository: &mut Repository, description: &str| {
+            repository.log_item(WorkItem::new(
+                String::from(description),
+                Status::InProgress,
+                HashSet::new(),
+            ))
+        };
+
+        repository
+            .transaction::<_, Error, _>(|repository| {
+                log(repository, "Outer")?;
+
+                // Rolling back the nested transaction keeps the changes of the enclosing one
+                let result: Result<(), Error> = repository.transaction(|repository| {
+                    log(repository, "Rolled back")?;
+                    Err(Error::InvalidTransition("Abort"))
+                });
+                assert!(result.is_err());
+
+                repository
+                    .transaction::<_, Error, _>(|repository| log(repository, "Inner").map(|_| ()))
+            })
+            .unwrap();
+
+        let descriptions: Vec<String> = repository
+            .list_items()
+            .unwrap()
+            .iter()
+            .map(|item| item.description().to_owned())
+            .collect();
+        assert_eq!(descriptions, vec!["Outer", "Inner"]);
+
+        // The nested changes are part of the journal entry of the outermost transaction
+        assert_eq!(repository.list_journal(10).unwrap().len(), 1);
+        assert_eq!(repository.list_journal(10).unwrap()[0].changes().len(), 2);
+
+        // Committed nested changes are rolled back along with the enclosing transaction
+        let result: Result<(), Error> = repository.transaction(|repository| {
+            repository
+                .transaction::<_, Error, _>(|repository| log(repository, "Nested").map(|_| ()))?;
+            Err(Error::InvalidTransition("Abort"))
+        });
+        assert!(result.is_err());
+        assert_eq!(repository.list_items().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,9 @@
+mod origin;
+mod revision;
+mod revision_field;
+
+pub(crate) use revision::field_values;
+
+pub use origin::{active_origin, select_origin, Origin};
+pub use revision::{Revision, PURGED, TRASHED};
+pub use revision_field::RevisionField;
@@ -0,0 +1,52 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::str;
+
+thread_local! {
+    /// Origin of the changes made from now on (in the current thread).
+    static SELECTED_ORIGIN: RefCell<Origin> = const { RefCell::new(Origin::Cli) };
+}
+
+/// Origin of a change to a work item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// The change has been made using the command line interface.
+    Cli,
+    /// The change has been made using the user interface.
+    Ui,
+    /// The change has been made by importing work items.
+    Import,
+}
+
+impl str::FromStr for Origin {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cli" => Ok(Origin::Cli),
+            "ui" => Ok(Origin::Ui),
+            "import" => Ok(Origin::Import),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Cli => write!(f, "cli"),
+            Origin::Ui => write!(f, "ui"),
+            Origin::Import => write!(f, "import"),
+        }
+    }
+}
+
+/// Select the origin to record for all following changes (in the current thread).
+pub fn select_origin(origin: Origin) {
+    SELECTED_ORIGIN.with(|selected| *selected.borrow_mut() = origin);
+}
+
+/// Get the origin currently recorded for changes (`Origin::Cli` unless selected otherwise).
+pub fn active_origin() -> Origin {
+    SELECTED_ORIGIN.with(|selected| *selected.borrow())
+}
@@ -0,0 +1,102 @@
+use crate::calc::WorkItem;
+use crate::revision::{Origin, RevisionField};
+
+/// Value of the trash field of work items moved to the trash.
+pub const TRASHED: &str = "TRASHED";
+
+/// Value of the trash field of work items permanently deleted.
+pub const PURGED: &str = "PURGED";
+
+/// Change of a single field of a work item recorded in its revision history.
+/// Values are stored as text: the description as is, the tags sorted and separated
+/// by commas, the project by its name, the status like `DONE`, the events as JSON array
+/// (like in the backup) and the trash state as `TRASHED` or `PURGED`.
+/// A missing value denotes an empty field (or a work item that did not exist yet).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    /// Timestamp the field has been changed (in milliseconds).
+    timestamp: i64,
+    /// Origin of the change.
+    origin: Origin,
+    /// The changed field.
+    field: RevisionField,
+    /// Value of the field before the change.
+    old_value: Option<String>,
+    /// Value of the field after the change.
+    new_value: Option<String>,
+}
+
+impl Revision {
+    pub(crate) fn new(
+        timestamp: i64,
+        origin: Origin,
+        field: RevisionField,
+        old_value: Option<String>,
+        new_value: Option<String>,
+    ) -> Revision {
+        Revision {
+            timestamp,
+            origin,
+            field,
+            old_value,
+            new_value,
+        }
+    }
+
+    /// Get the timestamp the field has been changed (in milliseconds).
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Get the origin of the change.
+    pub fn origin(&self) -> Origin {
+        self.origin
+    }
+
+    /// Get the changed field.
+    pub fn field(&self) -> RevisionField {
+        self.field
+    }
+
+    /// Get the value of the field before the change (None if it was empty).
+    pub fn old_value(&self) -> Option<&str> {
+        self.old_value.as_deref()
+    }
+
+    /// Get the value of the field after the change (None if it is empty).
+    pub fn new_value(&self) -> Option<&str> {
+        self.new_value.as_deref()
+    }
+}
+
+/// Get the values of the tracked fields of the given work item as stored in the revision history.
+pub(crate) fn field_values(item: &WorkItem) -> Vec<(RevisionField, Option<String>)> {
+    let mut tags = item.tags();
+    tags.sort();
+
+    vec![
+        (
+            RevisionField::Description,
+            Some(item.description().to_owned()),
+        ),
+        (
+            RevisionField::Tags,
+            match tags.is_empty() {
+                true => None,
+                false => Some(tags.join(", ")),
+            },
+        ),
+        (RevisionField::Project, item.project().map(|p| p.to_owned())),
+        (RevisionField::Status, Some(format!("{}", item.status()))),
+        (
+            RevisionField::Events,
+            match item.events().is_empty() {
+                true => None,
+                false => Some(
+                    serde_json::to_string(item.events())
+                        .expect("Events must always be serializable"),
+                ),
+            },
+        ),
+    ]
+}
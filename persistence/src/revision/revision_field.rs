@@ -0,0 +1,43 @@
+use std::fmt;
+use std::str;
+
+/// Field of a work item tracked in its revision history.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RevisionField {
+    Description,
+    Tags,
+    Project,
+    Status,
+    Events,
+    /// Whether the work item is in the trash (`TRASHED`) or has been purged (`PURGED`).
+    Trash,
+}
+
+impl str::FromStr for RevisionField {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DESCRIPTION" => Ok(RevisionField::Description),
+            "TAGS" => Ok(RevisionField::Tags),
+            "PROJECT" => Ok(RevisionField::Project),
+            "STATUS" => Ok(RevisionField::Status),
+            "EVENTS" => Ok(RevisionField::Events),
+            "TRASH" => Ok(RevisionField::Trash),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for RevisionField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevisionField::Description => write!(f, "DESCRIPTION"),
+            RevisionField::Tags => write!(f, "TAGS"),
+            RevisionField::Project => write!(f, "PROJECT"),
+            RevisionField::Status => write!(f, "STATUS"),
+            RevisionField::Events => write!(f, "EVENTS"),
+            RevisionField::Trash => write!(f, "TRASH"),
+        }
+    }
+}
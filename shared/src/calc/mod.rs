@@ -1,4 +1,12 @@
+mod rounding_mode;
+mod rounding_policy;
+mod rounding_scope;
 mod time_calculator;
 
+pub use rounding_mode::RoundingMode;
+pub use rounding_policy::{
+    active_rounding_policy, select_rounding_policy, RoundingPolicy, ROUNDING_ENV_VARIABLE,
+};
+pub use rounding_scope::RoundingScope;
 pub use time_calculator::calculate_unique_total_time;
 pub use time_calculator::TimeEvent;
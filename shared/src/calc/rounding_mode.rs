@@ -0,0 +1,28 @@
+use std::str;
+
+/// Direction in which durations are rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round up to the next multiple of the unit.
+    Up,
+    /// Round down to the previous multiple of the unit.
+    Down,
+    /// Round to the nearest multiple of the unit (halves are rounded up).
+    Nearest,
+}
+
+impl str::FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "up" => Ok(RoundingMode::Up),
+            "down" => Ok(RoundingMode::Down),
+            "nearest" => Ok(RoundingMode::Nearest),
+            _ => Err(format!(
+                "Unknown rounding mode '{}'. Use 'up', 'down' or 'nearest'",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::cell::RefCell;
+use std::env;
+use std::fmt;
+
+use crate::calc::{RoundingMode, RoundingScope};
+
+/// Environment variable holding the rounding policy to use (e. g. 'up:15m').
+pub const ROUNDING_ENV_VARIABLE: &str = "WORKLOG_ROUNDING";
+
+/// Special rounding policy name denoting that durations are not rounded.
+const NONE_NAME: &str = "none";
+
+/// Separator of the parts of a rounding policy specification.
+const SPEC_SEPARATOR: char = ':';
+
+thread_local! {
+    /// Rounding policy explicitly selected (for example by a command line option).
+    static SELECTED_ROUNDING_POLICY: RefCell<Option<Option<RoundingPolicy>>> =
+        const { RefCell::new(None) };
+}
+
+/// Policy used to round durations for billing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundingPolicy {
+    /// Direction to round in.
+    mode: RoundingMode,
+    /// Unit of work whose duration is rounded.
+    scope: RoundingScope,
+    /// Duration to round to a multiple of (in milliseconds).
+    unit: i64,
+    /// Minimum duration of any unit of work that has been worked on (in milliseconds).
+    minimum: i64,
+}
+
+impl RoundingPolicy {
+    pub fn new(
+        mode: RoundingMode,
+        scope: RoundingScope,
+        unit: i64,
+        minimum: i64,
+    ) -> RoundingPolicy {
+        RoundingPolicy {
+            mode,
+            scope,
+            unit,
+            minimum,
+        }
+    }
+
+    /// Parse the rounding policy from the given specification in the format
+    /// `MODE:UNIT[:SCOPE[:MINIMUM]]` (e. g. 'up:15m', 'nearest:6m:day' or 'up:6m:interval:15m').
+    /// The scope defaults to 'item'.
+    /// Returns `None` for 'none' denoting that durations are not rounded.
+    pub fn from_spec(spec: &str) -> Result<Option<RoundingPolicy>, String> {
+        if spec.trim().eq_ignore_ascii_case(NONE_NAME) {
+            return Ok(None);
+        }
+
+        let parts: Vec<&str> = spec.split(SPEC_SEPARATOR).map(|p| p.trim()).collect();
+        if parts.len() < 2 || parts.len() > 4 {
+            return Err(format!(
+                "Could not parse rounding policy '{}'. Use 'MODE:UNIT[:SCOPE[:MINIMUM]]' (e. g. 'up:15m' or 'nearest:6m:day') or 'none'",
+                spec
+            ));
+        }
+
+        let mode = parts[0].parse::<RoundingMode>()?;
+        let unit = parse_duration_millis(parts[1])?;
+        if unit <= 0 {
+            return Err(format!("The rounding unit '{}' must be positive", parts[1]));
+        }
+        let scope = match parts.get(2) {
+            Some(scope) => scope.parse::<RoundingScope>()?,
+            None => RoundingScope::Item,
+        };
+        let minimum = match parts.get(3) {
+            Some(minimum) => parse_duration_millis(minimum)?,
+            None => 0,
+        };
+
+        Ok(Some(RoundingPolicy::new(mode, scope, unit, minimum)))
+    }
+
+    /// Get the direction to round in.
+    pub fn mode(&self) -> RoundingMode {
+        self.mode
+    }
+
+    /// Get the unit of work whose duration is rounded.
+    pub fn scope(&self) -> RoundingScope {
+        self.scope
+    }
+
+    /// Get the duration to round to a multiple of (in milliseconds).
+    pub fn unit(&self) -> i64 {
+        self.unit
+    }
+
+    /// Get the minimum duration of any unit of work that has been worked on (in milliseconds).
+    pub fn minimum(&self) -> i64 {
+        self.minimum
+    }
+
+    /// Round the given duration (in milliseconds) to a multiple of the unit.
+    /// Durations of work that has been done are at least the minimum.
+    pub fn round(&self, duration: i64) -> i64 {
+        if duration <= 0 {
+            return 0;
+        }
+
+        let rounded = match self.mode {
+            RoundingMode::Up => (duration + self.unit - 1) / self.unit * self.unit,
+            RoundingMode::Down => duration / self.unit * self.unit,
+            RoundingMode::Nearest => (duration + self.unit / 2) / self.unit * self.unit,
+        };
+
+        rounded.max(self.minimum)
+    }
+}
+
+impl fmt::Display for RoundingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self.mode {
+            RoundingMode::Up => "rounded up",
+            RoundingMode::Down => "rounded down",
+            RoundingMode::Nearest => "rounded",
+        };
+        let scope = match self.scope {
+            RoundingScope::Interval => "interval",
+            RoundingScope::Item => "work item",
+            RoundingScope::Day => "day",
+        };
+
+        write!(
+            f,
+            "{} to multiples of {} per {}",
+            mode,
+            format_duration_millis(self.unit),
+            scope
+        )?;
+        if self.minimum > 0 {
+            write!(f, " (at least {})", format_duration_millis(self.minimum))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Select the rounding policy to use for all following operations (`None` to not round).
+pub fn select_rounding_policy(policy: Option<RoundingPolicy>) {
+    SELECTED_ROUNDING_POLICY.with(|selected| *selected.borrow_mut() = Some(policy));
+}
+
+/// Get the rounding policy currently in use (if any).
+/// An explicitly selected policy takes precedence over the `WORKLOG_ROUNDING` environment variable.
+/// Falls back to not rounding at all.
+pub fn active_rounding_policy() -> Option<RoundingPolicy> {
+    if let Some(policy) = SELECTED_ROUNDING_POLICY.with(|selected| *selected.borrow()) {
+        return policy;
+    }
+
+    env::var(ROUNDING_ENV_VARIABLE)
+        .ok()
+        .and_then(|spec| RoundingPolicy::from_spec(&spec).ok())
+        .flatten()
+}
+
+/// Parse the given duration like '15m' to milliseconds.
+fn parse_duration_millis(src: &str) -> Result<i64, String> {
+    crate::time::parse_duration(src)
+        .map(|seconds| seconds as i64 * 1000)
+        .map_err(|e| e.to_string())
+}
+
+/// Format the given duration (in milliseconds).
+fn format_duration_millis(duration: i64) -> String {
+    crate::time::format_duration((duration / 1000) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60 * 1000;
+
+    #[test]
+    fn test_from_spec() {
+        assert_eq!(
+            RoundingPolicy::from_spec("up:15m").unwrap(),
+            Some(RoundingPolicy::new(
+                RoundingMode::Up,
+                RoundingScope::Item,
+                15 * MINUTE,
+                0
+            ))
+        );
+        assert_eq!(
+            RoundingPolicy::from_spec(" Nearest : 6m : day : 1h ").unwrap(),
+            Some(RoundingPolicy::new(
+                RoundingMode::Nearest,
+                RoundingScope::Day,
+                6 * MINUTE,
+                60 * MINUTE
+            ))
+        );
+        assert_eq!(RoundingPolicy::from_spec("none").unwrap(), None);
+
+        assert!(RoundingPolicy::from_spec("up").is_err());
+        assert!(RoundingPolicy::from_spec("sideways:15m").is_err());
+        assert!(RoundingPolicy::from_spec("up:0m").is_err());
+        assert!(RoundingPolicy::from_spec("up:15m:week").is_err());
+    }
+
+    #[test]
+    fn test_round() {
+        let up = RoundingPolicy::new(RoundingMode::Up, RoundingScope::Item, 15 * MINUTE, 0);
+        assert_eq!(up.round(0), 0);
+        assert_eq!(up.round(1), 15 * MINUTE);
+        assert_eq!(up.round(15 * MINUTE), 15 * MINUTE);
+        assert_eq!(up.round(16 * MINUTE), 30 * MINUTE);
+
+        let down = RoundingPolicy::new(RoundingMode::Down, RoundingScope::Item, 6 * MINUTE, 0);
+        assert_eq!(down.round(11 * MINUTE), 6 * MINUTE);
+
+        let nearest =
+            RoundingPolicy::new(RoundingMode::Nearest, RoundingScope::Item, 6 * MINUTE, 0);
+        assert_eq!(nearest.round(8 * MINUTE), 6 * MINUTE);
+        assert_eq!(nearest.round(9 * MINUTE), 12 * MINUTE);
+
+        let minimum = RoundingPolicy::new(
+            RoundingMode::Down,
+            RoundingScope::Item,
+            6 * MINUTE,
+            15 * MINUTE,
+        );
+        assert_eq!(minimum.round(2 * MINUTE), 15 * MINUTE);
+        assert_eq!(minimum.round(20 * MINUTE), 18 * MINUTE);
+    }
+
+    #[test]
+    fn test_display() {
+        let policy = RoundingPolicy::from_spec("up:6m:interval:15m")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            policy.to_string(),
+            "rounded up to multiples of 6m per interval (at least 15m)"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use std::str;
+
+/// Unit of work whose duration is rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingScope {
+    /// Round every interval worked on a work item.
+    Interval,
+    /// Round the time worked on a work item.
+    Item,
+    /// Round the time worked on a day.
+    Day,
+}
+
+impl str::FromStr for RoundingScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "interval" => Ok(RoundingScope::Interval),
+            "item" => Ok(RoundingScope::Item),
+            "day" => Ok(RoundingScope::Day),
+            _ => Err(format!(
+                "Unknown rounding scope '{}'. Use 'interval', 'item' or 'day'",
+                s
+            )),
+        }
+    }
+}
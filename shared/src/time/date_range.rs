@@ -0,0 +1,226 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+
+use crate::time::time_zone::active_time_zone;
+
+/// Format of dates accepted as filter keyword.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Separator between the first and last period of a range keyword (e. g. '2021-01-01..2021-01-31').
+const RANGE_SEPARATOR: &str = "..";
+
+/// Get the current date in the active time zone.
+pub fn today() -> NaiveDate {
+    active_time_zone()
+        .date_time(chrono::Utc::now().timestamp_millis())
+        .date_naive()
+}
+
+/// Get the time range of timestamps (in milliseconds) covering the given date in the active time zone.
+/// The lower bound is inclusive, the upper bound exclusive.
+/// Days affected by a daylight saving time transition are shorter or longer than 24 hours.
+pub fn day_range(date: NaiveDate) -> (i64, i64) {
+    let time_zone = active_time_zone();
+
+    (time_zone.start_of_day(date), end_of_day(date))
+}
+
+/// Get the timestamp (in milliseconds) of the given time on the passed date in the active time zone.
+pub fn timestamp_at(date: NaiveDate, time: NaiveTime) -> i64 {
+    active_time_zone().timestamp_at(date.and_time(time))
+}
+
+/// Resolve the passed filter keyword to a time range of timestamps (in milliseconds) in the active time zone.
+/// Supported are single days ("today", "yesterday", "2020-02-02"), weeks ("this-week", "last-week",
+/// ISO weeks like "2021-W05"), months ("this-month", "last-month", "2021-01")
+/// and ranges between two of them ("2021-01-01..2021-01-31").
+/// Weeks start on Monday.
+pub fn resolve_date_range(keyword: &str) -> Result<(i64, i64), String> {
+    let (first_date, last_date) = resolve_dates(keyword)?;
+
+    Ok((
+        active_time_zone().start_of_day(first_date),
+        end_of_day(last_date),
+    ))
+}
+
+/// Get the timestamp (in milliseconds) of the first moment after the given date in the active time zone.
+/// The last supported date never ends.
+fn end_of_day(date: NaiveDate) -> i64 {
+    date.succ_opt().map_or(i64::MAX, |next_date| {
+        active_time_zone().start_of_day(next_date)
+    })
+}
+
+/// Resolve the passed keyword to the first and last date (inclusive) it covers.
+fn resolve_dates(keyword: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let keyword = keyword.trim();
+
+    if let Some(index) = keyword.find(RANGE_SEPARATOR) {
+        let (first_date, _) = resolve_period(&keyword[..index])?;
+        let (_, last_date) = resolve_period(&keyword[index + RANGE_SEPARATOR.len()..])?;
+
+        if last_date < first_date {
+            return Err(format!("The range '{}' ends before it starts", keyword));
+        }
+
+        return Ok((first_date, last_date));
+    }
+
+    resolve_period(keyword)
+}
+
+/// Resolve the passed period keyword (a day, week or month) to the first and last date (inclusive) it covers.
+fn resolve_period(keyword: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let today = today();
+    let out_of_range = || format!("The period '{}' is out of the supported range", keyword);
+
+    match keyword.trim() {
+        "today" => Ok((today, today)),
+        "yesterday" => today
+            .pred_opt()
+            .map(|yesterday| (yesterday, yesterday))
+            .ok_or_else(out_of_range),
+        "this-week" => Ok(week_of(today)),
+        "last-week" => Ok(week_of(today - Duration::weeks(1))),
+        "this-month" => month_of(today.year(), today.month()).ok_or_else(out_of_range),
+        "last-month" => month_of(today.year(), today.month())
+            .and_then(|(first_date, _)| first_date.pred_opt())
+            .and_then(|last_month| month_of(last_month.year(), last_month.month()))
+            .ok_or_else(out_of_range),
+        str => parse_period(str).ok_or_else(|| {
+            format!(
+                "Could not interpret '{}' as a date or range. Use 'today', 'yesterday', 'this-week', 'last-week', 'this-month', 'last-month', \
+                a date like '2021-01-31' (yyyy-MM-dd), an ISO week like '2021-W05', a month like '2021-01' or a range like '2021-01-01..2021-01-31'",
+                str
+            )
+        }),
+    }
+}
+
+/// Parse the given date, ISO week or month to the first and last date (inclusive) it covers.
+fn parse_period(src: &str) -> Option<(NaiveDate, NaiveDate)> {
+    if let Ok(date) = NaiveDate::parse_from_str(src, DATE_FORMAT) {
+        return Some((date, date));
+    }
+
+    let mut parts = src.splitn(2, '-');
+    let year: i32 = parts.next()?.parse().ok()?;
+    let rest = parts.next()?;
+
+    match rest.strip_prefix('W') {
+        Some(week) => {
+            let monday = NaiveDate::from_isoywd_opt(year, week.parse().ok()?, Weekday::Mon)?;
+
+            Some(week_of(monday))
+        }
+        None => month_of(year, rest.parse().ok()?),
+    }
+}
+
+/// Get the first and last date (Monday to Sunday) of the week the given date belongs to.
+fn week_of(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+
+    (monday, monday + Duration::days(6))
+}
+
+/// Get the first and last date of the given month.
+/// Returns None if the month is invalid or out of the supported range.
+fn month_of(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let first_date = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let next_month = match month {
+        12 => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1),
+    }?;
+
+    Some((first_date, next_month.pred_opt()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::time_zone::{select_time_zone, TimeZoneSetting};
+    use chrono_tz::Tz;
+
+    #[test]
+    fn test_day_range_in_time_zone() {
+        select_time_zone(TimeZoneSetting::Named(Tz::Europe__Berlin));
+
+        assert_eq!(
+            resolve_date_range("2021-01-31"),
+            Ok((1612047600000, 1612134000000))
+        );
+    }
+
+    #[test]
+    fn test_day_range_with_dst_transition() {
+        select_time_zone(TimeZoneSetting::Named(Tz::Europe__Berlin));
+
+        // Clocks have been turned forward on 2021-03-28 -> day has only 23 hours
+        let (from_timestamp, to_timestamp) =
+            day_range(NaiveDate::from_ymd_opt(2021, 3, 28).unwrap());
+        assert_eq!(to_timestamp - from_timestamp, 23 * 60 * 60 * 1000);
+
+        // Clocks have been turned back on 2021-10-31 -> day has 25 hours
+        let (from_timestamp, to_timestamp) =
+            day_range(NaiveDate::from_ymd_opt(2021, 10, 31).unwrap());
+        assert_eq!(to_timestamp - from_timestamp, 25 * 60 * 60 * 1000);
+    }
+
+    #[test]
+    fn test_resolve_relative_dates() {
+        let (yesterday_from, yesterday_to) = resolve_date_range("yesterday").unwrap();
+        let (today_from, _) = resolve_date_range("today").unwrap();
+
+        assert_eq!(yesterday_to, today_from);
+        assert!(yesterday_from < yesterday_to);
+        assert!(resolve_date_range("tomorrow-ish").is_err());
+    }
+
+    #[test]
+    fn test_resolve_periods() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            resolve_dates("2021-W05"),
+            Ok((date(2021, 2, 1), date(2021, 2, 7)))
+        );
+        // The first ISO week of 2021 starts in 2021 while the one of 2020 starts in 2019
+        assert_eq!(
+            resolve_dates("2020-W01"),
+            Ok((date(2019, 12, 30), date(2020, 1, 5)))
+        );
+        assert_eq!(
+            resolve_dates("2020-02"),
+            Ok((date(2020, 2, 1), date(2020, 2, 29)))
+        );
+        assert_eq!(
+            resolve_dates("2021-01-30..2021-W05"),
+            Ok((date(2021, 1, 30), date(2021, 2, 7)))
+        );
+        assert!(resolve_dates("2021-02-01..2021-01-31").is_err());
+        assert!(resolve_dates("2021-W54").is_err());
+        assert!(resolve_dates("2021-13").is_err());
+        assert!(resolve_dates("262143-12").is_err());
+
+        let (first_date, last_date) = resolve_dates("this-week").unwrap();
+        assert_eq!(first_date.weekday(), Weekday::Mon);
+        assert_eq!(last_date - first_date, Duration::days(6));
+
+        let (first_date, last_date) = resolve_dates("last-month").unwrap();
+        assert_eq!(first_date.day(), 1);
+        assert_eq!(
+            last_date.succ_opt(),
+            Some(resolve_dates("this-month").unwrap().0)
+        );
+    }
+
+    #[test]
+    fn test_week_range_with_dst_transition() {
+        select_time_zone(TimeZoneSetting::Named(Tz::Europe__Berlin));
+
+        // The week contains the 23 hour day of 2021-03-28
+        let (from_timestamp, to_timestamp) = resolve_date_range("2021-W12").unwrap();
+        assert_eq!(to_timestamp - from_timestamp, (7 * 24 - 1) * 60 * 60 * 1000);
+    }
+}
@@ -0,0 +1,23 @@
+use std::{error, fmt};
+
+/// Errors that may occur when parsing a duration.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DurationError {
+    /// The given string is not a duration.
+    Invalid(String),
+    /// The given duration is too long to be represented.
+    Overflow(String),
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationError::Invalid(src) => {
+                write!(f, "Could not parse time taken from given string '{}'", src)
+            }
+            DurationError::Overflow(src) => write!(f, "The duration '{}' is too long", src),
+        }
+    }
+}
+
+impl error::Error for DurationError {}
@@ -1,34 +1,40 @@
+use crate::time::DurationError;
+
 /// Parse the time taken in seconds from the given string in the format
-/// "Xh Xm Xs" where X must be a number and h, m or s are optional.
-pub fn parse_duration(src: &str) -> Result<i32, String> {
-    let mut hours = 0;
-    let mut minutes = 0;
-    let mut seconds = 0;
+/// "Xd Xh Xm Xs" where X must be a number and d, h, m or s are optional.
+/// Fails for durations that do not fit in the result.
+pub fn parse_duration(src: &str) -> Result<i32, DurationError> {
+    let mut total_seconds: i32 = 0;
 
     let mut number_buffer: String = String::new();
     for char in src.chars() {
+        let is_days = char == 'd';
         let is_hours = char == 'h';
         let is_minutes = char == 'm';
         let is_seconds = char == 's';
 
-        let consume_buffer = is_hours || is_minutes || is_seconds;
+        let consume_buffer = is_days || is_hours || is_minutes || is_seconds;
         if consume_buffer {
             // Parse number from buffer and clear it
-            let num: i32 = match number_buffer.trim().parse() {
-                Ok(res) => Ok(res),
-                Err(_) => Err(format!(
-                    "Could not parse time taken from given string '{}'",
-                    src
-                )),
-            }?;
-
-            if is_hours {
-                hours += num;
+            let num: i32 = number_buffer
+                .trim()
+                .parse()
+                .map_err(|_| DurationError::Invalid(src.to_owned()))?;
+
+            let unit_seconds = if is_days {
+                24 * 60 * 60
+            } else if is_hours {
+                60 * 60
             } else if is_minutes {
-                minutes += num;
-            } else if is_seconds {
-                seconds += num;
-            }
+                60
+            } else {
+                1
+            };
+
+            total_seconds = num
+                .checked_mul(unit_seconds)
+                .and_then(|seconds| total_seconds.checked_add(seconds))
+                .ok_or_else(|| DurationError::Overflow(src.to_owned()))?;
 
             number_buffer.clear();
         } else {
@@ -37,7 +43,7 @@ pub fn parse_duration(src: &str) -> Result<i32, String> {
         }
     }
 
-    return Ok(hours * 60 * 60 + minutes * 60 + seconds);
+    Ok(total_seconds)
 }
 
 /// Format a duration given in seconds in the form "Xh Xm Xs".
@@ -66,6 +72,27 @@ pub fn format_duration(mut seconds: u32) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parsing_days() {
+        assert_eq!(Ok(30 * 24 * 60 * 60), parse_duration("30d"));
+        assert_eq!(
+            Ok(24 * 60 * 60 + 2 * 60 * 60 + 5 * 60),
+            parse_duration("1d 2h 5m")
+        );
+        assert_eq!(
+            Err(DurationError::Overflow("100000d".to_owned())),
+            parse_duration("100000d")
+        );
+        assert_eq!(
+            Err(DurationError::Overflow("24855d 24h".to_owned())),
+            parse_duration("24855d 24h")
+        );
+        assert_eq!(
+            Err(DurationError::Invalid("xd".to_owned())),
+            parse_duration("xd")
+        );
+    }
+
     #[test]
     fn test_formatting_1s() {
         assert_eq!("1s", format_duration(1));
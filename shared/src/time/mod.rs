@@ -1,15 +1,17 @@
+mod date_range;
+mod duration_error;
 mod duration_parser;
+mod time_parser;
+mod time_zone;
 
+pub use date_range::{day_range, resolve_date_range, timestamp_at, today};
+pub use duration_error::DurationError;
 pub use duration_parser::format_duration;
 pub use duration_parser::parse_duration;
+pub use time_parser::parse_time_expression;
+pub use time_zone::{active_time_zone, select_time_zone, TimeZoneSetting, TIME_ZONE_ENV_VARIABLE};
 
-/// Get local date time for the passed timestamp.
-pub fn get_local_date_time(timestamp_millis: i64) -> chrono::DateTime<chrono::Local> {
-    let date_time: chrono::DateTime<chrono::Utc> = chrono::DateTime::from_utc(
-        chrono::NaiveDateTime::from_timestamp(timestamp_millis / 1000, 0),
-        chrono::Utc,
-    );
-
-    // Adjust UTC date time to the local timezone
-    chrono::DateTime::from(date_time)
+/// Get the date time for the passed timestamp in the active time zone.
+pub fn get_local_date_time(timestamp_millis: i64) -> chrono::DateTime<chrono::FixedOffset> {
+    active_time_zone().date_time(timestamp_millis)
 }
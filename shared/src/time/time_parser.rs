@@ -0,0 +1,132 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::time::duration_parser::parse_duration;
+use crate::time::time_zone::active_time_zone;
+
+/// Formats of times of the day.
+const TIME_FORMATS: [&str; 2] = ["%H:%M", "%H:%M:%S"];
+
+/// Formats of ISO date times without an offset.
+const DATE_TIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Parse the timestamp (in milliseconds) from the given time expression.
+/// Supported are
+/// - times of the current day ("17:30"),
+/// - times of the current or previous day ("today 09:15", "yesterday 17:00"),
+/// - durations before now ("-20m", "-1h 30m") and
+/// - ISO timestamps ("2021-01-31T17:00", "2021-01-31 17:00", "2021-01-31T17:00:00+01:00").
+///
+/// Times without an explicit offset are interpreted in the active time zone.
+pub fn parse_time_expression(src: &str) -> Result<i64, String> {
+    parse_time_expression_relative_to(src, chrono::Utc::now().timestamp_millis())
+}
+
+/// Parse the timestamp from the given time expression relative to the passed timestamp of now.
+fn parse_time_expression_relative_to(src: &str, now_millis: i64) -> Result<i64, String> {
+    let src = src.trim();
+    let error = || {
+        format!(
+            "Could not interpret '{}' as a point in time. Use a format like '17:30', 'yesterday 17:00', '-20m' or '2021-01-31T17:00'",
+            src
+        )
+    };
+
+    // Durations before now
+    if let Some(duration_str) = src.strip_prefix('-') {
+        let seconds = parse_duration(duration_str).map_err(|_| error())?;
+        if seconds <= 0 {
+            return Err(error());
+        }
+
+        return Ok(now_millis - seconds as i64 * 1000);
+    }
+
+    let time_zone = active_time_zone();
+    let today = time_zone.date_time(now_millis).date_naive();
+
+    // Times of the current or previous day
+    let mut words = src.split_whitespace();
+    let (date, time_str) = match (words.next(), words.next(), words.next()) {
+        (Some("today"), Some(time_str), None) => (Some(today), time_str),
+        (Some("yesterday"), Some(time_str), None) => {
+            (Some(today.pred_opt().ok_or_else(error)?), time_str)
+        }
+        _ => (None, src),
+    };
+    if let Some(time) = parse_time_of_day(time_str) {
+        return Ok(time_zone.timestamp_at(date.unwrap_or(today).and_time(time)));
+    } else if date.is_some() {
+        return Err(error());
+    }
+
+    // ISO timestamps
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(src) {
+        return Ok(date_time.timestamp_millis());
+    }
+    for format in &DATE_TIME_FORMATS {
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(src, format) {
+            return Ok(time_zone.timestamp_at(date_time));
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(src, "%Y-%m-%d") {
+        return Ok(time_zone.start_of_day(date));
+    }
+
+    Err(error())
+}
+
+/// Parse a time of the day ("17:30" or "17:30:15").
+fn parse_time_of_day(src: &str) -> Option<NaiveTime> {
+    TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(src, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::time_zone::{select_time_zone, TimeZoneSetting};
+    use chrono_tz::Tz;
+
+    /// 2021-01-31T12:00:00+01:00
+    const NOW: i64 = 1612090800000;
+
+    fn parse(src: &str) -> Result<i64, String> {
+        select_time_zone(TimeZoneSetting::Named(Tz::Europe__Berlin));
+
+        parse_time_expression_relative_to(src, NOW)
+    }
+
+    #[test]
+    fn test_parse_time_of_day() {
+        assert_eq!(parse("09:15"), Ok(NOW - (2 * 60 + 45) * 60 * 1000));
+        assert_eq!(parse("today 12:00:30"), Ok(NOW + 30 * 1000));
+        assert_eq!(parse("yesterday 17:00"), Ok(NOW - 19 * 60 * 60 * 1000));
+    }
+
+    #[test]
+    fn test_parse_duration_before_now() {
+        assert_eq!(parse("-20m"), Ok(NOW - 20 * 60 * 1000));
+        assert_eq!(parse("-1h 30m"), Ok(NOW - 90 * 60 * 1000));
+    }
+
+    #[test]
+    fn test_parse_iso() {
+        assert_eq!(parse("2021-01-31T12:00"), Ok(NOW));
+        assert_eq!(parse("2021-01-31 11:00:00"), Ok(NOW - 60 * 60 * 1000));
+        assert_eq!(parse("2021-01-31T12:00:00Z"), Ok(NOW + 60 * 60 * 1000));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse("tomorrow 10:00").is_err());
+        assert!(parse("yesterday noon").is_err());
+        assert!(parse("-").is_err());
+        assert!(parse("25:00").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::env;
+
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Environment variable holding the name of the time zone to use (e. g. 'Europe/Berlin').
+pub const TIME_ZONE_ENV_VARIABLE: &str = "WORKLOG_TZ";
+
+/// Special time zone name denoting the local time zone of the system.
+const LOCAL_NAME: &str = "local";
+
+thread_local! {
+    /// Time zone explicitly selected (for example by a command line option).
+    static SELECTED_TIME_ZONE: RefCell<Option<TimeZoneSetting>> = const { RefCell::new(None) };
+}
+
+/// Time zone used to determine dates and day boundaries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZoneSetting {
+    /// Local time zone of the system.
+    Local,
+    /// Time zone from the IANA time zone database.
+    Named(Tz),
+}
+
+impl TimeZoneSetting {
+    /// Get the time zone setting for the given name.
+    /// Either 'local' or the name of an IANA time zone (e. g. 'Europe/Berlin').
+    pub fn from_name(name: &str) -> Result<TimeZoneSetting, String> {
+        if name.trim().eq_ignore_ascii_case(LOCAL_NAME) {
+            return Ok(TimeZoneSetting::Local);
+        }
+
+        name.trim()
+            .parse::<Tz>()
+            .map(TimeZoneSetting::Named)
+            .map_err(|_| {
+                format!(
+                    "Unknown time zone '{}'. Use 'local' or a name like 'Europe/Berlin'",
+                    name
+                )
+            })
+    }
+
+    /// Get the date time for the passed timestamp (in milliseconds) in the time zone.
+    pub fn date_time(&self, timestamp_millis: i64) -> DateTime<FixedOffset> {
+        let date_time = Utc
+            .timestamp_opt(timestamp_millis / 1000, 0)
+            .single()
+            .expect("Timestamp must be in the supported range");
+
+        match self {
+            TimeZoneSetting::Local => fix_offset(date_time.with_timezone(&chrono::Local)),
+            TimeZoneSetting::Named(tz) => fix_offset(date_time.with_timezone(tz)),
+        }
+    }
+
+    /// Get the timestamp (in milliseconds) of the first moment of the given date in the time zone.
+    pub fn start_of_day(&self, date: NaiveDate) -> i64 {
+        self.timestamp_at(date.and_hms_opt(0, 0, 0).expect("Midnight is a valid time"))
+    }
+
+    /// Get the timestamp (in milliseconds) of the given local date time in the time zone.
+    pub fn timestamp_at(&self, date_time: NaiveDateTime) -> i64 {
+        match self {
+            TimeZoneSetting::Local => local_timestamp(&chrono::Local, date_time),
+            TimeZoneSetting::Named(tz) => local_timestamp(tz, date_time),
+        }
+    }
+}
+
+/// Select the time zone to use for all following operations.
+pub fn select_time_zone(setting: TimeZoneSetting) {
+    SELECTED_TIME_ZONE.with(|selected| *selected.borrow_mut() = Some(setting));
+}
+
+/// Get the time zone currently in use.
+/// An explicitly selected time zone takes precedence over the `WORKLOG_TZ` environment variable.
+/// Falls back to the local time zone of the system.
+pub fn active_time_zone() -> TimeZoneSetting {
+    if let Some(setting) = SELECTED_TIME_ZONE.with(|selected| *selected.borrow()) {
+        return setting;
+    }
+
+    env::var(TIME_ZONE_ENV_VARIABLE)
+        .ok()
+        .and_then(|name| TimeZoneSetting::from_name(&name).ok())
+        .unwrap_or(TimeZoneSetting::Local)
+}
+
+/// Convert the passed date time to a date time with a fixed offset.
+fn fix_offset<T: TimeZone>(date_time: DateTime<T>) -> DateTime<FixedOffset> {
+    let offset = date_time.offset().fix();
+
+    date_time.with_timezone(&offset)
+}
+
+/// Get the timestamp of the given local date time in the passed time zone.
+/// Ambiguous date times (when clocks are turned back) resolve to the earlier moment,
+/// date times skipped by a daylight saving time transition are moved forward by the length of the gap.
+fn local_timestamp<T: TimeZone>(tz: &T, date_time: NaiveDateTime) -> i64 {
+    match tz.from_local_datetime(&date_time) {
+        LocalResult::Single(date_time) => date_time.timestamp_millis(),
+        LocalResult::Ambiguous(earliest, _) => earliest.timestamp_millis(),
+        LocalResult::None => {
+            // Interpret the date time using the offset valid before the transition
+            let offset_before = tz
+                .offset_from_utc_datetime(&(date_time - chrono::Duration::days(1)))
+                .fix();
+
+            Utc.from_utc_datetime(&(date_time - offset_before))
+                .timestamp_millis()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            TimeZoneSetting::from_name("Europe/Berlin"),
+            Ok(TimeZoneSetting::Named(Tz::Europe__Berlin))
+        );
+        assert_eq!(
+            TimeZoneSetting::from_name("LOCAL"),
+            Ok(TimeZoneSetting::Local)
+        );
+        assert!(TimeZoneSetting::from_name("Mars/Olympus_Mons").is_err());
+    }
+
+    #[test]
+    fn test_start_of_day_respects_offset() {
+        let setting = TimeZoneSetting::Named(Tz::Europe__Berlin);
+
+        // 2021-01-31T00:00:00+01:00
+        assert_eq!(
+            setting.start_of_day(NaiveDate::from_ymd_opt(2021, 1, 31).unwrap()),
+            1612047600000
+        );
+    }
+
+    #[test]
+    fn test_start_of_day_with_skipped_midnight() {
+        // Midnight did not exist in Sao Paulo on 2018-11-04 (clocks jumped to 01:00)
+        let setting = TimeZoneSetting::Named(Tz::America__Sao_Paulo);
+
+        // 2018-11-04T01:00:00-02:00
+        assert_eq!(
+            setting.start_of_day(NaiveDate::from_ymd_opt(2018, 11, 4).unwrap()),
+            1541300400000
+        );
+    }
+}
@@ -13,15 +13,23 @@ pub use druid::{
     lens, AppLauncher, Color, Data, LensExt, Point, Screen, Size, UnitPoint, Widget, WidgetExt,
     WindowDesc,
 };
+use persistence::revision::Origin;
+use std::cell::RefCell;
 use std::error::Error;
+use std::rc::Rc;
 
 /// The apps title.
 const APP_TITLE: &str = "Worklog";
 
 /// Entry point of the application.
 fn main() -> Result<(), Box<dyn Error>> {
+    // Record all changes made using the UI as such in the revision history
+    persistence::select_origin(Origin::Ui);
+
+    let repository = Rc::new(RefCell::new(persistence::Repository::open()?));
+
     let state = state::UiState {
-        day: state::DayViewState::new(chrono::Local::today()),
+        day: state::DayViewState::new(repository, shared::time::today()),
     };
 
     // Create and configure main window
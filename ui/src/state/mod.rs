@@ -1,6 +1,7 @@
 mod state;
 pub(crate) mod work_item;
 
+pub(crate) use state::format_note;
 pub(crate) use state::DayViewState;
 pub(crate) use state::DayViewWorkItems;
 pub(crate) use state::SelectedWorkItemLens;
@@ -2,7 +2,9 @@ use crate::state::work_item;
 use crate::state::work_item::UiWorkItem;
 use druid::im;
 use druid::{Data, Lens};
-use persistence::calc::Status;
+use persistence::calc::{Note, Status};
+use persistence::query::ItemQuery;
+use persistence::Repository;
 use std::cell::RefCell;
 use std::error::Error;
 use std::rc::Rc;
@@ -17,8 +19,10 @@ pub(crate) struct UiState {
 /// State for a date.
 #[derive(Clone, Data, Lens)]
 pub(crate) struct DayViewState {
+    /// Repository to load and store work items with.
+    pub repository: Rc<RefCell<Repository>>,
     /// Date currently displayed.
-    pub date: Rc<chrono::Date<chrono::Local>>,
+    pub date: Rc<chrono::NaiveDate>,
     /// A currently selected work item.
     pub selected_work_item: Option<Rc<RefCell<UiWorkItem>>>,
     /// Reference to a list of work items.
@@ -44,19 +48,23 @@ impl Lens<Rc<RefCell<UiWorkItem>>, UiWorkItem> for SelectedWorkItemLens {
 
 impl DayViewState {
     /// Create new day view state for the given initial date.
-    pub fn new(date: chrono::Date<chrono::Local>) -> DayViewState {
+    pub fn new(repository: Rc<RefCell<Repository>>, date: chrono::NaiveDate) -> DayViewState {
+        let work_items = load_work_items(&repository, &date).expect("Could not load work items");
+
         DayViewState {
+            repository,
             date: Rc::new(date),
             selected_work_item: None,
-            work_items: load_work_items(&date).expect("Could not load work items"),
+            work_items,
         }
     }
 
     /// Update the day view state for the given date.
-    pub fn update(&mut self, new_date: chrono::Date<chrono::Local>) {
+    pub fn update(&mut self, new_date: chrono::NaiveDate) {
         self.unselect();
         self.date = Rc::from(new_date);
-        self.work_items = load_work_items(&new_date).expect("Could not load work items")
+        self.work_items =
+            load_work_items(&self.repository, &new_date).expect("Could not load work items")
     }
 
     /// Select a work item with the given ID.
@@ -95,12 +103,14 @@ pub(crate) struct DayViewWorkItems {
 
 /// Load work items for the given date.
 fn load_work_items(
-    date: &chrono::Date<chrono::Local>,
+    repository: &Rc<RefCell<Repository>>,
+    date: &chrono::NaiveDate,
 ) -> Result<Option<DayViewWorkItems>, Box<dyn Error>> {
-    let from_timestamp = date.and_hms(0, 0, 0).timestamp_millis();
-    let to_timestamp = date.succ().and_hms(0, 0, 0).timestamp_millis();
+    let (from_timestamp, to_timestamp) = shared::time::day_range(*date);
 
-    let items = persistence::find_items_by_timerange(from_timestamp, to_timestamp)?;
+    let items = repository
+        .borrow()
+        .query_items(&ItemQuery::new().time_range(from_timestamp, to_timestamp))?;
 
     if items.is_empty() {
         return Ok(None);
@@ -117,8 +127,11 @@ fn load_work_items(
                 Status::Paused => work_item::UiWorkItemStatus::Paused,
             },
             tags: im::Vector::from(item.tags()),
+            notes: item.notes().iter().map(format_note).collect(),
             work_item: Rc::new(RefCell::new(item)),
+            repository: Rc::clone(repository),
             tmp: String::new(),
+            new_note: String::new(),
         })));
     }
 
@@ -126,3 +139,12 @@ fn load_work_items(
         items: ui_work_items,
     }))
 }
+
+/// Format the given note for display.
+pub(crate) fn format_note(note: &Note) -> String {
+    format!(
+        "{}  {}",
+        shared::time::get_local_date_time(note.timestamp()).format("%Y-%m-%d %H:%M"),
+        note.text()
+    )
+}
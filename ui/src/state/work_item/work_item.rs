@@ -13,10 +13,16 @@ pub struct UiWorkItem {
     pub status: UiWorkItemStatus,
     /// Tags of the item.
     pub tags: im::Vector<String>,
+    /// Notes of the item formatted for display (oldest first).
+    pub notes: im::Vector<String>,
     /// Reference to the original work item.
     pub work_item: Rc<RefCell<persistence::calc::WorkItem>>,
+    /// Repository to store changes of the work item with.
+    pub repository: Rc<RefCell<persistence::Repository>>,
     /// Temporary string used for example to add a new tag to the tag list.
     pub tmp: String,
+    /// Text of the note currently being written.
+    pub new_note: String,
 }
 
 #[derive(Clone, Data, PartialEq, Debug)]
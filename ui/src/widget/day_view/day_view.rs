@@ -117,6 +117,18 @@ fn build_detail_view() -> impl Widget<UiWorkItem> {
                     .lens(lens::Unit)
                     .padding((100.0, 10.0)),
             )
+            .with_child(
+                Label::new("Notes")
+                    .with_text_size(20.0)
+                    .center()
+                    .padding((0.0, 0.0, 0.0, 10.0)),
+            )
+            .with_child(build_detail_view_notes())
+            .with_child(
+                HorizontalSeparator::new(4.0, Color::rgb(0.9, 0.9, 0.9))
+                    .lens(lens::Unit)
+                    .padding((100.0, 10.0)),
+            )
             .with_child(Label::new(|data: &UiWorkItem, _: &Env| {
                 let work_item = data.work_item.as_ref().borrow();
 
@@ -138,6 +150,56 @@ fn build_detail_view_tags() -> impl Widget<UiWorkItem> {
     })
 }
 
+fn build_detail_view_notes() -> impl Widget<UiWorkItem> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            List::new(|| {
+                Label::new(|note: &String, _: &Env| note.to_owned())
+                    .with_line_break_mode(LineBreaking::WordWrap)
+                    .padding((0.0, 2.0))
+                    .expand_width()
+            })
+            .lens(UiWorkItem::notes),
+        )
+        .with_spacer(6.0)
+        .with_child(
+            Flex::row()
+                .with_flex_child(
+                    TextBox::multiline()
+                        .with_placeholder("Add a note...")
+                        .lens(UiWorkItem::new_note)
+                        .expand_width(),
+                    1.0,
+                )
+                .with_spacer(4.0)
+                .with_child(
+                    UiButton::new(Label::new("Add").padding((4.0, 2.0)))
+                        .with_color(Color::rgb8(102, 153, 204))
+                        .on_click(|ctx, data: &mut UiWorkItem, _| {
+                            let text = data.new_note.trim().to_owned();
+                            if text.is_empty() {
+                                return;
+                            }
+
+                            // Update work item in backend
+                            let mut work_item = data.work_item.borrow_mut();
+                            work_item.add_note(text, None);
+                            data.repository
+                                .borrow_mut()
+                                .update_items(vec![&work_item])
+                                .unwrap();
+
+                            // Update UI work item
+                            data.notes = work_item.notes().iter().map(state::format_note).collect();
+                            data.new_note.clear(); // Reset to empty for the next note
+
+                            ctx.request_update();
+                        }),
+                ),
+        )
+}
+
 fn build_detail_view_status() -> impl Widget<UiWorkItem> {
     Flex::row()
         .cross_axis_alignment(CrossAxisAlignment::Center)
@@ -175,8 +237,11 @@ fn build_detail_view_status_buttons() -> impl Widget<UiWorkItem> {
 
                     // Update work item in backend
                     let mut work_item = data.work_item.borrow_mut();
-                    work_item.pause_working().unwrap();
-                    persistence::update_items(vec![&work_item]).unwrap();
+                    work_item.pause_working(None).unwrap();
+                    data.repository
+                        .borrow_mut()
+                        .update_items(vec![&work_item])
+                        .unwrap();
 
                     // Notify list item that it needs to update as well
                     ctx.submit_command(ITEM_CHANGED.with(data.id).to(ITEM_LIST_WIDGET_ID));
@@ -195,8 +260,11 @@ fn build_detail_view_status_buttons() -> impl Widget<UiWorkItem> {
 
                     // Update work item in backend
                     let mut work_item = data.work_item.borrow_mut();
-                    work_item.continue_working().unwrap();
-                    persistence::update_items(vec![&work_item]).unwrap();
+                    work_item.continue_working(None).unwrap();
+                    data.repository
+                        .borrow_mut()
+                        .update_items(vec![&work_item])
+                        .unwrap();
 
                     // Notify list item that it needs to update as well
                     ctx.submit_command(ITEM_CHANGED.with(data.id).to(ITEM_LIST_WIDGET_ID));
@@ -216,7 +284,10 @@ fn build_detail_view_status_buttons() -> impl Widget<UiWorkItem> {
                     // Update work item in backend
                     let mut work_item = data.work_item.borrow_mut();
                     work_item.finish_working(None).unwrap();
-                    persistence::update_items(vec![&work_item]).unwrap();
+                    data.repository
+                        .borrow_mut()
+                        .update_items(vec![&work_item])
+                        .unwrap();
 
                     // Notify list item that it needs to update as well
                     ctx.submit_command(ITEM_CHANGED.with(data.id).to(ITEM_LIST_WIDGET_ID));
@@ -247,7 +318,10 @@ fn build_detail_view_title() -> impl Widget<UiWorkItem> {
             // Update work item in backend
             let mut work_item = data.work_item.borrow_mut();
             work_item.set_description(data.description.to_owned());
-            persistence::update_items(vec![&work_item]).unwrap();
+            data.repository
+                .borrow_mut()
+                .update_items(vec![&work_item])
+                .unwrap();
 
             // Notify list item that it needs to update as well
             ctx.submit_command(ITEM_CHANGED.with(data.id).to(ITEM_LIST_WIDGET_ID));
@@ -300,7 +374,7 @@ fn build_work_item_widget() -> impl Widget<UiWorkItem> {
 }
 
 /// Build the header of the day view.
-fn build_header() -> impl Widget<Rc<chrono::Date<chrono::Local>>> {
+fn build_header() -> impl Widget<Rc<chrono::NaiveDate>> {
     let arrow_left_svg = icon::get_icon(icon::ARROW_LEFT);
     let arrow_right_svg = icon::get_icon(icon::ARROW_RIGHT);
 
@@ -329,8 +403,8 @@ fn build_header() -> impl Widget<Rc<chrono::Date<chrono::Local>>> {
 }
 
 /// Build the date label for the day view header.
-fn build_header_date_label() -> Label<Rc<chrono::Date<chrono::Local>>> {
-    Label::dynamic(|date_ref: &Rc<chrono::Date<chrono::Local>>, _| {
+fn build_header_date_label() -> Label<Rc<chrono::NaiveDate>> {
+    Label::dynamic(|date_ref: &Rc<chrono::NaiveDate>, _| {
         date_ref.as_ref().format("%A, %d. %B").to_string()
     })
     .with_text_size(32.0)
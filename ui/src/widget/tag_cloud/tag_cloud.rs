@@ -46,7 +46,10 @@ impl TagCloud {
                         let mut work_item = data.work_item.as_ref().borrow_mut();
                         work_item.push_tag(data.tmp.to_owned());
 
-                        persistence::update_items(vec![&work_item]).unwrap();
+                        data.repository
+                            .borrow_mut()
+                            .update_items(vec![&work_item])
+                            .unwrap();
 
                         data.tmp.clear(); // Reset to empty for the next edit
 
@@ -213,7 +216,10 @@ impl Widget<UiWorkItem> for TagCloud {
                         let mut work_item = data.work_item.as_ref().borrow_mut();
                         work_item.pop_tag(tag);
 
-                        persistence::update_items(vec![&work_item]).unwrap();
+                        data.repository
+                            .borrow_mut()
+                            .update_items(vec![&work_item])
+                            .unwrap();
 
                         ctx.request_update();
 